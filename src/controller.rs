@@ -20,6 +20,8 @@ use crate::{prelude::*, reader::ReaderError};
 pub enum Controller {
     /// 0x00
     BankSelection(DataByte),
+    /// 0x20
+    BankSelectionFine(DataByte),
     /// 0x01
     ModulationCoarse(DataByte),
     /// 0x21
@@ -97,9 +99,16 @@ impl Controller {
     pub const fn reset_all() -> Self {
         Self::ResetAllControllers(DataByte::ZERO)
     }
-    /// A controller command not listed
+    /// A controller command not listed.
+    ///
+    /// Bank selects, controllers 0 and 32, are made into [`Controller::BankSelection`]
+    /// and [`Controller::BankSelectionFine`], as they are when read.
     pub const fn other(byte_1: DataByte, byte_2: DataByte) -> Self {
-        Self::Other { byte_1, byte_2 }
+        match byte_1.value() {
+            0x00 => Self::BankSelection(byte_2),
+            0x20 => Self::BankSelectionFine(byte_2),
+            _ => Self::Other { byte_1, byte_2 },
+        }
     }
 
     pub(crate) fn read<'a, R>(reader: &mut Reader<R>) -> ReadResult<Self>
@@ -111,6 +120,7 @@ impl Controller {
         let data_byte = reader.read_next_as_databyte()?;
         let controller = match controller_byte {
             0x00 => BankSelection(data_byte),
+            0x20 => BankSelectionFine(data_byte),
             0x01 => ModulationCoarse(data_byte),
             0x21 => ModulationFine(data_byte),
             0x06 => DataEntryCoarse(data_byte),
@@ -144,6 +154,7 @@ impl Controller {
         use Controller::*;
         match self {
             BankSelection(byte) => [0x00, byte.value()],
+            BankSelectionFine(b) => [0x20, b.value()],
             ModulationCoarse(b) => [0x01, b.value()],
             ModulationFine(b) => [0x21, b.value()],
             DataEntryCoarse(b) => [0x06, b.value()],
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::{Note, channel::Channel, file::EventId};
+
 // #[derive(Debug, Error)]
 // pub enum LiveEventError {
 //     #[error("Invalid slice length: {0}")]
@@ -167,6 +169,72 @@ pub enum FileError {
     NoTiming,
 }
 
+/// Problems editing a file
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EditError {
+    /// Type 0 MIDI Format (SingleMultiChannel) must contain exactly one track
+    #[error("Type 0 MIDI Format (SingleMultiChannel) must contain exactly one track. Found {0}")]
+    SingleTrackFormat(usize),
+    /// The track index is out of bounds
+    #[error("No track at index {0}")]
+    TrackIndex(usize),
+    /// The event does not exist in the file
+    #[error("No event with id {0:?}")]
+    UnknownEvent(EventId),
+    /// The event has no velocity to change
+    #[error("Event {0:?} has no velocity")]
+    NoVelocity(EventId),
+    /// Transposing the note would move it outside of the MIDI range (0-127)
+    #[error("Transposing {note:?} by {semitones} semitones is out of range")]
+    NoteOutOfRange {
+        /// The note before transposing
+        note: Note,
+        /// The requested transposition
+        semitones: i8,
+    },
+    /// The edit needs musical time, but the file is timed in SMPTE frames
+    #[error("The file's timing is not in ticks per quarter note")]
+    SmpteTiming,
+    /// The region ends before it starts
+    #[error("The region from tick {start} to {end} ends before it starts")]
+    InvalidRegion {
+        /// The first tick of the region
+        start: u32,
+        /// The tick the region ends on
+        end: u32,
+    },
+    /// Both files use a channel, and it could not be shared or moved
+    #[error("Both files use channel {0:?}")]
+    ChannelCollision(Channel),
+    /// Tracks timed in ticks per quarter note keep a single tempo,
+    /// so a tempo map with changes can't be kept
+    #[error("A track keeps one tempo, but the tempo map has {0} tempo changes")]
+    TempoChanges(usize),
+}
+
+/// Problems reading a note name or chord symbol
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SymbolError {
+    /// There was no text to read
+    #[error("The symbol is empty")]
+    Empty,
+    /// A name didn't start with a note letter
+    #[error("Expected a note letter from A to G. Found {0:?}")]
+    Letter(char),
+    /// Text that isn't part of a symbol
+    #[error("Unrecognized text at byte {0}")]
+    Unexpected(usize),
+    /// The parts of a chord symbol can't be combined, such as a suspended minor chord
+    #[error("The chord symbol's quality and extensions don't combine")]
+    Incompatible,
+    /// A note name had no octave number after it
+    #[error("Expected an octave number after the note name")]
+    MissingOctave,
+    /// A note name was outside the MIDI range
+    #[error("The note is outside the MIDI range")]
+    OutOfRange,
+}
+
 /// An error related toe Smpte parsing
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum SmpteError {
@@ -58,6 +58,15 @@ pub enum LiveEvent<'a> {
 }
 
 impl LiveEvent<'_> {
+    /// Clone any borrowed data so that the event no longer borrows from its source
+    pub fn into_owned(self) -> LiveEvent<'static> {
+        match self {
+            LiveEvent::ChannelVoice(c) => LiveEvent::ChannelVoice(c),
+            LiveEvent::SysCommon(s) => LiveEvent::SysCommon(s.into_owned()),
+            LiveEvent::SysRealTime(r) => LiveEvent::SysRealTime(r),
+        }
+    }
+
     /// returns Some if the message is a [`ChannelVoiceMessage`].
     pub fn channel_voice(&self) -> Option<&ChannelVoiceMessage> {
         match self {
@@ -65,6 +74,14 @@ impl LiveEvent<'_> {
             _ => None,
         }
     }
+
+    /// returns Some if the message is a [`ChannelVoiceMessage`].
+    pub fn channel_voice_mut(&mut self) -> Option<&mut ChannelVoiceMessage> {
+        match self {
+            LiveEvent::ChannelVoice(c) => Some(c),
+            _ => None,
+        }
+    }
     // /// Returns the event as a set of bytes. These bytes are to be interpreted by a MIDI live stream
     // pub fn to_bytes(&self) -> Vec<u8> {
     //     match self {
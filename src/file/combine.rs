@@ -0,0 +1,365 @@
+use alloc::vec::Vec;
+
+use crate::{
+    prelude::*,
+    transform::{convert_tick_between, retime},
+};
+
+use super::convert::fill_missing;
+
+/// What to do with a channel used by both files of a [`MidiFile::append`] or [`MidiFile::overlay`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChannelPolicy {
+    /// Leave every channel as it is, so both files play on it
+    #[default]
+    Share,
+    /// Move the added file's shared channels to channels neither file uses.
+    ///
+    /// [`Channel::Ten`] is kept as it is, since it is the percussion channel.
+    Reassign,
+    /// Route the added file's channels through a map before adding it
+    Route(ChannelMap),
+    /// Refuse to combine the files
+    Fail,
+}
+
+#[doc = r#"
+Options for combining two files with [`MidiFile::append`] and [`MidiFile::overlay`].
+
+The added file is converted to the timing and tempo of the file it is added to,
+keeping its place in time. A file holds a single tempo, so if the tempos differ,
+the added file's ticks are scaled to sound the same at the new tempo.
+
+# Example
+```rust
+# use midix::prelude::*;
+fn song(tpqn: u16, channel: Channel) -> MidiFile<'static> {
+    let mut track = Track::new(Vec::new());
+    let on = VoiceEvent::note_on(note!(C, 4), Velocity::MAX);
+    let off = VoiceEvent::note_off(note!(C, 4), Velocity::ZERO);
+    track.insert(0, channel.send_event(on).into());
+    track.insert(tpqn as u32, channel.send_event(off).into());
+    MidiFile::new(Format::SingleMultiChannel(track), Timing::new_ticks_per_quarter_note(tpqn))
+}
+
+let mut medley = song(96, Channel::One);
+// a beat of silence between the songs
+let options = Combine::new().with_offset(96);
+medley.append(song(480, Channel::One), &options).unwrap();
+
+let ticks: Vec<_> = medley.tracks()[0].events().iter().map(Ticked::accumulated_ticks).collect();
+assert_eq!(ticks, [0, 96, 192, 288]);
+
+// layer a part on top, moving it off the shared channel
+let options = Combine::new().with_channel_policy(ChannelPolicy::Reassign);
+medley.overlay(song(96, Channel::One), &options).unwrap();
+let layered = medley.tracks()[0].events()[1].event().channel_voice().unwrap().channel();
+assert_eq!(layered, Channel::Two);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Combine {
+    offset: u32,
+    channels: ChannelPolicy,
+    rounding: Rounding,
+}
+
+impl Combine {
+    /// Combine files with no gap, sharing channels and rounding to the nearest tick
+    pub const fn new() -> Self {
+        Self {
+            offset: 0,
+            channels: ChannelPolicy::Share,
+            rounding: Rounding::Nearest,
+        }
+    }
+
+    /// Move the added file later by a number of ticks, in the timing of the file it is added to.
+    ///
+    /// When appending, this is the gap after the end of the file.
+    /// When overlaying, this is where the added file starts.
+    pub const fn with_offset(mut self, ticks: u32) -> Self {
+        self.offset = ticks;
+        self
+    }
+
+    /// Choose what happens to channels used by both files
+    pub const fn with_channel_policy(mut self, policy: ChannelPolicy) -> Self {
+        self.channels = policy;
+        self
+    }
+
+    /// Choose how converted positions between two ticks are rounded
+    pub const fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+}
+
+impl<'a> MidiFile<'a> {
+    /// Add another file after the end of this one.
+    ///
+    /// Each track of `other` continues the track at the same index, and any extra tracks
+    /// are added after them. A [`FormatType::SingleMultiChannel`] file keeps its single track,
+    /// and a [`FormatType::SequentiallyIndependent`] file adds `other`'s tracks as new sequences.
+    ///
+    /// # Errors
+    /// If the channels of the files can't be combined with the chosen [`ChannelPolicy`]
+    pub fn append(&mut self, other: MidiFile<'a>, options: &Combine) -> Result<(), EditError> {
+        let end = self
+            .tracks()
+            .iter()
+            .map(|track| track.end_tick())
+            .max()
+            .unwrap_or(0);
+        let tracks = self.prepare(other, end.saturating_add(options.offset), options)?;
+
+        match &mut self.format {
+            Format::SingleMultiChannel(track) => merge_into(track, tracks),
+            Format::SequentiallyIndependent(existing) => existing.extend(tracks),
+            Format::Simultaneous(existing) => {
+                for (index, track) in tracks.into_iter().enumerate() {
+                    match existing.get_mut(index) {
+                        Some(existing) => merge_into(existing, [track]),
+                        None => existing.push(track),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Play another file at the same time as this one, starting at the offset of `options`.
+    ///
+    /// `other`'s tracks are added after this file's tracks.
+    /// A [`FormatType::SingleMultiChannel`] file keeps its single track.
+    ///
+    /// # Errors
+    /// If the channels of the files can't be combined with the chosen [`ChannelPolicy`]
+    pub fn overlay(&mut self, other: MidiFile<'a>, options: &Combine) -> Result<(), EditError> {
+        let tracks = self.prepare(other, options.offset, options)?;
+        match &mut self.format {
+            Format::SingleMultiChannel(track) => merge_into(track, tracks),
+            Format::Simultaneous(existing) | Format::SequentiallyIndependent(existing) => {
+                existing.extend(tracks)
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve channels, then move `other` to this file's timing, starting at `start`
+    fn prepare(
+        &self,
+        mut other: MidiFile<'a>,
+        start: u32,
+        options: &Combine,
+    ) -> Result<Vec<Track<'a>>, EditError> {
+        resolve_channels(options.channels, self, &mut other)?;
+
+        // the added file plays at this file's tempo where it starts
+        let to_map = TempoMap::new(TempoMap::from_file(self).tempo_at(start));
+        let from_map = TempoMap::from_file(&other);
+        let (from, to) = (other.timing(), self.timing());
+        for track in other.tracks_mut() {
+            let ticks: Vec<u32> = track
+                .events()
+                .iter()
+                .map(|event| {
+                    let tick = convert_tick_between(
+                        event.accumulated_ticks(),
+                        (from, &from_map),
+                        (to, &to_map),
+                        options.rounding,
+                    );
+                    start.saturating_add(tick)
+                })
+                .collect();
+            retime(track, &ticks);
+        }
+        Ok(other.into_format().into_tracks())
+    }
+}
+
+/// Add the events of `tracks` to `track`, filling in any info it is missing
+fn merge_into<'a>(track: &mut Track<'a>, tracks: impl IntoIterator<Item = Track<'a>>) {
+    for other in tracks {
+        let (info, events) = other.into_parts();
+        fill_missing(track.info_mut(), info);
+        track.extend(events);
+    }
+}
+
+/// Returns one bit for each channel with channel voice events in the file
+fn used_channels(file: &MidiFile<'_>) -> u16 {
+    let mut used = 0;
+    file.for_each_track(|track| {
+        for event in track.events() {
+            if let Some(msg) = event.event().channel_voice() {
+                used |= 1 << msg.channel() as u8;
+            }
+        }
+    });
+    used
+}
+
+fn resolve_channels(
+    policy: ChannelPolicy,
+    file: &MidiFile<'_>,
+    other: &mut MidiFile<'_>,
+) -> Result<(), EditError> {
+    let ours = used_channels(file);
+    let theirs = used_channels(other);
+    let shared = ours & theirs;
+    let map = match policy {
+        ChannelPolicy::Share => return Ok(()),
+        ChannelPolicy::Fail => match Channel::all()
+            .into_iter()
+            .find(|channel| shared & (1 << *channel as u8) != 0)
+        {
+            Some(channel) => return Err(EditError::ChannelCollision(channel)),
+            None => return Ok(()),
+        },
+        ChannelPolicy::Route(map) => map,
+        ChannelPolicy::Reassign => {
+            let mut taken = ours | theirs;
+            let mut map = ChannelMap::new();
+            for channel in Channel::all() {
+                if channel == Channel::Ten || shared & (1 << channel as u8) == 0 {
+                    continue;
+                }
+                let free = Channel::all()
+                    .into_iter()
+                    .find(|free| *free != Channel::Ten && taken & (1 << *free as u8) == 0)
+                    .ok_or(EditError::ChannelCollision(channel))?;
+                taken |= 1 << free as u8;
+                map = map.set(channel, free);
+            }
+            map
+        }
+    };
+
+    map.apply_to_file(other);
+    for track in other.tracks_mut() {
+        let info = track.info_mut();
+        info.channel = info
+            .channel
+            .and_then(|channel| map.destinations(channel).next());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn file_on(timing: Timing, tempo: Tempo, channel: Channel, ticks: &[u32]) -> MidiFile<'static> {
+    let mut track = Track::new(Vec::new());
+    track.info_mut().tempo = tempo;
+    for tick in ticks {
+        let on = VoiceEvent::note_on(note!(C, 4), Velocity::MAX);
+        track.insert(*tick, channel.send_event(on).into());
+    }
+    MidiFile::new(Format::Simultaneous(alloc::vec![track]), timing)
+}
+
+#[cfg(test)]
+fn channels_and_ticks(track: &Track) -> Vec<(Channel, u32)> {
+    track
+        .events()
+        .iter()
+        .map(|e| {
+            let channel = e.event().channel_voice().unwrap().channel();
+            (channel, e.accumulated_ticks())
+        })
+        .collect()
+}
+
+#[test]
+fn append_keeps_time_across_tempos() {
+    let tpqn = Timing::new_ticks_per_quarter_note(96);
+    let mut file = file_on(tpqn, Tempo::new(500_000), Channel::One, &[0, 96]);
+    // twice as slow, at a finer resolution
+    let other = file_on(
+        Timing::new_ticks_per_quarter_note(192),
+        Tempo::new(1_000_000),
+        Channel::Two,
+        &[0, 192],
+    );
+    file.append(other, &Combine::new().with_offset(96)).unwrap();
+    assert_eq!(
+        channels_and_ticks(file.tracks()[0]),
+        [
+            (Channel::One, 0),
+            (Channel::One, 96),
+            (Channel::Two, 192),
+            (Channel::Two, 384)
+        ]
+    );
+}
+
+#[test]
+fn overlay_adds_tracks_to_smpte() {
+    // 1000 ticks per second
+    let smpte = Timing::new_smpte(SmpteFps::TwentyFive, DataByte::new_unchecked(40));
+    let mut file = file_on(smpte, Tempo::default(), Channel::One, &[0]);
+    let other = file_on(
+        Timing::new_ticks_per_quarter_note(96),
+        Tempo::new(500_000),
+        Channel::Three,
+        &[0, 48],
+    );
+    file.overlay(other, &Combine::new().with_offset(10))
+        .unwrap();
+    assert_eq!(file.tracks().len(), 2);
+    assert_eq!(
+        channels_and_ticks(file.tracks()[1]),
+        [(Channel::Three, 10), (Channel::Three, 260)]
+    );
+}
+
+#[test]
+fn channel_policies() {
+    let tpqn = Timing::new_ticks_per_quarter_note(96);
+    let tempo = Tempo::default();
+    let base = || {
+        let mut file = file_on(tpqn, tempo, Channel::One, &[0]);
+        file.overlay(file_on(tpqn, tempo, Channel::Two, &[0]), &Combine::new())
+            .unwrap();
+        file.overlay(file_on(tpqn, tempo, Channel::Ten, &[0]), &Combine::new())
+            .unwrap();
+        file
+    };
+    let added = || {
+        let mut file = file_on(tpqn, tempo, Channel::Two, &[5]);
+        file.tracks_mut()[0].insert(
+            6,
+            Channel::Ten
+                .send_event(VoiceEvent::note_on(note!(C, 2), Velocity::MAX))
+                .into(),
+        );
+        file
+    };
+
+    let fail = Combine::new().with_channel_policy(ChannelPolicy::Fail);
+    assert_eq!(
+        base().overlay(added(), &fail),
+        Err(EditError::ChannelCollision(Channel::Two))
+    );
+
+    let reassign = Combine::new().with_channel_policy(ChannelPolicy::Reassign);
+    let mut file = base();
+    file.overlay(added(), &reassign).unwrap();
+    assert_eq!(
+        channels_and_ticks(file.tracks()[3]),
+        [(Channel::Three, 5), (Channel::Ten, 6)]
+    );
+
+    let route = ChannelMap::new().set(Channel::Two, Channel::Sixteen);
+    let mut file = base();
+    file.overlay(
+        added(),
+        &Combine::new().with_channel_policy(ChannelPolicy::Route(route)),
+    )
+    .unwrap();
+    assert_eq!(
+        channels_and_ticks(file.tracks()[3])[0],
+        (Channel::Sixteen, 5)
+    );
+}
@@ -0,0 +1,273 @@
+use alloc::{format, vec::Vec};
+
+use crate::prelude::*;
+
+impl<'a> MidiFile<'a> {
+    /// Merge every track into one, making the file [`FormatType::SingleMultiChannel`].
+    ///
+    /// Events on the same tick keep their order, with earlier tracks first.
+    ///
+    /// The merged track keeps the first track's tempo and time signature,
+    /// and the tempo and time signature of every later track are dropped.
+    /// The name and instrument of a track with the events of one channel are kept
+    /// with the merged track, and given back to that channel by [`MidiFile::split_channels`].
+    /// Other track info is taken from the first track that has it.
+    ///
+    /// Tracks of a [`FormatType::SequentiallyIndependent`] file are separate sequences,
+    /// so merging them plays them all at once.
+    pub fn merge_tracks(&mut self) {
+        let placeholder = Format::Simultaneous(Vec::new());
+        let tracks = core::mem::replace(&mut self.format, placeholder).into_tracks();
+
+        let mut info: Option<TrackInfo<'a>> = None;
+        let mut events = Vec::new();
+        for track in tracks {
+            let channel = single_channel(&track);
+            let (mut track_info, track_events) = track.into_parts();
+            if let Some(channel) = channel
+                && (track_info.name.is_some() || track_info.instrument.is_some())
+            {
+                track_info.channel_info.push(ChannelInfo {
+                    channel,
+                    name: track_info.name.take(),
+                    instrument: track_info.instrument.take(),
+                });
+            }
+            match &mut info {
+                None => info = Some(track_info),
+                Some(info) => fill_missing(info, track_info),
+            }
+            events.extend(track_events);
+        }
+        // stable, so events on the same tick stay in track order
+        events.sort_by_key(Ticked::accumulated_ticks);
+
+        let mut info = info.unwrap_or_default();
+        info.channel = None;
+        self.format = Format::SingleMultiChannel(Track::from_ticked(info, events));
+    }
+
+    /// Split the file into one track per channel, making the file [`FormatType::Simultaneous`].
+    ///
+    /// The first track is a conductor track, holding the tempo, time signature,
+    /// key signature and any events without a channel, such as system exclusive messages.
+    /// It is followed by a track for each channel in use, in channel order.
+    ///
+    /// Each channel track keeps the name and instrument of the track its channel was merged from,
+    /// if it was one, and is otherwise named after its channel, such as `Channel 10`.
+    /// Use [`MidiFile::split_channels_with`] to name them differently.
+    pub fn split_channels(&mut self) {
+        self.split_channels_with(|channel, track| {
+            if track.info().name.is_none() {
+                let name = format!("Channel {}", channel.to_byte() + 1);
+                track.info_mut().name = Some(BytesText::new_from_bytes(name.into_bytes()));
+            }
+        });
+    }
+
+    /// Split the file into one track per channel, like [`MidiFile::split_channels`].
+    ///
+    /// `describe` is called with each new channel track, to set its name, instrument name
+    /// or any other track info. Tracks start with the name and instrument kept for their channel
+    /// by [`MidiFile::merge_tracks`], and no instrument otherwise.
+    pub fn split_channels_with<F>(&mut self, mut describe: F)
+    where
+        F: FnMut(Channel, &mut Track<'a>),
+    {
+        self.merge_tracks();
+        let placeholder = Format::Simultaneous(Vec::new());
+        let merged = core::mem::replace(&mut self.format, placeholder).into_tracks();
+        let (mut info, events) = merged
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| Track::new(Vec::new()))
+            .into_parts();
+
+        let mut conductor = Vec::new();
+        let mut channels: [Vec<Ticked<LiveEvent<'a>>>; 16] = Default::default();
+        for event in events {
+            match event.event().channel_voice() {
+                Some(msg) => channels[msg.channel().to_byte() as usize].push(event),
+                None => conductor.push(event),
+            }
+        }
+
+        let conductor_info = TrackInfo {
+            time_signature: info.time_signature,
+            key_signature: info.key_signature,
+            name: info.name,
+            device: info.device,
+            track_info: info.track_info,
+            tempo: info.tempo,
+            smpte_offset: info.smpte_offset,
+            ..Default::default()
+        };
+        let mut tracks = alloc::vec![Track::from_ticked(conductor_info, conductor)];
+        let mut channel_info = core::mem::take(&mut info.channel_info);
+
+        for (channel, events) in Channel::all().into_iter().zip(channels) {
+            if events.is_empty() {
+                continue;
+            }
+            let (name, instrument) = match channel_info.iter().position(|c| c.channel == channel) {
+                Some(index) => {
+                    let kept = channel_info.swap_remove(index);
+                    (kept.name, kept.instrument)
+                }
+                None => (None, None),
+            };
+            let info = TrackInfo {
+                channel: Some(channel),
+                name,
+                instrument,
+                ..Default::default()
+            };
+            let mut track = Track::from_ticked(info, events);
+            describe(channel, &mut track);
+            tracks.push(track);
+        }
+        self.format = Format::Simultaneous(tracks);
+    }
+}
+
+/// Fill any info that `info` is missing from `other`.
+///
+/// `info` always keeps its own tempo and time signature, so those of `other` are dropped.
+pub(super) fn fill_missing<'a>(info: &mut TrackInfo<'a>, other: TrackInfo<'a>) {
+    info.name = info.name.take().or(other.name);
+    info.device = info.device.take().or(other.device);
+    info.instrument = info.instrument.take().or(other.instrument);
+    info.track_info = info.track_info.or(other.track_info);
+    info.key_signature = info.key_signature.or(other.key_signature);
+    info.smpte_offset = info.smpte_offset.take().or(other.smpte_offset);
+    info.channel_info.extend(other.channel_info);
+}
+
+/// The channel of every channel voice event in a track, if there is exactly one
+fn single_channel(track: &Track<'_>) -> Option<Channel> {
+    let mut channels = track
+        .events()
+        .iter()
+        .filter_map(|event| event.event().channel_voice().map(|msg| msg.channel()));
+    let first = channels.next().or(track.info().channel)?;
+    channels.all(|channel| channel == first).then_some(first)
+}
+
+#[cfg(test)]
+fn note_on(channel: Channel, note: Note) -> LiveEvent<'static> {
+    channel
+        .send_event(VoiceEvent::note_on(note, Velocity::MAX))
+        .into()
+}
+
+#[test]
+fn merge_keeps_order() {
+    let mut first = Track::new(Vec::new());
+    first.info_mut().tempo = Tempo::new(400_000);
+    first.insert(0, note_on(Channel::One, note!(C, 4)));
+    first.insert(10, note_on(Channel::One, note!(D, 4)));
+
+    let mut second = Track::new(Vec::new());
+    second.info_mut().name = Some(BytesText::new_from_bytes(b"bass".as_slice()));
+    second.info_mut().tempo = Tempo::new(600_000);
+    second.insert(0, note_on(Channel::Two, note!(C, 2)));
+    second.insert(5, note_on(Channel::Two, note!(D, 2)));
+
+    let mut file = MidiFile::new(
+        Format::Simultaneous(alloc::vec![first, second]),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    file.merge_tracks();
+
+    assert_eq!(file.format_type(), FormatType::SingleMultiChannel);
+    let track = file.tracks()[0];
+    // the second track's tempo is dropped
+    assert_eq!(track.info().tempo, Tempo::new(400_000));
+    // the second track's name belongs to its channel
+    assert_eq!(track.info().name, None);
+    assert_eq!(track.info().channel_info.len(), 1);
+    assert_eq!(track.info().channel_info[0].channel, Channel::Two);
+    assert_eq!(
+        track.info().channel_info[0].name.as_ref().unwrap().as_str(),
+        Ok("bass")
+    );
+    let notes: Vec<_> = track
+        .events()
+        .iter()
+        .map(|e| e.event().channel_voice().unwrap().note().unwrap())
+        .collect();
+    assert_eq!(notes, [note!(C, 4), note!(C, 2), note!(D, 2), note!(D, 4)]);
+}
+
+#[test]
+fn split_by_channel() {
+    let mut track = Track::new(Vec::new());
+    track.info_mut().key_signature = Some(KeySignature::new(-3, true));
+    track.insert(0, note_on(Channel::Ten, note!(C, 2)));
+    track.insert(0, note_on(Channel::Three, note!(E, 4)));
+    track.insert(5, SystemExclusiveMessage::new(alloc::vec![0x7E]).into());
+    track.insert(10, note_on(Channel::Ten, note!(D, 2)));
+    let mut file = MidiFile::new(
+        Format::SingleMultiChannel(track),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+
+    file.split_channels();
+    assert_eq!(file.format_type(), FormatType::Simultaneous);
+
+    let tracks = file.tracks();
+    assert_eq!(tracks.len(), 3);
+    assert_eq!(
+        tracks[0].info().key_signature,
+        Some(KeySignature::new(-3, true))
+    );
+    assert_eq!(tracks[0].events().len(), 1);
+
+    assert_eq!(tracks[1].info().channel, Some(Channel::Three));
+    assert_eq!(
+        tracks[1].info().name.as_ref().unwrap().as_str(),
+        Ok("Channel 3")
+    );
+    assert_eq!(tracks[2].info().channel, Some(Channel::Ten));
+    assert_eq!(tracks[2].events().len(), 2);
+
+    // and back again
+    file.merge_tracks();
+    assert_eq!(file.tracks()[0].events().len(), 4);
+}
+
+#[test]
+fn names_survive_a_round_trip() {
+    let text = |text: &'static str| Some(BytesText::new_from_bytes(text.as_bytes()));
+    let mut conductor = Track::new(Vec::new());
+    conductor.info_mut().name = text("song");
+    let mut piano = Track::new(Vec::new());
+    piano.info_mut().name = text("right hand");
+    piano.info_mut().instrument = text("Piano");
+    piano.insert(0, note_on(Channel::One, note!(C, 4)));
+    let mut drums = Track::new(Vec::new());
+    drums.info_mut().name = text("kit");
+    drums.insert(0, note_on(Channel::Ten, note!(C, 2)));
+    let mut file = MidiFile::new(
+        Format::Simultaneous(alloc::vec![conductor, piano, drums]),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+
+    file.merge_tracks();
+    assert_eq!(file.tracks()[0].info().name, text("song"));
+    file.split_channels();
+
+    let names: Vec<_> = file
+        .tracks()
+        .iter()
+        .map(|track| (track.info().name.clone(), track.info().instrument.clone()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (text("song"), None),
+            (text("right hand"), text("Piano")),
+            (text("kit"), None)
+        ]
+    );
+}
@@ -0,0 +1,655 @@
+#![doc = r#"
+Reversible edits over a [`MidiFile`]
+"#]
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::prelude::*;
+
+/// A stable identifier for an event in a [`FileEditor`].
+///
+/// Identifiers are never reused, and follow their event through moves and changes,
+/// so a selection of events survives edits, undos and redos.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct EventId(u64);
+
+/// The smallest reversible change to a file.
+///
+/// Every edit is made of removing and inserting events at exact indices,
+/// so the inverse of an operation is the other operation at the same position.
+#[derive(Debug, Clone)]
+enum Op<'a> {
+    Insert {
+        track: usize,
+        index: usize,
+        id: EventId,
+        event: Ticked<LiveEvent<'a>>,
+    },
+    Remove {
+        track: usize,
+        index: usize,
+        id: EventId,
+        event: Ticked<LiveEvent<'a>>,
+    },
+}
+
+impl Op<'_> {
+    fn inverse(self) -> Self {
+        match self {
+            Op::Insert {
+                track,
+                index,
+                id,
+                event,
+            } => Op::Remove {
+                track,
+                index,
+                id,
+                event,
+            },
+            Op::Remove {
+                track,
+                index,
+                id,
+                event,
+            } => Op::Insert {
+                track,
+                index,
+                id,
+                event,
+            },
+        }
+    }
+}
+
+#[doc = r#"
+Edits a [`MidiFile`] while recording how to undo and redo every change.
+
+Each event in the file is given an [`EventId`] that is stable across edits.
+
+Every edit is its own undoable step, unless it is made within a transaction,
+in which case the whole transaction is undone or redone at once.
+
+# Example
+```rust
+# use midix::prelude::*;
+let file = MidiFile::new(
+    Format::SingleMultiChannel(Track::new(Vec::new())),
+    Timing::new_ticks_per_quarter_note(96),
+);
+let mut editor = FileEditor::new(file);
+
+let on = Channel::One.send_event(VoiceEvent::note_on(note!(C, 4), Velocity::new(64).unwrap()));
+let off = Channel::One.send_event(VoiceEvent::note_off(note!(C, 4), Velocity::ZERO));
+
+// insert a note as a single undoable step
+let (note_on, note_off) = editor.transaction(|editor| {
+    let note_on = editor.insert(0, 0, on.into()).unwrap();
+    let note_off = editor.insert(0, 96, off.into()).unwrap();
+    (note_on, note_off)
+});
+
+editor.transpose(&[note_on, note_off], 12).unwrap();
+editor.set_velocity(note_on, Velocity::MAX).unwrap();
+assert_eq!(editor.file().tracks()[0].events()[0].event().channel_voice().unwrap().note(), Some(note!(C, 5)));
+
+editor.undo();
+editor.undo();
+assert_eq!(editor.file().tracks()[0].events()[0].event(), &LiveEvent::from(on));
+
+editor.undo();
+assert!(editor.file().tracks()[0].events().is_empty());
+
+// ids survive an undo and redo
+editor.redo();
+assert_eq!(editor.tick_of(note_off), Some(96));
+```
+"#]
+#[derive(Debug, Clone)]
+pub struct FileEditor<'a> {
+    file: MidiFile<'a>,
+    /// The ids of every event, parallel to the events of each track
+    ids: Vec<Vec<EventId>>,
+    /// The track and index of every event, kept in step with `ids`
+    positions: BTreeMap<EventId, (usize, usize)>,
+    next_id: u64,
+    undo: Vec<Vec<Op<'a>>>,
+    redo: Vec<Vec<Op<'a>>>,
+    /// Operations of the currently open transaction
+    pending: Vec<Op<'a>>,
+    transaction_depth: usize,
+}
+
+impl<'a> FileEditor<'a> {
+    /// Start editing a file
+    pub fn new(file: MidiFile<'a>) -> Self {
+        let mut next_id = 0;
+        let ids = file
+            .tracks()
+            .iter()
+            .map(|track| {
+                track
+                    .events()
+                    .iter()
+                    .map(|_| {
+                        next_id += 1;
+                        EventId(next_id - 1)
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<EventId>>>();
+        let positions = ids
+            .iter()
+            .enumerate()
+            .flat_map(|(track, ids)| {
+                ids.iter()
+                    .enumerate()
+                    .map(move |(index, id)| (*id, (track, index)))
+            })
+            .collect();
+        Self {
+            file,
+            ids,
+            positions,
+            next_id,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            pending: Vec::new(),
+            transaction_depth: 0,
+        }
+    }
+
+    /// Returns the file being edited
+    pub fn file(&self) -> &MidiFile<'a> {
+        &self.file
+    }
+
+    /// Stop editing, returning the file
+    pub fn into_file(self) -> MidiFile<'a> {
+        self.file
+    }
+
+    /// Returns the ids of a track's events, in the same order as [`Track::events`]
+    pub fn ids(&self, track: usize) -> Option<&[EventId]> {
+        self.ids.get(track).map(Vec::as_slice)
+    }
+
+    /// Returns the track and index of an event
+    pub fn position(&self, id: EventId) -> Option<(usize, usize)> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Returns an event by its id
+    pub fn event(&self, id: EventId) -> Option<&Ticked<LiveEvent<'a>>> {
+        let (track, index) = self.position(id)?;
+        self.file.tracks()[track].events().get(index)
+    }
+
+    /// Returns the tick of an event by its id
+    pub fn tick_of(&self, id: EventId) -> Option<u32> {
+        self.event(id).map(Ticked::accumulated_ticks)
+    }
+
+    /// Returns true if there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns true if there is an edit to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undo the last edit or transaction.
+    ///
+    /// Returns false if there was nothing to undo.
+    ///
+    /// Any open transaction is committed first.
+    pub fn undo(&mut self) -> bool {
+        self.close_transactions();
+        let Some(ops) = self.undo.pop() else {
+            return false;
+        };
+        let inverse = ops.into_iter().rev().map(Op::inverse).collect::<Vec<_>>();
+        for op in inverse.iter().cloned() {
+            self.apply(op);
+        }
+        self.redo.push(inverse);
+        true
+    }
+
+    /// Redo the last undone edit or transaction.
+    ///
+    /// Returns false if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        self.close_transactions();
+        let Some(ops) = self.redo.pop() else {
+            return false;
+        };
+        let inverse = ops.into_iter().rev().map(Op::inverse).collect::<Vec<_>>();
+        for op in inverse.iter().cloned() {
+            self.apply(op);
+        }
+        self.undo.push(inverse);
+        true
+    }
+
+    /// Group every following edit into one undoable step, until
+    /// [`FileEditor::commit_transaction`] is called.
+    ///
+    /// Transactions may be nested. Only the outermost transaction forms an undoable step.
+    pub fn begin_transaction(&mut self) {
+        self.transaction_depth += 1;
+    }
+
+    /// Close the innermost open transaction
+    pub fn commit_transaction(&mut self) {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+        if self.transaction_depth == 0 && !self.pending.is_empty() {
+            let ops = core::mem::take(&mut self.pending);
+            self.undo.push(ops);
+        }
+    }
+
+    /// Run a closure within a transaction
+    pub fn transaction<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.begin_transaction();
+        let result = f(self);
+        self.commit_transaction();
+        result
+    }
+
+    /// Insert an event into a track at a tick.
+    ///
+    /// The event is placed after any events already on that tick.
+    ///
+    /// # Errors
+    /// If the track does not exist
+    pub fn insert(
+        &mut self,
+        track: usize,
+        tick: u32,
+        event: LiveEvent<'a>,
+    ) -> Result<EventId, EditError> {
+        let events = self
+            .file
+            .tracks()
+            .get(track)
+            .ok_or(EditError::TrackIndex(track))?
+            .events();
+        let index = events.partition_point(|e| e.accumulated_ticks() <= tick);
+
+        let id = EventId(self.next_id);
+        self.next_id += 1;
+        self.record(vec_of(Op::Insert {
+            track,
+            index,
+            id,
+            event: Ticked::new(tick, event),
+        }));
+        Ok(id)
+    }
+
+    /// Delete an event
+    ///
+    /// # Errors
+    /// If the event does not exist
+    pub fn delete(&mut self, id: EventId) -> Result<Ticked<LiveEvent<'a>>, EditError> {
+        let op = self.remove_op(id)?;
+        let Op::Remove { event, .. } = &op else {
+            unreachable!()
+        };
+        let event = event.clone();
+        self.record(vec_of(op));
+        Ok(event)
+    }
+
+    /// Move an event to a new tick.
+    ///
+    /// The event is placed after any events already on that tick.
+    ///
+    /// # Errors
+    /// If the event does not exist
+    pub fn move_event(&mut self, id: EventId, to_tick: u32) -> Result<(), EditError> {
+        self.replace(id, |event| Ok(Ticked::new(to_tick, event.into_event())))
+    }
+
+    /// Change the velocity of an event
+    ///
+    /// # Errors
+    /// - If the event does not exist
+    /// - If the event has no velocity
+    pub fn set_velocity(&mut self, id: EventId, velocity: Velocity) -> Result<(), EditError> {
+        self.replace(id, |mut event| {
+            let value = event
+                .event_mut()
+                .channel_voice_mut()
+                .and_then(|msg| msg.event.velocity_mut())
+                .ok_or(EditError::NoVelocity(id))?;
+            *value = velocity;
+            Ok(event)
+        })
+    }
+
+    /// Transpose a set of events by a number of semitones.
+    ///
+    /// Events without a note are left unchanged.
+    /// The edit is a single undoable step.
+    ///
+    /// # Errors
+    /// - If an event does not exist
+    /// - If a note would be moved outside of the MIDI range. No events are changed.
+    pub fn transpose(&mut self, ids: &[EventId], semitones: i8) -> Result<(), EditError> {
+        // each event is transposed once, however often it is selected
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        // validate first, so the edit is all or nothing
+        for id in &ids {
+            let event = self.event(*id).ok_or(EditError::UnknownEvent(*id))?;
+            if let Some(note) = event.event().channel_voice().and_then(|msg| msg.note())
+                && note.checked_transpose(semitones).is_none()
+            {
+                return Err(EditError::NoteOutOfRange { note, semitones });
+            }
+        }
+        self.transaction(|editor| {
+            for id in ids {
+                editor.replace(id, |mut event| {
+                    if let Some(note) = event
+                        .event_mut()
+                        .channel_voice_mut()
+                        .and_then(|msg| msg.event.note_mut())
+                    {
+                        *note =
+                            note.checked_transpose(semitones)
+                                .ok_or(EditError::NoteOutOfRange {
+                                    note: *note,
+                                    semitones,
+                                })?;
+                    }
+                    Ok(event)
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Move a set of events to the nearest multiple of `grid` ticks.
+    ///
+    /// The edit is a single undoable step.
+    ///
+    /// # Errors
+    /// If an event does not exist. No events are changed.
+    pub fn quantize(&mut self, ids: &[EventId], grid: u32) -> Result<(), EditError> {
+        if let Some(id) = ids.iter().find(|id| self.position(**id).is_none()) {
+            return Err(EditError::UnknownEvent(*id));
+        }
+        if grid == 0 {
+            return Ok(());
+        }
+        self.transaction(|editor| {
+            for id in ids {
+                let tick = editor.tick_of(*id).unwrap();
+                let quantized = tick.saturating_add(grid / 2) / grid * grid;
+                if quantized != tick {
+                    editor.move_event(*id, quantized)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Remove an event, change it, and insert it again, as one step
+    fn replace<F>(&mut self, id: EventId, f: F) -> Result<(), EditError>
+    where
+        F: FnOnce(Ticked<LiveEvent<'a>>) -> Result<Ticked<LiveEvent<'a>>, EditError>,
+    {
+        let remove = self.remove_op(id)?;
+        let Op::Remove {
+            track,
+            index,
+            event,
+            ..
+        } = &remove
+        else {
+            unreachable!()
+        };
+        let (track, index) = (*track, *index);
+        let event = f(event.clone())?;
+
+        let events = self.file.tracks()[track].events();
+        let tick = event.accumulated_ticks();
+        // where the event would go, once it has been removed
+        let mut new_index = events.partition_point(|e| e.accumulated_ticks() <= tick);
+        if new_index > index {
+            new_index -= 1;
+        }
+        // keep the event's place within its tick if it didn't move
+        if events[index].accumulated_ticks() == tick {
+            new_index = index;
+        }
+
+        self.record(alloc::vec![
+            remove,
+            Op::Insert {
+                track,
+                index: new_index,
+                id,
+                event,
+            },
+        ]);
+        Ok(())
+    }
+
+    fn remove_op(&self, id: EventId) -> Result<Op<'a>, EditError> {
+        let (track, index) = self.position(id).ok_or(EditError::UnknownEvent(id))?;
+        let event = self.file.tracks()[track].events()[index].clone();
+        Ok(Op::Remove {
+            track,
+            index,
+            id,
+            event,
+        })
+    }
+
+    /// Apply operations and add them to the history
+    fn record(&mut self, ops: Vec<Op<'a>>) {
+        for op in ops.iter().cloned() {
+            self.apply(op);
+        }
+        self.redo.clear();
+        if self.transaction_depth > 0 {
+            self.pending.extend(ops);
+        } else {
+            self.undo.push(ops);
+        }
+    }
+
+    fn apply(&mut self, op: Op<'a>) {
+        match op {
+            Op::Insert {
+                track,
+                index,
+                id,
+                event,
+            } => {
+                self.file.tracks_mut()[track].events.insert(index, event);
+                self.ids[track].insert(index, id);
+                self.positions.insert(id, (track, index));
+                self.reindex(track, index + 1);
+            }
+            Op::Remove {
+                track, index, id, ..
+            } => {
+                self.file.tracks_mut()[track].events.remove(index);
+                self.ids[track].remove(index);
+                self.positions.remove(&id);
+                self.reindex(track, index);
+            }
+        }
+    }
+
+    /// Update the positions of a track's events from `from` onwards, after they have shifted
+    fn reindex(&mut self, track: usize, from: usize) {
+        for (index, id) in self.ids[track].iter().enumerate().skip(from) {
+            if let Some(position) = self.positions.get_mut(id) {
+                *position = (track, index);
+            }
+        }
+    }
+
+    fn close_transactions(&mut self) {
+        if self.transaction_depth > 0 {
+            self.transaction_depth = 1;
+            self.commit_transaction();
+        }
+    }
+}
+
+fn vec_of<T>(item: T) -> Vec<T> {
+    alloc::vec![item]
+}
+
+#[cfg(test)]
+fn editor_with_notes(notes: &[(u32, u8)]) -> (FileEditor<'static>, Vec<EventId>) {
+    let events = notes
+        .iter()
+        .map(|(tick, note)| {
+            let event = VoiceEvent::note_on(Note::from_databyte(*note).unwrap(), Velocity::MAX);
+            Ticked::new(*tick, Channel::One.send_event(event).into())
+        })
+        .collect();
+    let file = MidiFile::new(
+        Format::SingleMultiChannel(Track::from_ticked(TrackInfo::default(), events)),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    let editor = FileEditor::new(file);
+    let ids = editor.ids(0).unwrap().to_vec();
+    (editor, ids)
+}
+
+#[cfg(test)]
+fn note_event(note: u8) -> LiveEvent<'static> {
+    let event = VoiceEvent::note_on(Note::from_databyte(note).unwrap(), Velocity::MAX);
+    Channel::One.send_event(event).into()
+}
+
+#[cfg(test)]
+fn state(editor: &FileEditor) -> Vec<(u32, u8)> {
+    editor.file().tracks()[0]
+        .events()
+        .iter()
+        .map(|e| {
+            let note = e.event().channel_voice().unwrap().note().unwrap();
+            (e.accumulated_ticks(), note.byte())
+        })
+        .collect()
+}
+
+#[test]
+fn undo_redo_moves() {
+    let (mut editor, ids) = editor_with_notes(&[(0, 60), (10, 62), (20, 64)]);
+    let original = state(&editor);
+
+    editor.move_event(ids[0], 15).unwrap();
+    assert_eq!(state(&editor), [(10, 62), (15, 60), (20, 64)]);
+    editor.delete(ids[2]).unwrap();
+    assert_eq!(state(&editor), [(10, 62), (15, 60)]);
+
+    assert!(editor.undo());
+    assert!(editor.undo());
+    assert!(!editor.undo());
+    assert_eq!(state(&editor), original);
+    assert_eq!(editor.ids(0).unwrap(), ids);
+
+    assert!(editor.redo());
+    assert_eq!(state(&editor), [(10, 62), (15, 60), (20, 64)]);
+    assert_eq!(editor.tick_of(ids[0]), Some(15));
+}
+
+#[test]
+fn new_edit_clears_redo() {
+    let (mut editor, ids) = editor_with_notes(&[(0, 60)]);
+    editor.move_event(ids[0], 5).unwrap();
+    editor.undo();
+    assert!(editor.can_redo());
+    editor.set_velocity(ids[0], Velocity::ZERO).unwrap();
+    assert!(!editor.can_redo());
+}
+
+#[test]
+fn quantize_at_the_last_tick() {
+    let (mut editor, ids) = editor_with_notes(&[(u32::MAX - 1, 60)]);
+    editor.quantize(&ids, 10).unwrap();
+    assert_eq!(state(&editor), [(u32::MAX / 10 * 10, 60)]);
+}
+
+#[test]
+fn transactions_undo_together() {
+    let (mut editor, ids) = editor_with_notes(&[(1, 60), (11, 62), (22, 64)]);
+    editor.quantize(&ids, 10).unwrap();
+    assert_eq!(state(&editor), [(0, 60), (10, 62), (20, 64)]);
+
+    editor.begin_transaction();
+    editor.transpose(&ids[..2], -2).unwrap();
+    editor.delete(ids[2]).unwrap();
+    editor.commit_transaction();
+    assert_eq!(state(&editor), [(0, 58), (10, 60)]);
+
+    editor.undo();
+    assert_eq!(state(&editor), [(0, 60), (10, 62), (20, 64)]);
+    editor.undo();
+    assert_eq!(state(&editor), [(1, 60), (11, 62), (22, 64)]);
+}
+
+#[test]
+fn transpose_selected_twice() {
+    let (mut editor, ids) = editor_with_notes(&[(0, 60), (0, 120)]);
+    editor.transpose(&[ids[1], ids[1]], 5).unwrap();
+    assert_eq!(state(&editor), [(0, 60), (0, 125)]);
+    assert_eq!(
+        editor.transpose(&[ids[1], ids[1]], 5),
+        Err(EditError::NoteOutOfRange {
+            note: Note::from_databyte(125).unwrap(),
+            semitones: 5
+        })
+    );
+}
+
+#[test]
+fn positions_follow_edits() {
+    let (mut editor, ids) = editor_with_notes(&[(0, 60), (10, 62), (20, 64), (30, 65)]);
+    editor.move_event(ids[0], 25).unwrap();
+    editor.delete(ids[1]).unwrap();
+    let new = editor.insert(0, 0, note_event(67)).unwrap();
+    for (index, id) in [new, ids[2], ids[0], ids[3]].into_iter().enumerate() {
+        assert_eq!(editor.position(id), Some((0, index)));
+    }
+    assert_eq!(editor.position(ids[1]), None);
+    editor.undo();
+    editor.undo();
+    assert_eq!(editor.position(ids[1]), Some((0, 0)));
+    assert_eq!(editor.position(new), None);
+    editor.redo();
+    for (index, id) in editor.ids(0).unwrap().iter().enumerate() {
+        assert_eq!(editor.position(*id), Some((0, index)));
+    }
+}
+
+#[test]
+fn transpose_is_all_or_nothing() {
+    let (mut editor, ids) = editor_with_notes(&[(0, 60), (0, 120)]);
+    assert_eq!(
+        editor.transpose(&ids, 10),
+        Err(EditError::NoteOutOfRange {
+            note: Note::from_databyte(120).unwrap(),
+            semitones: 10
+        })
+    );
+    assert_eq!(state(&editor), [(0, 60), (0, 120)]);
+    assert!(!editor.can_undo());
+}
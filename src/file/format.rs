@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 
 use super::track::Track;
+use crate::EditError;
 /*
 TODO: Use this for the MIDI file type.
 
@@ -31,6 +32,61 @@ pub enum Format<'a> {
     SequentiallyIndependent(Vec<Track<'a>>),
 }
 
+impl<'a> Format<'a> {
+    /// Returns the format type
+    pub fn format_type(&self) -> FormatType {
+        match self {
+            Format::SequentiallyIndependent(_) => FormatType::SequentiallyIndependent,
+            Format::Simultaneous(_) => FormatType::Simultaneous,
+            Format::SingleMultiChannel(_) => FormatType::SingleMultiChannel,
+        }
+    }
+
+    /// Returns the tracks of the format
+    pub fn tracks(&self) -> &[Track<'a>] {
+        match self {
+            Format::SequentiallyIndependent(t) | Format::Simultaneous(t) => t,
+            Format::SingleMultiChannel(t) => core::slice::from_ref(t),
+        }
+    }
+
+    /// Returns the tracks of the format mutably
+    pub fn tracks_mut(&mut self) -> &mut [Track<'a>] {
+        match self {
+            Format::SequentiallyIndependent(t) | Format::Simultaneous(t) => t,
+            Format::SingleMultiChannel(t) => core::slice::from_mut(t),
+        }
+    }
+
+    /// Returns the owned tracks of the format
+    pub fn into_tracks(self) -> Vec<Track<'a>> {
+        match self {
+            Format::SequentiallyIndependent(t) | Format::Simultaneous(t) => t,
+            Format::SingleMultiChannel(t) => alloc::vec![t],
+        }
+    }
+
+    /// Create a format from a type and a set of tracks.
+    ///
+    /// # Errors
+    /// If the format type is [`FormatType::SingleMultiChannel`] and there isn't exactly one track
+    pub fn from_tracks(
+        format_type: FormatType,
+        mut tracks: Vec<Track<'a>>,
+    ) -> Result<Self, EditError> {
+        Ok(match format_type {
+            FormatType::SingleMultiChannel => {
+                if tracks.len() != 1 {
+                    return Err(EditError::SingleTrackFormat(tracks.len()));
+                }
+                Format::SingleMultiChannel(tracks.pop().unwrap())
+            }
+            FormatType::Simultaneous => Format::Simultaneous(tracks),
+            FormatType::SequentiallyIndependent => Format::SequentiallyIndependent(tracks),
+        })
+    }
+}
+
 #[doc = r#"
 Identifies the type of the MIDI file.
 
@@ -14,9 +14,24 @@ sf = 7: 7 sharps
 mi = 0: major key
 mi = 1: minor key
 "#]
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "bevy", derive(bevy::reflect::Reflect))]
 pub struct KeySignature([u8; 2]);
 impl KeySignature {
+    /// Create a new key signature from a count of sharps (positive) or flats (negative).
+    ///
+    /// The count is clamped to `-7..=7`.
+    pub const fn new(sharp_flat_count: i8, minor: bool) -> Self {
+        let count = if sharp_flat_count < -7 {
+            -7
+        } else if sharp_flat_count > 7 {
+            7
+        } else {
+            sharp_flat_count
+        };
+        Self([count as u8, minor as u8])
+    }
+
     /// Create a new key signature from a byte slice
     pub const fn new_from_bytes(v: [u8; 2]) -> Self {
         Self(v)
@@ -25,21 +40,71 @@ impl KeySignature {
     /// Count the number of flats or sharps. a positive number
     /// indicates a number of sharps. a negative number indicates
     /// a number of flats.
+    ///
+    /// Files may hold any byte, so the count is clamped to `-7..=7`.
     pub const fn sharp_flat_count(&self) -> i8 {
-        self.0[0] as i8
+        let count = self.0[0] as i8;
+        if count < -7 {
+            -7
+        } else if count > 7 {
+            7
+        } else {
+            count
+        }
     }
 
     /// the identifiable count of sharps
     pub fn num_sharps(&self) -> u8 {
-        self.sharp_flat_count().min(0).unsigned_abs()
+        self.sharp_flat_count().max(0).unsigned_abs()
     }
 
     /// the identifiable count of flats
     pub fn num_flats(&self) -> u8 {
-        self.sharp_flat_count().neg().min(0).unsigned_abs()
+        self.sharp_flat_count().neg().max(0).unsigned_abs()
     }
     /// True if the key is identified as minor
     pub const fn minor_key(&self) -> bool {
         self.0[1] == 1
     }
+
+    /// Returns the key signature moved up (or down, if negative) by a number of semitones.
+    ///
+    /// The result is spelled with at most six accidentals, except for a key of six
+    /// accidentals, which keeps its sharps or flats (F♯ major stays F♯, not G♭).
+    pub const fn transpose(&self, semitones: i8) -> Self {
+        // a semitone is seven steps around the circle of fifths
+        let count = self.sharp_flat_count() as i16;
+        let mut fifths = (count + 7 * semitones as i16).rem_euclid(12);
+        if fifths > 6 || (fifths == 6 && count < 0) {
+            fifths -= 12;
+        }
+        Self::new(fifths as i8, self.minor_key())
+    }
+}
+
+#[test]
+fn transpose_key_signature() {
+    let c_major = KeySignature::new(0, false);
+    // D major
+    assert_eq!(c_major.transpose(2), KeySignature::new(2, false));
+    // B flat major
+    assert_eq!(c_major.transpose(-2), KeySignature::new(-2, false));
+    // D flat major, rather than C sharp major
+    assert_eq!(c_major.transpose(13), KeySignature::new(-5, false));
+
+    let e_flat_minor = KeySignature::new(-6, true);
+    assert_eq!(e_flat_minor.transpose(12), e_flat_minor);
+    // E minor
+    assert_eq!(e_flat_minor.transpose(1), KeySignature::new(1, true));
+}
+
+#[test]
+fn count_accidentals() {
+    let d_major = KeySignature::new(2, false);
+    assert_eq!((d_major.num_sharps(), d_major.num_flats()), (2, 0));
+    let e_flat_major = KeySignature::new(-3, false);
+    assert_eq!(
+        (e_flat_major.num_sharps(), e_flat_major.num_flats()),
+        (0, 3)
+    );
 }
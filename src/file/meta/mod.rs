@@ -151,9 +151,12 @@ impl<'a> MetaMessage<'a> {
             MetaMessage::TrackName(name) => {
                 info.name = Some(name);
             }
+            MetaMessage::InstrumentName(instrument) => info.instrument = Some(instrument),
             MetaMessage::DeviceName(device) => info.device = Some(device),
             MetaMessage::MidiChannel(channel) => info.channel = Some(channel),
             MetaMessage::Tempo(tempo) => info.tempo = tempo,
+            MetaMessage::TimeSignature(time_signature) => info.time_signature = time_signature,
+            MetaMessage::KeySignature(key_signature) => info.key_signature = Some(key_signature),
             MetaMessage::SmpteOffset(offset) => info.smpte_offset = Some(offset),
             _ => {}
         }
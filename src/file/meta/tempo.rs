@@ -12,6 +12,30 @@ impl Default for Tempo {
 }
 
 impl Tempo {
+    /// Create a tempo from the number of microseconds per quarter note.
+    ///
+    /// Only the lower 24 bits are used, since that is all a tempo meta message can hold.
+    pub const fn new(micros_per_quarter_note: u32) -> Self {
+        Self(micros_per_quarter_note & 0x00FF_FFFF)
+    }
+
+    /// Create a tempo from a number of quarter notes per minute.
+    ///
+    /// The tempo is clamped to what a tempo meta message can hold, from one to `0xFFFFFF`
+    /// microseconds per quarter note. Zero, negative and NaN bpm give the slowest tempo.
+    pub fn from_bpm(bpm: f64) -> Self {
+        const SLOWEST: f64 = 0x00FF_FFFF as f64;
+        if bpm.is_nan() || bpm <= 0. {
+            return Self(SLOWEST as u32);
+        }
+        Self((60_000_000. / bpm).clamp(1., SLOWEST) as u32)
+    }
+
+    /// The number of quarter notes per minute
+    pub fn bpm(&self) -> f64 {
+        60_000_000. / self.0 as f64
+    }
+
     /// Interprete a byte slice as a tempo
     pub fn new_from_bytes(v: &[u8]) -> Self {
         let mut val = [0; 4];
@@ -41,3 +65,16 @@ fn known_tempo() {
 
     assert_eq!(tempo.micros_per_quarter_note(), 500000);
 }
+
+#[test]
+fn tempo_bpm() {
+    assert_eq!(Tempo::from_bpm(120.), Tempo::default());
+    assert_eq!(Tempo::new(1_000_000).bpm(), 60.);
+
+    let slowest = Tempo::new(0x00FF_FFFF);
+    assert_eq!(Tempo::from_bpm(0.), slowest);
+    assert_eq!(Tempo::from_bpm(-120.), slowest);
+    assert_eq!(Tempo::from_bpm(f64::NAN), slowest);
+    assert_eq!(Tempo::from_bpm(1.), slowest);
+    assert_eq!(Tempo::from_bpm(f64::INFINITY), Tempo::new(1));
+}
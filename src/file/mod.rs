@@ -14,6 +14,23 @@ pub use track::*;
 mod timed_event_iter;
 pub use timed_event_iter::*;
 
+mod playhead;
+pub use playhead::*;
+
+mod recorder;
+pub use recorder::*;
+
+mod editor;
+pub use editor::*;
+
+mod convert;
+
+mod slice;
+pub use slice::*;
+
+mod combine;
+pub use combine::*;
+
 mod timing;
 pub use timing::*;
 
@@ -21,7 +38,7 @@ mod meta;
 pub use meta::*;
 
 use crate::{
-    ParseError,
+    EditError, ParseError,
     events::LiveEvent,
     file::builder::MidiFileBuilder,
     message::Timed,
@@ -47,6 +64,11 @@ impl bevy::asset::VisitAssetDependencies for MidiFile<'static> {
 }
 
 impl<'a> MidiFile<'a> {
+    /// Create a file from its tracks and timing
+    pub fn new(format: Format<'a>, timing: Timing) -> Self {
+        Self { timing, format }
+    }
+
     /// Parse a set of bytes into a file struct
     pub fn parse<B>(bytes: B) -> ReadResult<Self>
     where
@@ -79,6 +101,11 @@ impl<'a> MidiFile<'a> {
         self.timing
     }
 
+    /// Replace the timing without moving any events
+    pub(crate) fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
     /// Executes the provided function for all the tracks in the format.
     ///
     /// Useful if you don't want to allocate more data on the stack.
@@ -103,11 +130,110 @@ impl<'a> MidiFile<'a> {
     }
     /// Returns the format type for the file.
     pub fn format_type(&self) -> FormatType {
-        match &self.format {
-            Format::SequentiallyIndependent(_) => FormatType::SequentiallyIndependent,
-            Format::Simultaneous(_) => FormatType::Simultaneous,
-            Format::SingleMultiChannel(_) => FormatType::SingleMultiChannel,
+        self.format.format_type()
+    }
+
+    /// Returns the tracks of the file, held by their format
+    pub fn format(&self) -> &Format<'a> {
+        &self.format
+    }
+
+    /// Returns the tracks of the file mutably
+    pub fn tracks_mut(&mut self) -> &mut [Track<'a>] {
+        self.format.tracks_mut()
+    }
+
+    /// Returns a track of the file mutably
+    pub fn track_mut(&mut self, index: usize) -> Option<&mut Track<'a>> {
+        self.format.tracks_mut().get_mut(index)
+    }
+
+    /// Add a track to the end of the file.
+    ///
+    /// # Errors
+    /// If the file is [`FormatType::SingleMultiChannel`], which can only hold one track
+    pub fn push_track(&mut self, track: Track<'a>) -> Result<(), EditError> {
+        let len = self.format.tracks().len();
+        self.insert_track(len, track)
+    }
+
+    /// Insert a track at an index.
+    ///
+    /// # Errors
+    /// - If the file is [`FormatType::SingleMultiChannel`], which can only hold one track
+    /// - If `index` is greater than the number of tracks
+    pub fn insert_track(&mut self, index: usize, track: Track<'a>) -> Result<(), EditError> {
+        match &mut self.format {
+            Format::SingleMultiChannel(_) => Err(EditError::SingleTrackFormat(2)),
+            Format::Simultaneous(tracks) | Format::SequentiallyIndependent(tracks) => {
+                if index > tracks.len() {
+                    return Err(EditError::TrackIndex(index));
+                }
+                tracks.insert(index, track);
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove the track at an index.
+    ///
+    /// # Errors
+    /// - If the file is [`FormatType::SingleMultiChannel`], which must keep its track
+    /// - If there is no track at `index`
+    pub fn remove_track(&mut self, index: usize) -> Result<Track<'a>, EditError> {
+        match &mut self.format {
+            Format::SingleMultiChannel(_) => Err(EditError::SingleTrackFormat(0)),
+            Format::Simultaneous(tracks) | Format::SequentiallyIndependent(tracks) => {
+                if index >= tracks.len() {
+                    return Err(EditError::TrackIndex(index));
+                }
+                Ok(tracks.remove(index))
+            }
+        }
+    }
+
+    /// Move the track at `from` so that it ends up at index `to`.
+    ///
+    /// # Errors
+    /// If either index is out of bounds
+    pub fn move_track(&mut self, from: usize, to: usize) -> Result<(), EditError> {
+        let tracks = self.format.tracks_mut();
+        let len = tracks.len();
+        if from >= len {
+            return Err(EditError::TrackIndex(from));
+        }
+        if to >= len {
+            return Err(EditError::TrackIndex(to));
         }
+        if from < to {
+            tracks[from..=to].rotate_left(1);
+        } else {
+            tracks[to..=from].rotate_right(1);
+        }
+        Ok(())
+    }
+
+    /// Change the format of the file, keeping its tracks.
+    ///
+    /// # Errors
+    /// If changing to [`FormatType::SingleMultiChannel`] with more than one track
+    pub fn set_format_type(&mut self, format_type: FormatType) -> Result<(), EditError> {
+        if format_type == self.format_type() {
+            return Ok(());
+        }
+        let tracks = self.format.tracks().len();
+        if format_type == FormatType::SingleMultiChannel && tracks != 1 {
+            return Err(EditError::SingleTrackFormat(tracks));
+        }
+        let placeholder = Format::Simultaneous(Vec::new());
+        let format = core::mem::replace(&mut self.format, placeholder);
+        self.format = Format::from_tracks(format_type, format.into_tracks())?;
+        Ok(())
+    }
+
+    /// Returns the owned tracks of the file, held by their format
+    pub fn into_format(self) -> Format<'a> {
+        self.format
     }
 
     /// Returns a set of timed events from the midi file.
@@ -118,3 +244,66 @@ impl<'a> MidiFile<'a> {
         }
     }
 }
+
+#[cfg(test)]
+fn named_track(name: &'static str) -> Track<'static> {
+    let name = MetaMessage::TrackName(BytesText::new_from_bytes(name.as_bytes()));
+    Track::new(alloc::vec![TrackEvent::new(0, name.into())])
+}
+
+#[cfg(test)]
+fn track_names(file: &MidiFile) -> Vec<alloc::string::String> {
+    file.tracks()
+        .iter()
+        .map(|t| t.info().name.clone().unwrap().into_string().unwrap())
+        .collect()
+}
+
+#[test]
+fn edit_tracks() {
+    let mut file = MidiFile::new(
+        Format::Simultaneous(alloc::vec![named_track("a")]),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    file.push_track(named_track("b")).unwrap();
+    file.insert_track(0, named_track("c")).unwrap();
+    assert_eq!(
+        file.insert_track(4, named_track("d")),
+        Err(EditError::TrackIndex(4))
+    );
+    assert_eq!(track_names(&file), ["c", "a", "b"]);
+
+    file.move_track(0, 2).unwrap();
+    assert_eq!(track_names(&file), ["a", "b", "c"]);
+    file.move_track(2, 1).unwrap();
+    assert_eq!(track_names(&file), ["a", "c", "b"]);
+
+    let removed = file.remove_track(1).unwrap();
+    assert_eq!(removed.info().name.as_ref().unwrap().as_str(), Ok("c"));
+    assert_eq!(track_names(&file), ["a", "b"]);
+}
+
+#[test]
+fn change_format() {
+    let mut file = MidiFile::new(
+        Format::SingleMultiChannel(named_track("a")),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    assert_eq!(
+        file.push_track(named_track("b")),
+        Err(EditError::SingleTrackFormat(2))
+    );
+
+    file.set_format_type(FormatType::Simultaneous).unwrap();
+    file.push_track(named_track("b")).unwrap();
+    assert_eq!(
+        file.set_format_type(FormatType::SingleMultiChannel),
+        Err(EditError::SingleTrackFormat(2))
+    );
+
+    file.remove_track(0).unwrap();
+    file.set_format_type(FormatType::SingleMultiChannel)
+        .unwrap();
+    assert_eq!(file.format_type(), FormatType::SingleMultiChannel);
+    assert_eq!(track_names(&file), ["b"]);
+}
@@ -0,0 +1,338 @@
+#![doc = r#"
+A fixed-timestep cursor over the events of a [`MidiFile`]
+"#]
+
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+#[doc = r#"
+Plays back a [`MidiFile`] by stepping through time manually.
+
+Game loops advance in discrete `dt` steps rather than wall-clock callbacks.
+A `Playhead` owns the file's events (sorted by their timestamp) and yields every
+event crossed by a call to [`Playhead::advance`], so a long frame never skips notes.
+
+No thread or clock is involved: the caller decides how much time passes.
+
+# Example
+```rust
+# use midix::prelude::*;
+# let bytes = [
+#     0x4D, 0x54, 0x68, 0x64, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x01, 0x00, 0x60,
+#     0x4D, 0x54, 0x72, 0x6B, 0x00, 0x00, 0x00, 0x0C,
+#     0x00, 0x90, 0x3C, 0x40, // note on at tick 0
+#     0x60, 0x80, 0x3C, 0x40, // note off at tick 96 (500ms at 120bpm)
+#     0x00, 0xFF, 0x2F, 0x00,
+# ];
+let file = MidiFile::parse(bytes.as_slice()).unwrap();
+let mut playhead = Playhead::new(file);
+
+// the note on is crossed in the first 16ms
+assert_eq!(playhead.advance(UMicros::from_ms(16)).count(), 1);
+
+// nothing happens for a while
+assert_eq!(playhead.advance(UMicros::from_ms(16)).count(), 0);
+
+// a frame drop still yields the note off
+assert_eq!(playhead.advance(UMicros::from_ms(1_000)).count(), 1);
+assert!(playhead.is_finished());
+```
+"#]
+#[derive(Debug, Clone)]
+pub struct Playhead<'a> {
+    events: Vec<Timed<LiveEvent<'a>>>,
+    /// Current position in microseconds.
+    ///
+    /// Kept as a float so that fractional rates do not accumulate rounding error.
+    position: f64,
+    /// Index of the first event that has not yet been crossed
+    cursor: usize,
+    rate: f64,
+    looping: bool,
+    loop_region: Option<Range<UMicros>>,
+    duration: UMicros,
+}
+
+impl<'a> Playhead<'a> {
+    /// Create a new playhead positioned at the start of the file
+    pub fn new(file: MidiFile<'a>) -> Self {
+        let mut events: Vec<_> = file.into_events().collect();
+        // tracks are yielded one after another, so merge them in time.
+        // this sort is stable, which keeps same-timestamp events in file order.
+        events.sort_by_key(|event| event.timestamp);
+
+        let duration = UMicros::new(events.last().map(|e| e.timestamp).unwrap_or(0));
+
+        Self {
+            events,
+            position: 0.,
+            cursor: 0,
+            rate: 1.,
+            looping: false,
+            loop_region: None,
+            duration,
+        }
+    }
+
+    /// Move the playhead forward by `dt` (scaled by the [rate](Playhead::rate)).
+    ///
+    /// Yields every event whose timestamp is in `[position, position + dt)`.
+    /// If looping is enabled and the step crosses the end of the loop,
+    /// the events at the end of the loop are yielded, followed by the events
+    /// at the start of the loop, as many times as the step requires.
+    pub fn advance(&mut self, dt: UMicros) -> PlayheadEvents<'_, 'a> {
+        let start = self.cursor;
+        let mut target = self.position + dt.us() as f64 * self.rate;
+
+        let mut loops = 0;
+        let mut loop_indices = 0..0;
+
+        if let Some(region) = self.active_loop() {
+            let loop_start = region.start.us() as f64;
+            let loop_end = region.end.us() as f64;
+            let loop_len = loop_end - loop_start;
+
+            if self.position < loop_end && target >= loop_end {
+                // when looping the whole file, the final events sit exactly on the loop end
+                // and must still be played.
+                let end_index = if self.loop_region.is_some() {
+                    self.index_of(region.end)
+                } else {
+                    self.events.len()
+                };
+                loop_indices = self.index_of(region.start)..end_index;
+                // the first crossing finishes the current pass through the loop
+                let overshoot = target - loop_end;
+                loops = 1 + (overshoot / loop_len) as usize;
+                target = loop_start + overshoot % loop_len;
+            }
+        }
+
+        self.position = target;
+        let end = self.index_of_f64(target);
+
+        let first_end = if loops > 0 { loop_indices.end } else { end };
+        self.cursor = end;
+
+        PlayheadEvents {
+            events: &self.events,
+            index: start,
+            segment_end: first_end.max(start),
+            loops_remaining: loops,
+            loop_indices,
+            final_end: end,
+        }
+    }
+
+    /// Move the playhead to an absolute position.
+    ///
+    /// Events at exactly `position` will be yielded on the next call to [`Playhead::advance`].
+    pub fn seek(&mut self, position: UMicros) {
+        self.position = position.us() as f64;
+        self.cursor = self.index_of(position);
+    }
+
+    /// Returns the current position of the playhead
+    pub fn position(&self) -> UMicros {
+        UMicros::new(self.position as u64)
+    }
+
+    /// Set the playback rate.
+    ///
+    /// `1.0` is normal speed, `2.0` is double speed, `0.5` is half speed.
+    /// Negative rates are clamped to `0.0`, which pauses playback.
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate.max(0.);
+    }
+
+    /// Returns the playback rate
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Enable or disable looping.
+    ///
+    /// If no [loop region](Playhead::set_loop_region) is set,
+    /// the whole file is looped.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Returns true if looping is enabled
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Restrict looping to a region of the file.
+    ///
+    /// `None` loops the whole file. This has no effect unless looping is enabled.
+    pub fn set_loop_region(&mut self, region: Option<Range<UMicros>>) {
+        self.loop_region = region;
+    }
+
+    /// Returns the timestamp of the last event in the file
+    pub fn duration(&self) -> UMicros {
+        self.duration
+    }
+
+    /// Returns true if every event has been yielded and the playhead will not loop
+    pub fn is_finished(&self) -> bool {
+        self.active_loop().is_none() && self.cursor >= self.events.len()
+    }
+
+    /// Returns all events held by the playhead, sorted by timestamp
+    pub fn events(&self) -> &[Timed<LiveEvent<'a>>] {
+        &self.events
+    }
+
+    /// The loop region, if looping is enabled and the region is not empty.
+    fn active_loop(&self) -> Option<Range<UMicros>> {
+        if !self.looping {
+            return None;
+        }
+        let region = self
+            .loop_region
+            .clone()
+            .unwrap_or(UMicros::ZERO..self.duration);
+
+        // an empty region would loop forever without moving
+        (region.start < region.end).then_some(region)
+    }
+
+    /// Index of the first event at or after `time`
+    fn index_of(&self, time: UMicros) -> usize {
+        self.events.partition_point(|e| e.timestamp < time.us())
+    }
+
+    /// Index of the first event at or after `time`, which may be fractional
+    fn index_of_f64(&self, time: f64) -> usize {
+        self.events.partition_point(|e| (e.timestamp as f64) < time)
+    }
+}
+
+/// Events crossed by a single call to [`Playhead::advance`]
+pub struct PlayheadEvents<'p, 'a> {
+    events: &'p [Timed<LiveEvent<'a>>],
+    index: usize,
+    segment_end: usize,
+    loops_remaining: usize,
+    loop_indices: Range<usize>,
+    final_end: usize,
+}
+
+impl<'p, 'a> Iterator for PlayheadEvents<'p, 'a> {
+    type Item = &'p Timed<LiveEvent<'a>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.index < self.segment_end {
+                let event = &self.events[self.index];
+                self.index += 1;
+                return Some(event);
+            }
+            if self.loops_remaining == 0 {
+                return None;
+            }
+            self.loops_remaining -= 1;
+            self.index = self.loop_indices.start;
+            self.segment_end = if self.loops_remaining == 0 {
+                self.final_end
+            } else {
+                self.loop_indices.end
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+fn note_event(delta_ticks: u32, note: u8) -> TrackEvent<'static> {
+    TrackEvent::new(
+        delta_ticks,
+        TrackMessage::ChannelVoice(ChannelVoiceMessage::new(
+            Channel::One,
+            VoiceEvent::note_on(Note::from_databyte(note).unwrap(), Velocity::MAX),
+        )),
+    )
+}
+
+/// 480 tpqn at 120bpm: one tick is 1_041.6 micros, so a quarter note is 500ms.
+#[cfg(test)]
+fn quarter_notes(notes: &[u8]) -> MidiFile<'static> {
+    let events = notes
+        .iter()
+        .enumerate()
+        .map(|(i, note)| note_event(if i == 0 { 0 } else { 480 }, *note))
+        .collect();
+    MidiFile {
+        timing: Timing::new_ticks_per_quarter_note(480),
+        format: Format::SingleMultiChannel(Track::new(events)),
+    }
+}
+
+#[cfg(test)]
+fn notes_of<'p>(events: impl Iterator<Item = &'p Timed<LiveEvent<'static>>>) -> Vec<u8> {
+    events
+        .filter_map(|e| e.event.channel_voice()?.note())
+        .map(|n| n.byte())
+        .collect()
+}
+
+#[test]
+fn advance_yields_every_crossed_event() {
+    let mut playhead = Playhead::new(quarter_notes(&[60, 62, 64, 65]));
+
+    assert_eq!(notes_of(playhead.advance(UMicros::from_ms(100))), [60]);
+    assert_eq!(notes_of(playhead.advance(UMicros::from_ms(100))), []);
+    // frame drop
+    assert_eq!(
+        notes_of(playhead.advance(UMicros::from_ms(1_500))),
+        [62, 64, 65]
+    );
+    assert!(playhead.is_finished());
+}
+
+#[test]
+fn seek_and_rate() {
+    let mut playhead = Playhead::new(quarter_notes(&[60, 62, 64, 65]));
+    playhead.seek(UMicros::from_ms(1_000));
+    playhead.set_rate(2.);
+
+    // 250ms at double speed reaches 1500ms
+    assert_eq!(notes_of(playhead.advance(UMicros::from_ms(250))), [64]);
+    assert_eq!(playhead.position(), UMicros::from_ms(1_500));
+    assert_eq!(notes_of(playhead.advance(UMicros::from_ms(1))), [65]);
+}
+
+#[test]
+fn looping_wraps_around() {
+    let mut playhead = Playhead::new(quarter_notes(&[60, 62, 64]));
+    playhead.set_looping(true);
+    assert_eq!(playhead.duration(), UMicros::from_ms(1_000));
+
+    assert_eq!(notes_of(playhead.advance(UMicros::from_ms(900))), [60, 62]);
+    // crossing the end goes back to the start of the loop
+    assert_eq!(notes_of(playhead.advance(UMicros::from_ms(200))), [64, 60]);
+    assert_eq!(playhead.position(), UMicros::from_ms(100));
+    assert!(!playhead.is_finished());
+
+    // a step longer than the loop yields every pass
+    assert_eq!(
+        notes_of(playhead.advance(UMicros::from_ms(2_000))),
+        [62, 64, 60, 62, 64, 60]
+    );
+}
+
+#[test]
+fn loop_region() {
+    let mut playhead = Playhead::new(quarter_notes(&[60, 62, 64, 65]));
+    playhead.set_looping(true);
+    playhead.set_loop_region(Some(UMicros::from_ms(500)..UMicros::from_ms(1_000)));
+
+    assert_eq!(
+        notes_of(playhead.advance(UMicros::from_ms(1_200))),
+        [60, 62, 62]
+    );
+    assert_eq!(playhead.position(), UMicros::from_ms(700));
+}
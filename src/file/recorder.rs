@@ -0,0 +1,294 @@
+#![doc = r#"
+Captures a live performance into a [`MidiFile`]
+"#]
+
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+#[doc = r#"
+Records timestamped [`LiveEvent`]s from a live input and turns them into a [`MidiFile`].
+
+Timestamps are converted into ticks using the recorder's [`Timing`] and [`Tempo`].
+The first recorded event starts the recording unless [`Recorder::start`] is called first.
+
+The produced file is [`Format::Simultaneous`], with two tracks:
+1. A conductor track holding the tempo and time signature
+2. The performance
+
+Real-time messages (clock, start, stop, ...) are not stored, since they have no place in a file.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut recorder = Recorder::new(Timing::new_ticks_per_quarter_note(480))
+    .with_tempo(Tempo::from_bpm(120.))
+    // input quantize to sixteenth notes
+    .with_quantize(120);
+
+let c4 = VoiceEvent::note_on(note!(C, 4), Velocity::MAX).send_to_channel(Channel::One);
+recorder.start(UMicros::ZERO);
+recorder.record(UMicros::from_ms(510), c4);
+
+// the note is still held when we stop, so it is closed for us
+let file = recorder.stop(UMicros::from_ms(1_000));
+
+let performance = file.tracks()[1];
+assert_eq!(performance.events().len(), 2);
+// 510ms is 490 ticks, which is quantized to 480
+assert_eq!(performance.events()[0].accumulated_ticks(), 480);
+assert_eq!(performance.events()[1].accumulated_ticks(), 960);
+```
+"#]
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    timing: Timing,
+    tempo: Tempo,
+    time_signature: TimeSignature,
+    quantize: Option<u32>,
+    origin: Option<UMicros>,
+    events: Vec<Ticked<LiveEvent<'static>>>,
+    /// Notes that are currently held, and how far their note on was moved by quantization
+    held: Vec<HeldNote>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HeldNote {
+    channel: Channel,
+    note: Note,
+    tick: u32,
+    shift: i64,
+}
+
+impl Recorder {
+    /// Create a recorder at 120bpm in 4/4 without quantization
+    pub fn new(timing: Timing) -> Self {
+        Self {
+            timing,
+            tempo: Tempo::default(),
+            time_signature: TimeSignature::default(),
+            quantize: None,
+            origin: None,
+            events: Vec::new(),
+            held: Vec::new(),
+        }
+    }
+
+    /// Set the tempo used to convert timestamps into ticks.
+    ///
+    /// This is written into the conductor track.
+    pub fn with_tempo(mut self, tempo: Tempo) -> Self {
+        self.tempo = tempo;
+        self
+    }
+
+    /// Set the time signature written into the conductor track
+    pub fn with_time_signature(mut self, time_signature: TimeSignature) -> Self {
+        self.time_signature = time_signature;
+        self
+    }
+
+    /// Move note ons to the nearest multiple of `grid` ticks as they are recorded.
+    ///
+    /// The matching note off is moved by the same amount, so note lengths are kept.
+    /// A grid of `0` disables quantization.
+    pub fn with_quantize(mut self, grid: u32) -> Self {
+        self.quantize = (grid > 0).then_some(grid);
+        self
+    }
+
+    /// Returns the timing of the recording
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    /// Returns the tempo of the recording
+    pub fn tempo(&self) -> Tempo {
+        self.tempo
+    }
+
+    /// Mark the start of the recording.
+    ///
+    /// Events recorded before `at` are placed at tick 0.
+    pub fn start(&mut self, at: UMicros) {
+        self.origin = Some(at);
+    }
+
+    /// Returns true once the recording has started
+    pub fn is_recording(&self) -> bool {
+        self.origin.is_some()
+    }
+
+    /// Returns the events recorded so far, in the order they were received
+    pub fn events(&self) -> &[Ticked<LiveEvent<'static>>] {
+        &self.events
+    }
+
+    /// Record an event received at `timestamp`
+    pub fn record<'e, E>(&mut self, timestamp: UMicros, event: E)
+    where
+        E: Into<LiveEvent<'e>>,
+    {
+        let event = event.into();
+        if matches!(event, LiveEvent::SysRealTime(_)) {
+            return;
+        }
+        let origin = *self.origin.get_or_insert(timestamp);
+        let tick = self
+            .timing
+            .micros_to_ticks(timestamp.saturating_sub(origin), self.tempo);
+
+        let tick = match &event {
+            LiveEvent::ChannelVoice(msg) => self.place_voice(msg, tick),
+            _ => tick,
+        };
+
+        self.events.push(Ticked::new(tick, event.into_owned()));
+    }
+
+    /// Finish the recording at `at`, producing a file.
+    ///
+    /// Any notes still held are closed at `at`.
+    pub fn stop(mut self, at: UMicros) -> MidiFile<'static> {
+        let origin = self.origin.unwrap_or(at);
+        let end = self
+            .timing
+            .micros_to_ticks(at.saturating_sub(origin), self.tempo);
+
+        for held in core::mem::take(&mut self.held) {
+            let tick = end.max(held.tick);
+            let note_off = VoiceEvent::note_off(held.note, Velocity::ZERO);
+            self.events
+                .push(Ticked::new(tick, held.channel.send_event(note_off).into()));
+        }
+
+        // quantization may have moved events before ones recorded earlier.
+        // the sort is stable, so events on the same tick stay in the order received.
+        self.events.sort_by_key(|event| event.accumulated_ticks());
+
+        let conductor = Track::new(alloc::vec![
+            TrackEvent::new(0, MetaMessage::Tempo(self.tempo).into()),
+            TrackEvent::new(0, MetaMessage::TimeSignature(self.time_signature).into()),
+        ]);
+        let mut performance = Track::new(Vec::new());
+        performance.events = self.events;
+
+        MidiFile::new(
+            Format::Simultaneous(alloc::vec![conductor, performance]),
+            self.timing,
+        )
+    }
+
+    /// Returns the tick a voice message should be placed on, tracking held notes.
+    fn place_voice(&mut self, msg: &ChannelVoiceMessage, tick: u32) -> u32 {
+        let channel = msg.channel();
+        if let Some(note) = msg.is_note_on() {
+            let placed = match self.quantize {
+                Some(grid) => tick.saturating_add(grid / 2) / grid * grid,
+                None => tick,
+            };
+            self.held.push(HeldNote {
+                channel,
+                note,
+                tick: placed,
+                shift: placed as i64 - tick as i64,
+            });
+            placed
+        } else if let Some(note) = msg.is_note_off() {
+            let Some(index) = self
+                .held
+                .iter()
+                .position(|held| held.channel == channel && held.note == note)
+            else {
+                return tick;
+            };
+            let held = self.held.remove(index);
+            ((tick as i64 + held.shift).max(held.tick as i64)) as u32
+        } else {
+            tick
+        }
+    }
+}
+
+#[cfg(test)]
+fn voice(event: VoiceEvent) -> ChannelVoiceMessage {
+    event.send_to_channel(Channel::Two)
+}
+
+#[test]
+fn record_converts_micros_to_ticks() {
+    let mut recorder = Recorder::new(Timing::new_ticks_per_quarter_note(96));
+
+    // the first event starts the recording
+    recorder.record(
+        UMicros::from_ms(2_000),
+        voice(VoiceEvent::note_on(note!(E, 4), Velocity::MAX)),
+    );
+    recorder.record(UMicros::from_ms(2_100), SystemRealTimeMessage::TimingClock);
+    recorder.record(
+        UMicros::from_ms(2_250),
+        voice(VoiceEvent::note_off(note!(E, 4), Velocity::ZERO)),
+    );
+
+    let file = recorder.stop(UMicros::from_ms(3_000));
+    assert_eq!(file.format_type(), FormatType::Simultaneous);
+
+    let tracks = file.tracks();
+    assert_eq!(tracks[0].info().tempo, Tempo::default());
+    assert_eq!(tracks[0].info().time_signature, TimeSignature::default());
+
+    let ticks: Vec<_> = tracks[1]
+        .events()
+        .iter()
+        .map(|e| e.accumulated_ticks())
+        .collect();
+    assert_eq!(ticks, [0, 48]);
+}
+
+#[test]
+fn quantize_keeps_note_length() {
+    let mut recorder = Recorder::new(Timing::new_ticks_per_quarter_note(480))
+        .with_tempo(Tempo::new(480_000))
+        .with_quantize(240);
+    recorder.start(UMicros::ZERO);
+
+    // 1ms per tick
+    recorder.record(
+        UMicros::from_ms(230),
+        voice(VoiceEvent::note_on(note!(C, 4), Velocity::MAX)),
+    );
+    recorder.record(
+        UMicros::from_ms(250),
+        voice(VoiceEvent::note_on(note!(G, 4), Velocity::MAX)),
+    );
+    recorder.record(
+        UMicros::from_ms(300),
+        voice(VoiceEvent::note_off(note!(C, 4), Velocity::ZERO)),
+    );
+
+    let file = recorder.stop(UMicros::from_ms(700));
+    let events: Vec<_> = file.tracks()[1]
+        .events()
+        .iter()
+        .map(|e| {
+            let msg = e.event().channel_voice().unwrap();
+            (
+                e.accumulated_ticks(),
+                msg.note().unwrap(),
+                msg.is_note_on().is_some(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        events,
+        [
+            (240, note!(C, 4), true),
+            (240, note!(G, 4), true),
+            // moved by 10 ticks with its note on
+            (310, note!(C, 4), false),
+            // closed on stop
+            (700, note!(G, 4), false),
+        ]
+    );
+}
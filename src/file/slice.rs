@@ -0,0 +1,446 @@
+use alloc::vec::Vec;
+
+use crate::{prelude::*, transform::note_spans};
+
+/// A position in a [`MidiFile`], used to mark the edges of a [`MidiFile::slice`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    /// A number of ticks from the start of the file
+    Ticks(u32),
+    /// A time from the start of the file, following its tempo
+    Time(UMicros),
+    /// A bar and beat, counted from zero, following the first track's time signature.
+    ///
+    /// Bar 0, beat 0 is the start of the file. Beats are in units of the
+    /// time signature's denominator, so beat 1 in 6/8 is the second eighth note.
+    Bar {
+        /// Bars from the start of the file
+        bar: u32,
+        /// Beats into the bar
+        beat: u32,
+    },
+}
+
+impl Position {
+    /// The start of a bar, counted from zero
+    pub const fn bar(bar: u32) -> Self {
+        Self::Bar { bar, beat: 0 }
+    }
+}
+
+impl From<u32> for Position {
+    fn from(ticks: u32) -> Self {
+        Self::Ticks(ticks)
+    }
+}
+
+impl From<UMicros> for Position {
+    fn from(time: UMicros) -> Self {
+        Self::Time(time)
+    }
+}
+
+impl<'a> MidiFile<'a> {
+    /// Returns the tick of a position.
+    ///
+    /// # Errors
+    /// If the position is a [`Position::Bar`] and the file is timed in SMPTE frames
+    pub fn tick_at(&self, position: Position) -> Result<u32, EditError> {
+        match (position, self.timing()) {
+            (Position::Ticks(tick), _) => Ok(tick),
+            (Position::Time(time), Timing::TicksPerQuarterNote(tpqn)) => {
+                let tempo_map = TempoMap::from_file(self);
+                Ok(tempo_map.micros_to_ticks(time, tpqn.ticks_per_quarter_note()))
+            }
+            (Position::Time(time), Timing::Smpte(smpte)) => {
+                let ticks_per_second =
+                    smpte.fps().as_division() as u128 * smpte.ticks_per_frame() as u128;
+                let ticks =
+                    Rounding::Nearest.divide(time.us() as u128 * ticks_per_second, 1_000_000);
+                Ok(ticks.min(u32::MAX as u128) as u32)
+            }
+            (Position::Bar { bar, beat }, Timing::TicksPerQuarterNote(tpqn)) => {
+                let signature = self
+                    .tracks()
+                    .first()
+                    .map(|track| track.info().time_signature)
+                    .unwrap_or_default();
+                // a whole note is four quarter notes
+                let ticks_per_beat =
+                    (tpqn.ticks_per_quarter_note() as u64 * 4) >> signature.den().min(63);
+                let beats = bar as u64 * signature.num() as u64 + beat as u64;
+                Ok((beats * ticks_per_beat).min(u32::MAX as u64) as u32)
+            }
+            (Position::Bar { .. }, Timing::Smpte(_)) => Err(EditError::SmpteTiming),
+        }
+    }
+
+    #[doc = r#"
+    Copy a region of the file into a new file, moved to start at tick 0.
+
+    The region includes events from `start` up to, but not including, `end`.
+
+    - Notes that start before the region and are still sounding at its start are
+      struck again at tick 0.
+    - Notes that are still sounding at the end of the region are ended there.
+    - The last program, pitch bend and controller values of each channel from before
+      the region are sent at tick 0, in that order with controllers first,
+      so the region sounds as it did in the file.
+    - The tempo in effect at the start of the region becomes the tempo of the first track.
+
+    Tracks keep their info, including their time signature, and the new file keeps
+    the timing and format of this file.
+
+    # Errors
+    - If `end` is before `start`
+    - If either position is a [`Position::Bar`] and the file is timed in SMPTE frames
+
+    # Example
+    ```rust
+    # use midix::prelude::*;
+    let mut track = Track::new(Vec::new());
+    let program = VoiceEvent::program_change(Program::new(33).unwrap());
+    let on = VoiceEvent::note_on(note!(E, 2), Velocity::MAX);
+    let off = VoiceEvent::note_off(note!(E, 2), Velocity::ZERO);
+    track.insert(0, Channel::Two.send_event(program).into());
+    track.insert(0, Channel::Two.send_event(on).into());
+    track.insert(1_000, Channel::Two.send_event(off).into());
+    let file = MidiFile::new(Format::SingleMultiChannel(track), Timing::new_ticks_per_quarter_note(96));
+
+    // the second bar of 4/4
+    let excerpt = file.slice(Position::bar(1), Position::bar(2)).unwrap();
+    let events = excerpt.tracks()[0].events();
+
+    // the program is chased, and the held note is struck again and ended at the bar line
+    assert_eq!(events[0].event(), &Channel::Two.send_event(program).into());
+    assert_eq!(events[1].event(), &Channel::Two.send_event(on).into());
+    assert_eq!(events[2].accumulated_ticks(), 384);
+    ```
+    "#]
+    pub fn slice(
+        &self,
+        start: impl Into<Position>,
+        end: impl Into<Position>,
+    ) -> Result<MidiFile<'a>, EditError> {
+        let start = self.tick_at(start.into())?;
+        let end = self.tick_at(end.into())?;
+        if end < start {
+            return Err(EditError::InvalidRegion { start, end });
+        }
+
+        let tempo = TempoMap::from_file(self).tempo_at(start);
+        let mut tracks: Vec<Track<'a>> = self
+            .tracks()
+            .into_iter()
+            .map(|track| track.slice(start, end))
+            .collect();
+        if let Some(first) = tracks.first_mut() {
+            first.info_mut().tempo = tempo;
+        }
+
+        let format = Format::from_tracks(self.format_type(), tracks)?;
+        Ok(MidiFile::new(format, self.timing()))
+    }
+}
+
+impl<'a> Track<'a> {
+    /// Copy the events from tick `start` up to `end` into a new track, moved to start at tick 0.
+    ///
+    /// Notes crossing the edges and the channel state before `start` are handled
+    /// as in [`MidiFile::slice`]. The track's info is copied unchanged.
+    pub fn slice(&self, start: u32, end: u32) -> Track<'a> {
+        let end = end.max(start);
+        let events = self.events();
+        let mut in_span = alloc::vec![false; events.len()];
+
+        // (new tick, rank, event): chased state ranks first, added note offs last,
+        // and everything else keeps its order in the track
+        let mut sliced: Vec<(u32, usize, LiveEvent<'a>)> = Vec::new();
+        for span in note_spans(events) {
+            in_span[span.on] = true;
+            if let Some(off) = span.off {
+                in_span[off] = true;
+            }
+
+            let on_tick = events[span.on].accumulated_ticks();
+            let off_tick = span.off.map(|off| events[off].accumulated_ticks());
+            let ended_before = on_tick < start && off_tick.is_some_and(|tick| tick <= start);
+            if on_tick >= end || ended_before {
+                continue;
+            }
+
+            let on = events[span.on].event().clone();
+            sliced.push((on_tick.max(start) - start, span.on + 1, on));
+            match span.off {
+                Some(off) if events[off].accumulated_ticks() < end => {
+                    let tick = events[off].accumulated_ticks() - start;
+                    sliced.push((tick, off + 1, events[off].event().clone()));
+                }
+                _ => {
+                    let off = VoiceEvent::note_off(span.note, Velocity::ZERO);
+                    sliced.push((end - start, usize::MAX, span.channel.send_event(off).into()));
+                }
+            }
+        }
+
+        let mut state = ChannelState::default();
+        for (index, event) in events.iter().enumerate() {
+            if in_span[index] {
+                continue;
+            }
+            let tick = event.accumulated_ticks();
+            if tick < start {
+                if let Some(msg) = event.event().channel_voice() {
+                    state.update(*msg);
+                }
+            } else if tick < end {
+                sliced.push((tick - start, index + 1, event.event().clone()));
+            }
+        }
+        sliced.extend(state.into_messages().map(|msg| (0, 0, msg.into())));
+
+        sliced.sort_by_key(|(tick, rank, _)| (*tick, *rank));
+        let events = sliced
+            .into_iter()
+            .map(|(tick, _, event)| Ticked::new(tick, event))
+            .collect();
+        Track::from_ticked(self.info().clone(), events)
+    }
+}
+
+/// The last program, pitch bend and controller values of each channel
+#[derive(Default)]
+struct ChannelState {
+    controllers: Vec<ChannelVoiceMessage>,
+    programs: [Option<ChannelVoiceMessage>; 16],
+    pitch_bends: [Option<ChannelVoiceMessage>; 16],
+    /// Whether a non-registered parameter was selected after the last registered one
+    nrpn_last: [bool; 16],
+}
+
+impl ChannelState {
+    fn update(&mut self, msg: ChannelVoiceMessage) {
+        let channel = msg.channel().to_byte() as usize;
+        match msg.event() {
+            VoiceEvent::ProgramChange { .. } => self.programs[channel] = Some(msg),
+            VoiceEvent::PitchBend(_) => self.pitch_bends[channel] = Some(msg),
+            VoiceEvent::ControlChange(controller) => {
+                let number = controller.to_bytes()[0];
+                // channel mode messages, such as all notes off, are not state
+                if number >= 120 {
+                    return;
+                }
+                match number {
+                    98 | 99 => self.nrpn_last[channel] = true,
+                    100 | 101 => self.nrpn_last[channel] = false,
+                    _ => {}
+                }
+                let key = |msg: &ChannelVoiceMessage| match msg.event() {
+                    VoiceEvent::ControlChange(c) => (msg.channel(), c.to_bytes()[0]),
+                    _ => unreachable!("only control changes are stored"),
+                };
+                match self
+                    .controllers
+                    .binary_search_by_key(&(msg.channel(), number), key)
+                {
+                    Ok(index) => self.controllers[index] = msg,
+                    Err(index) => self.controllers.insert(index, msg),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Controllers first, so bank selects come before their program change.
+    ///
+    /// Parameter numbers come before the other controllers, so data entry goes to
+    /// the parameter that was selected last.
+    fn into_messages(self) -> impl Iterator<Item = ChannelVoiceMessage> {
+        let Self {
+            controllers,
+            programs,
+            pitch_bends,
+            nrpn_last,
+        } = self;
+        Channel::all().into_iter().flat_map(move |channel| {
+            let index = channel.to_byte() as usize;
+            let (rpn, nrpn) = if nrpn_last[index] { (0, 1) } else { (1, 0) };
+            let mut channel_controllers: Vec<_> = controllers
+                .iter()
+                .filter(|msg| msg.channel() == channel)
+                .copied()
+                .collect();
+            // stable, and the most significant byte of a parameter number first
+            channel_controllers.sort_by_key(|msg| match msg.event() {
+                VoiceEvent::ControlChange(controller) => match controller.to_bytes()[0] {
+                    number @ (100 | 101) => (rpn, u8::MAX - number),
+                    number @ (98 | 99) => (nrpn, u8::MAX - number),
+                    number => (2, number),
+                },
+                _ => unreachable!("only control changes are stored"),
+            });
+            channel_controllers
+                .into_iter()
+                .chain(programs[index])
+                .chain(pitch_bends[index])
+        })
+    }
+}
+
+#[cfg(test)]
+fn voice(tick: u32, channel: Channel, event: VoiceEvent) -> Ticked<LiveEvent<'static>> {
+    Ticked::new(tick, channel.send_event(event).into())
+}
+
+#[cfg(test)]
+fn note(tick: u32, on: bool, key: u8) -> Ticked<LiveEvent<'static>> {
+    let note = Note::from_databyte(key).unwrap();
+    let event = if on {
+        VoiceEvent::note_on(note, Velocity::MAX)
+    } else {
+        VoiceEvent::note_off(note, Velocity::ZERO)
+    };
+    voice(tick, Channel::One, event)
+}
+
+#[cfg(test)]
+fn summary(track: &Track) -> Vec<(u32, &'static str, u8)> {
+    track
+        .events()
+        .iter()
+        .map(|e| {
+            let msg = e.event().channel_voice().unwrap();
+            let (kind, value) = match msg.event() {
+                VoiceEvent::NoteOn { note, .. } => ("on", note.byte()),
+                VoiceEvent::NoteOff { note, .. } => ("off", note.byte()),
+                VoiceEvent::ControlChange(c) => ("cc", c.to_bytes()[1]),
+                VoiceEvent::ProgramChange { program } => ("program", program.byte()),
+                _ => ("other", 0),
+            };
+            (e.accumulated_ticks(), kind, value)
+        })
+        .collect()
+}
+
+#[test]
+fn slice_truncates_notes() {
+    let track = Track::from_ticked(
+        TrackInfo::default(),
+        alloc::vec![
+            note(0, true, 60),
+            note(50, false, 60),
+            note(80, true, 62),
+            note(150, false, 62),
+            note(120, true, 64),
+            note(260, false, 64),
+            note(300, true, 65),
+            note(310, false, 65),
+        ],
+    );
+    let sliced = track.slice(100, 200);
+    assert_eq!(
+        summary(&sliced),
+        [
+            (0, "on", 62),
+            (20, "on", 64),
+            (50, "off", 62),
+            (100, "off", 64)
+        ]
+    );
+}
+
+#[test]
+fn slice_chases_state() {
+    let volume = |value| {
+        VoiceEvent::control_change(Controller::other(
+            DataByte::new_unchecked(7),
+            DataByte::new_unchecked(value),
+        ))
+    };
+    let bank = VoiceEvent::control_change(Controller::other(
+        DataByte::new_unchecked(0),
+        DataByte::new_unchecked(1),
+    ));
+    let program = |p| VoiceEvent::program_change(Program::new(p).unwrap());
+    let track = Track::from_ticked(
+        TrackInfo::default(),
+        alloc::vec![
+            voice(0, Channel::One, volume(100)),
+            voice(0, Channel::One, program(5)),
+            voice(10, Channel::One, bank),
+            voice(20, Channel::One, program(9)),
+            voice(30, Channel::One, volume(80)),
+            voice(
+                30,
+                Channel::One,
+                VoiceEvent::control_change(Controller::mute_all())
+            ),
+            voice(40, Channel::One, volume(60)),
+        ],
+    );
+    let sliced = track.slice(35, 100);
+    assert_eq!(
+        summary(&sliced),
+        [
+            (0, "cc", 1),
+            (0, "cc", 80),
+            (0, "program", 9),
+            (5, "cc", 60)
+        ]
+    );
+}
+
+#[test]
+fn slice_chases_parameters() {
+    let cc = |number, value| {
+        VoiceEvent::control_change(Controller::other(
+            DataByte::new_unchecked(number),
+            DataByte::new_unchecked(value),
+        ))
+    };
+    // a non-registered parameter, then a pitch bend range of 12 semitones
+    let track = Track::from_ticked(
+        TrackInfo::default(),
+        alloc::vec![
+            voice(0, Channel::One, cc(99, 1)),
+            voice(0, Channel::One, cc(98, 8)),
+            voice(0, Channel::One, cc(6, 64)),
+            voice(10, Channel::One, cc(101, 0)),
+            voice(10, Channel::One, cc(100, 0)),
+            voice(10, Channel::One, cc(6, 12)),
+            voice(10, Channel::One, cc(38, 0)),
+            note(20, true, 60),
+        ],
+    );
+    let controllers: Vec<_> = track
+        .slice(15, 100)
+        .events()
+        .iter()
+        .filter_map(|e| match e.event().channel_voice()?.event() {
+            VoiceEvent::ControlChange(c) => Some(c.to_bytes()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        controllers,
+        [[99, 1], [98, 8], [101, 0], [100, 0], [6, 12], [38, 0]]
+    );
+}
+
+#[test]
+fn slice_positions() {
+    let mut track = Track::new(Vec::new());
+    track.info_mut().tempo = Tempo::new(1_000_000);
+    track.info_mut().time_signature = TimeSignature::new_from_bytes([6, 3, 36, 8]);
+    let file = MidiFile::new(
+        Format::SingleMultiChannel(track),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    assert_eq!(file.tick_at(Position::bar(2)), Ok(576));
+    assert_eq!(file.tick_at(Position::Bar { bar: 1, beat: 1 }), Ok(336));
+    assert_eq!(file.tick_at(UMicros::from_ms(1_500).into()), Ok(144));
+    assert_eq!(
+        file.slice(20, 10),
+        Err(EditError::InvalidRegion { start: 20, end: 10 })
+    );
+}
@@ -1,6 +1,9 @@
 mod smpte;
 pub use smpte::*;
 
+mod tempo_map;
+pub use tempo_map::*;
+
 use crate::{prelude::*, reader::ReaderError};
 
 /// The header timing type.
@@ -60,6 +63,46 @@ impl Timing {
             _ => None,
         }
     }
+
+    /// The length of one tick in microseconds.
+    ///
+    /// The tempo is ignored for [`Timing::Smpte`], since its ticks are absolute.
+    pub fn micros_per_tick(&self, tempo: Tempo) -> f64 {
+        match self {
+            Self::TicksPerQuarterNote(tpqn) => {
+                tempo.micros_per_quarter_note() as f64 / tpqn.ticks_per_quarter_note() as f64
+            }
+            Self::Smpte(smpte) => {
+                let ticks_per_second =
+                    smpte.fps().as_division() as u32 * smpte.ticks_per_frame() as u32;
+                1_000_000. / ticks_per_second as f64
+            }
+        }
+    }
+
+    /// Convert a duration into the nearest number of ticks at the given tempo
+    pub fn micros_to_ticks(&self, micros: UMicros, tempo: Tempo) -> u32 {
+        (micros.us() as f64 / self.micros_per_tick(tempo) + 0.5) as u32
+    }
+
+    /// Convert a number of ticks into a duration at the given tempo
+    pub fn ticks_to_micros(&self, ticks: u32, tempo: Tempo) -> UMicros {
+        UMicros::new((ticks as f64 * self.micros_per_tick(tempo) + 0.5) as u64)
+    }
+}
+
+#[test]
+fn tick_conversion() {
+    let timing = Timing::new_ticks_per_quarter_note(480);
+    let tempo = Tempo::default();
+    assert_eq!(timing.micros_to_ticks(UMicros::from_ms(500), tempo), 480);
+    assert_eq!(timing.ticks_to_micros(240, tempo), UMicros::from_ms(250));
+
+    let timing = Timing::new_smpte(SmpteFps::TwentyFive, DataByte::new_unchecked(40));
+    assert_eq!(
+        timing.micros_to_ticks(UMicros::from_ms(1_000), tempo),
+        1_000
+    );
 }
 
 /// A representation of the `tpqn` timing for a MIDI file
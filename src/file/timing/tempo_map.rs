@@ -0,0 +1,176 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+#[doc = r#"
+The tempo of a piece over time, in musical ticks.
+
+Converts between ticks and absolute time across tempo changes.
+Ticks are counted at whatever resolution the caller passes in,
+so the map must be built with ticks of that same resolution.
+
+# Example
+```rust
+# use midix::prelude::*;
+// 120bpm, then 60bpm from the second bar (at 96 ticks per quarter note)
+let map = TempoMap::new(Tempo::from_bpm(120.)).with_change(384, Tempo::from_bpm(60.));
+
+assert_eq!(map.ticks_to_micros(384, 96), UMicros::from_ms(2_000));
+assert_eq!(map.ticks_to_micros(480, 96), UMicros::from_ms(3_000));
+assert_eq!(map.micros_to_ticks(UMicros::from_ms(3_000), 96), 480);
+```
+"#]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TempoMap {
+    /// Sorted by tick. The first change is always at tick 0.
+    changes: Vec<(u32, Tempo)>,
+}
+
+impl Default for TempoMap {
+    fn default() -> Self {
+        Self::new(Tempo::default())
+    }
+}
+
+impl TempoMap {
+    /// A map with a single tempo
+    pub fn new(tempo: Tempo) -> Self {
+        Self {
+            changes: alloc::vec![(0, tempo)],
+        }
+    }
+
+    /// A map with the tempo of a file's first track.
+    ///
+    /// This is the tempo used when iterating over the file's events.
+    /// A track keeps only one tempo, the last tempo meta message read into it,
+    /// so the map never has tempo changes. Add them with [`TempoMap::insert`].
+    pub fn from_file(file: &MidiFile<'_>) -> Self {
+        let tempo = file
+            .tracks()
+            .first()
+            .map(|track| track.info().tempo)
+            .unwrap_or_default();
+        Self::new(tempo)
+    }
+
+    /// Change the tempo at a tick, replacing any change already on that tick
+    pub fn insert(&mut self, tick: u32, tempo: Tempo) {
+        match self.changes.binary_search_by_key(&tick, |(t, _)| *t) {
+            Ok(index) => self.changes[index].1 = tempo,
+            Err(index) => self.changes.insert(index, (tick, tempo)),
+        }
+    }
+
+    /// Change the tempo at a tick, returning self
+    pub fn with_change(mut self, tick: u32, tempo: Tempo) -> Self {
+        self.insert(tick, tempo);
+        self
+    }
+
+    /// Returns every tempo change as `(tick, tempo)`, in order
+    pub fn changes(&self) -> &[(u32, Tempo)] {
+        &self.changes
+    }
+
+    /// Returns the tempo in effect at a tick
+    pub fn tempo_at(&self, tick: u32) -> Tempo {
+        let index = self.changes.partition_point(|(t, _)| *t <= tick);
+        self.changes[index.saturating_sub(1)].1
+    }
+
+    /// Convert a tick into time since tick 0, to the nearest microsecond
+    pub fn ticks_to_micros(&self, tick: u32, ticks_per_quarter_note: u16) -> UMicros {
+        let tpqn = ticks_per_quarter_note.max(1) as u128;
+        UMicros::new(((self.scaled_micros(tick) + tpqn / 2) / tpqn) as u64)
+    }
+
+    /// Convert time since tick 0 into the nearest tick
+    pub fn micros_to_ticks(&self, micros: UMicros, ticks_per_quarter_note: u16) -> u32 {
+        let scaled = micros.us() as u128 * ticks_per_quarter_note.max(1) as u128;
+        self.tick_at_scaled(scaled, 1, Rounding::Nearest)
+    }
+
+    /// Returns microseconds multiplied by ticks per quarter note, which is exact
+    pub(crate) fn scaled_micros(&self, tick: u32) -> u128 {
+        let mut total = 0;
+        for (index, (start, tempo)) in self.changes.iter().enumerate() {
+            if *start >= tick {
+                break;
+            }
+            let end = self
+                .changes
+                .get(index + 1)
+                .map_or(tick, |(next, _)| (*next).min(tick));
+            total += (end - start) as u128 * tempo.micros_per_quarter_note() as u128;
+        }
+        total
+    }
+
+    /// The tick at `numerator / denominator` scaled microseconds
+    pub(crate) fn tick_at_scaled(
+        &self,
+        numerator: u128,
+        denominator: u128,
+        rounding: Rounding,
+    ) -> u32 {
+        let mut scaled_start = 0u128;
+        for (index, (start, tempo)) in self.changes.iter().enumerate() {
+            let per_tick = tempo.micros_per_quarter_note().max(1) as u128;
+            if let Some((next, _)) = self.changes.get(index + 1) {
+                let scaled_end = scaled_start + (next - start) as u128 * per_tick;
+                if numerator < scaled_end * denominator {
+                    let into = numerator.saturating_sub(scaled_start * denominator);
+                    return start + rounding.divide(into, denominator * per_tick) as u32;
+                }
+                scaled_start = scaled_end;
+            } else {
+                let into = numerator.saturating_sub(scaled_start * denominator);
+                let ticks = rounding.divide(into, denominator * per_tick);
+                return (*start as u128 + ticks).min(u32::MAX as u128) as u32;
+            }
+        }
+        unreachable!("a tempo map always has a tempo at tick 0")
+    }
+}
+
+/// How a converted position that falls between two ticks is rounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// To the nearest tick, with halves rounded up
+    #[default]
+    Nearest,
+    /// To the earlier tick
+    Down,
+    /// To the later tick
+    Up,
+}
+
+impl Rounding {
+    /// Divide `numerator` by `denominator`, rounding the result
+    pub const fn divide(&self, numerator: u128, denominator: u128) -> u128 {
+        match self {
+            Self::Nearest => (numerator + denominator / 2) / denominator,
+            Self::Down => numerator / denominator,
+            Self::Up => numerator.div_ceil(denominator),
+        }
+    }
+}
+
+#[test]
+fn tempo_changes() {
+    let map = TempoMap::new(Tempo::new(500_000))
+        .with_change(96, Tempo::new(1_000_000))
+        .with_change(192, Tempo::new(250_000));
+    assert_eq!(map.tempo_at(95), Tempo::new(500_000));
+    assert_eq!(map.tempo_at(96), Tempo::new(1_000_000));
+
+    assert_eq!(map.ticks_to_micros(48, 96), UMicros::from_ms(250));
+    assert_eq!(map.ticks_to_micros(144, 96), UMicros::from_ms(1_000));
+    assert_eq!(map.ticks_to_micros(288, 96), UMicros::from_ms(1_750));
+
+    for tick in [0, 48, 96, 150, 192, 288, 1_000] {
+        let micros = map.ticks_to_micros(tick, 96);
+        assert_eq!(map.micros_to_ticks(micros, 96), tick);
+    }
+}
@@ -8,12 +8,14 @@ pub use event::*;
 mod message;
 pub use message::*;
 
+use core::ops::{Bound, Range, RangeBounds};
+
 use alloc::vec::Vec;
 
 use crate::{
     channel::Channel,
     events::LiveEvent,
-    file::{BytesText, SmpteOffset, Tempo, TimeSignature},
+    file::{BytesText, KeySignature, SmpteOffset, Tempo, TimeSignature},
     message::Ticked,
 };
 
@@ -70,14 +72,164 @@ impl<'a> Track<'a> {
         }
     }
 
+    /// Create a track from events positioned by their accumulated ticks.
+    ///
+    /// The events do not need to be sorted. Events on the same tick keep their relative order.
+    pub fn from_ticked(info: TrackInfo<'a>, mut events: Vec<Ticked<LiveEvent<'a>>>) -> Self {
+        events.sort_by_key(Ticked::accumulated_ticks);
+        Self { info, events }
+    }
+
     /// Get information about the track
     pub fn info(&self) -> &TrackInfo<'a> {
         &self.info
     }
+
+    /// Get a mutable reference to the information about the track
+    pub fn info_mut(&mut self) -> &mut TrackInfo<'a> {
+        &mut self.info
+    }
+
     /// Get the timed events for the track
     pub fn events(&self) -> &[Ticked<LiveEvent<'a>>] {
         self.events.as_slice()
     }
+
+    /// Returns the owned events of the track, sorted by tick
+    pub fn into_events(self) -> Vec<Ticked<LiveEvent<'a>>> {
+        self.events
+    }
+
+    /// Returns the owned info and events of the track
+    pub fn into_parts(self) -> (TrackInfo<'a>, Vec<Ticked<LiveEvent<'a>>>) {
+        (self.info, self.events)
+    }
+
+    /// Iterate over the events mutably, alongside their ticks.
+    ///
+    /// Use [`Track::move_event`] to change when an event happens.
+    pub fn events_mut(&mut self) -> impl Iterator<Item = (u32, &mut LiveEvent<'a>)> {
+        self.events
+            .iter_mut()
+            .map(|e| (e.accumulated_ticks(), e.event_mut()))
+    }
+
+    /// Returns the number of events in the track
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if the track has no events
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the tick of the last event, or 0 for an empty track
+    pub fn end_tick(&self) -> u32 {
+        self.events
+            .last()
+            .map(Ticked::accumulated_ticks)
+            .unwrap_or(0)
+    }
+
+    /// Returns the events within a range of ticks
+    pub fn range<R: RangeBounds<u32>>(&self, ticks: R) -> &[Ticked<LiveEvent<'a>>] {
+        let indices = self.indices_of(ticks);
+        &self.events[indices]
+    }
+
+    /// Insert an event at a tick, returning its index.
+    ///
+    /// The event is placed after any events already on that tick.
+    pub fn insert(&mut self, tick: u32, event: LiveEvent<'a>) -> usize {
+        let index = self
+            .events
+            .partition_point(|e| e.accumulated_ticks() <= tick);
+        self.events.insert(index, Ticked::new(tick, event));
+        index
+    }
+
+    /// Insert many events, keeping the track sorted
+    pub fn extend<I>(&mut self, events: I)
+    where
+        I: IntoIterator<Item = Ticked<LiveEvent<'a>>>,
+    {
+        self.events.extend(events);
+        self.events.sort_by_key(Ticked::accumulated_ticks);
+    }
+
+    /// Remove the event at an index
+    ///
+    /// # Panics
+    /// If `index` is out of bounds
+    pub fn remove(&mut self, index: usize) -> Ticked<LiveEvent<'a>> {
+        self.events.remove(index)
+    }
+
+    /// Remove the first event on `tick` that equals `event`
+    pub fn remove_at(&mut self, tick: u32, event: &LiveEvent<'a>) -> Option<Ticked<LiveEvent<'a>>> {
+        let index = self.position_at(tick, event)?;
+        Some(self.events.remove(index))
+    }
+
+    /// Remove every event within a range of ticks
+    pub fn remove_range<R: RangeBounds<u32>>(&mut self, ticks: R) -> Vec<Ticked<LiveEvent<'a>>> {
+        let indices = self.indices_of(ticks);
+        self.events.drain(indices).collect()
+    }
+
+    /// Move the event at an index to a new tick, returning its new index.
+    ///
+    /// The event is placed after any events already on that tick.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds
+    pub fn move_event(&mut self, index: usize, to_tick: u32) -> usize {
+        let event = self.events.remove(index).into_event();
+        self.insert(to_tick, event)
+    }
+
+    /// Move the first event on `tick` that equals `event` to a new tick.
+    ///
+    /// Returns the new index of the event, if it was found.
+    pub fn move_at(&mut self, tick: u32, event: &LiveEvent<'a>, to_tick: u32) -> Option<usize> {
+        let index = self.position_at(tick, event)?;
+        Some(self.move_event(index, to_tick))
+    }
+
+    /// Only keep the events for which the predicate returns true
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Ticked<LiveEvent<'a>>) -> bool,
+    {
+        self.events.retain(f);
+    }
+
+    fn position_at(&self, tick: u32, event: &LiveEvent<'a>) -> Option<usize> {
+        let start = self
+            .events
+            .partition_point(|e| e.accumulated_ticks() < tick);
+        self.events[start..]
+            .iter()
+            .take_while(|e| e.accumulated_ticks() == tick)
+            .position(|e| e.event() == event)
+            .map(|offset| start + offset)
+    }
+
+    /// The indices of the events within a range of ticks
+    fn indices_of<R: RangeBounds<u32>>(&self, ticks: R) -> Range<usize> {
+        let start = match ticks.start_bound() {
+            Bound::Included(&t) => self.events.partition_point(|e| e.accumulated_ticks() < t),
+            Bound::Excluded(&t) => self.events.partition_point(|e| e.accumulated_ticks() <= t),
+            Bound::Unbounded => 0,
+        };
+        let end = match ticks.end_bound() {
+            Bound::Included(&t) => self.events.partition_point(|e| e.accumulated_ticks() <= t),
+            Bound::Excluded(&t) => self.events.partition_point(|e| e.accumulated_ticks() < t),
+            Bound::Unbounded => self.events.len(),
+        };
+        start..end.max(start)
+    }
 }
 
 /// Provides information about the track
@@ -86,14 +238,109 @@ impl<'a> Track<'a> {
 #[cfg_attr(feature = "bevy", derive(bevy::reflect::Reflect))]
 pub struct TrackInfo<'a> {
     pub time_signature: TimeSignature,
+    pub key_signature: Option<KeySignature>,
     pub name: Option<BytesText<'a>>,
+    pub instrument: Option<BytesText<'a>>,
     pub device: Option<BytesText<'a>>,
     pub track_info: Option<u16>,
     pub channel: Option<Channel>,
     pub tempo: Tempo,
     /// this is intentionally allowed if the file doesn't identify as using smpte.
     pub smpte_offset: Option<SmpteOffset>,
+    /// The names of the channels of a track merged from several tracks,
+    /// so they can be given back when it is split
+    pub(crate) channel_info: Vec<ChannelInfo<'a>>,
+}
+
+/// The name and instrument of one channel in a track that holds several,
+/// kept from the track the channel came from
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy::reflect::Reflect))]
+pub(crate) struct ChannelInfo<'a> {
+    /// The channel
+    pub(crate) channel: Channel,
+    /// The name of the channel's track
+    pub(crate) name: Option<BytesText<'a>>,
+    /// The instrument name of the channel's track
+    pub(crate) instrument: Option<BytesText<'a>>,
 }
 
 #[test]
 fn get_accumulated_ticks() {}
+
+#[cfg(test)]
+fn note_on(note: u8) -> LiveEvent<'static> {
+    use crate::prelude::*;
+    Channel::One
+        .send_event(VoiceEvent::note_on(
+            Note::from_databyte(note).unwrap(),
+            Velocity::MAX,
+        ))
+        .into()
+}
+
+#[cfg(test)]
+fn ticks_and_notes(track: &Track) -> Vec<(u32, u8)> {
+    track
+        .events()
+        .iter()
+        .map(|e| {
+            let note = e.event().channel_voice().unwrap().note().unwrap();
+            (e.accumulated_ticks(), note.byte())
+        })
+        .collect()
+}
+
+#[test]
+fn insert_keeps_events_sorted() {
+    let mut track = Track::new(Vec::new());
+    assert_eq!(track.insert(96, note_on(62)), 0);
+    assert_eq!(track.insert(0, note_on(60)), 0);
+    // inserted after the existing event on the same tick
+    assert_eq!(track.insert(96, note_on(64)), 2);
+
+    assert_eq!(ticks_and_notes(&track), [(0, 60), (96, 62), (96, 64)]);
+    assert_eq!(track.end_tick(), 96);
+}
+
+#[test]
+fn remove_and_move_by_tick() {
+    let mut track = Track::from_ticked(
+        TrackInfo::default(),
+        alloc::vec![
+            Ticked::new(192, note_on(64)),
+            Ticked::new(0, note_on(60)),
+            Ticked::new(96, note_on(62)),
+        ],
+    );
+    assert_eq!(ticks_and_notes(&track), [(0, 60), (96, 62), (192, 64)]);
+
+    assert!(track.remove_at(96, &note_on(60)).is_none());
+    assert_eq!(
+        track.remove_at(96, &note_on(62)),
+        Some(Ticked::new(96, note_on(62)))
+    );
+
+    assert_eq!(track.move_at(0, &note_on(60), 300), Some(1));
+    assert_eq!(ticks_and_notes(&track), [(192, 64), (300, 60)]);
+}
+
+#[test]
+fn range_deletion_and_retain() {
+    let mut track = Track::from_ticked(
+        TrackInfo::default(),
+        (0..8)
+            .map(|i| Ticked::new(i * 10, note_on(60 + i as u8)))
+            .collect(),
+    );
+    assert_eq!(track.range(20..40).len(), 2);
+
+    let removed = track.remove_range(20..=40);
+    assert_eq!(removed.len(), 3);
+
+    track.retain(|e| e.accumulated_ticks() != 70);
+    assert_eq!(
+        ticks_and_notes(&track),
+        [(0, 60), (10, 61), (50, 65), (60, 66)]
+    );
+}
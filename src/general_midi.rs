@@ -0,0 +1,864 @@
+use core::{fmt, ops::RangeInclusive};
+
+use crate::prelude::*;
+
+/// The General MIDI 1 program names, by program number
+const PROGRAM_NAMES: [&str; 128] = [
+    // piano
+    "Acoustic Grand Piano",
+    "Bright Acoustic Piano",
+    "Electric Grand Piano",
+    "Honky-tonk Piano",
+    "Electric Piano 1",
+    "Electric Piano 2",
+    "Harpsichord",
+    "Clavi",
+    // chromatic percussion
+    "Celesta",
+    "Glockenspiel",
+    "Music Box",
+    "Vibraphone",
+    "Marimba",
+    "Xylophone",
+    "Tubular Bells",
+    "Dulcimer",
+    // organ
+    "Drawbar Organ",
+    "Percussive Organ",
+    "Rock Organ",
+    "Church Organ",
+    "Reed Organ",
+    "Accordion",
+    "Harmonica",
+    "Tango Accordion",
+    // guitar
+    "Acoustic Guitar (nylon)",
+    "Acoustic Guitar (steel)",
+    "Electric Guitar (jazz)",
+    "Electric Guitar (clean)",
+    "Electric Guitar (muted)",
+    "Overdriven Guitar",
+    "Distortion Guitar",
+    "Guitar Harmonics",
+    // bass
+    "Acoustic Bass",
+    "Electric Bass (finger)",
+    "Electric Bass (pick)",
+    "Fretless Bass",
+    "Slap Bass 1",
+    "Slap Bass 2",
+    "Synth Bass 1",
+    "Synth Bass 2",
+    // strings
+    "Violin",
+    "Viola",
+    "Cello",
+    "Contrabass",
+    "Tremolo Strings",
+    "Pizzicato Strings",
+    "Orchestral Harp",
+    "Timpani",
+    // ensemble
+    "String Ensemble 1",
+    "String Ensemble 2",
+    "Synth Strings 1",
+    "Synth Strings 2",
+    "Choir Aahs",
+    "Voice Oohs",
+    "Synth Voice",
+    "Orchestra Hit",
+    // brass
+    "Trumpet",
+    "Trombone",
+    "Tuba",
+    "Muted Trumpet",
+    "French Horn",
+    "Brass Section",
+    "Synth Brass 1",
+    "Synth Brass 2",
+    // reed
+    "Soprano Sax",
+    "Alto Sax",
+    "Tenor Sax",
+    "Baritone Sax",
+    "Oboe",
+    "English Horn",
+    "Bassoon",
+    "Clarinet",
+    // pipe
+    "Piccolo",
+    "Flute",
+    "Recorder",
+    "Pan Flute",
+    "Blown Bottle",
+    "Shakuhachi",
+    "Whistle",
+    "Ocarina",
+    // synth lead
+    "Lead 1 (square)",
+    "Lead 2 (sawtooth)",
+    "Lead 3 (calliope)",
+    "Lead 4 (chiff)",
+    "Lead 5 (charang)",
+    "Lead 6 (voice)",
+    "Lead 7 (fifths)",
+    "Lead 8 (bass + lead)",
+    // synth pad
+    "Pad 1 (new age)",
+    "Pad 2 (warm)",
+    "Pad 3 (polysynth)",
+    "Pad 4 (choir)",
+    "Pad 5 (bowed)",
+    "Pad 6 (metallic)",
+    "Pad 7 (halo)",
+    "Pad 8 (sweep)",
+    // synth effects
+    "FX 1 (rain)",
+    "FX 2 (soundtrack)",
+    "FX 3 (crystal)",
+    "FX 4 (atmosphere)",
+    "FX 5 (brightness)",
+    "FX 6 (goblins)",
+    "FX 7 (echoes)",
+    "FX 8 (sci-fi)",
+    // ethnic
+    "Sitar",
+    "Banjo",
+    "Shamisen",
+    "Koto",
+    "Kalimba",
+    "Bag Pipe",
+    "Fiddle",
+    "Shanai",
+    // percussive
+    "Tinkle Bell",
+    "Agogo",
+    "Steel Drums",
+    "Woodblock",
+    "Taiko Drum",
+    "Melodic Tom",
+    "Synth Drum",
+    "Reverse Cymbal",
+    // sound effects
+    "Guitar Fret Noise",
+    "Breath Noise",
+    "Seashore",
+    "Bird Tweet",
+    "Telephone Ring",
+    "Helicopter",
+    "Applause",
+    "Gunshot",
+];
+
+/// The General MIDI 2 variations, in bank 121 with a bank LSB above 0, by program and LSB
+const GM2_VARIATION_NAMES: [(u8, u8, &str); 128] = [
+    (0, 1, "Wide Acoustic Grand"),
+    (0, 2, "Dark Acoustic Grand"),
+    (1, 1, "Wide Bright Acoustic"),
+    (2, 1, "Wide Electric Grand"),
+    (3, 1, "Wide Honky-tonk"),
+    (4, 1, "Detuned Electric Piano 1"),
+    (4, 2, "Electric Piano 1 Variation"),
+    (4, 3, "60's Electric Piano"),
+    (5, 1, "Detuned Electric Piano 2"),
+    (5, 2, "Electric Piano 2 Variation"),
+    (5, 3, "Legend Electric Piano"),
+    (5, 4, "Phase Electric Piano"),
+    (6, 1, "Coupled Harpsichord"),
+    (6, 2, "Wide Harpsichord"),
+    (6, 3, "Open Harpsichord"),
+    (7, 1, "Pulse Clavinet"),
+    (11, 1, "Wet Vibraphone"),
+    (12, 1, "Wide Marimba"),
+    (14, 1, "Church Bell"),
+    (14, 2, "Carillon"),
+    (16, 1, "Detuned Drawbar Organ"),
+    (16, 2, "Italian 60's Organ"),
+    (16, 3, "Drawbar Organ 2"),
+    (17, 1, "Detuned Percussive Organ"),
+    (17, 2, "Percussive Organ 2"),
+    (19, 1, "Church Organ (Octave Mix)"),
+    (19, 2, "Detuned Church Organ"),
+    (20, 1, "Puff Organ"),
+    (21, 1, "Accordion 2"),
+    (24, 1, "Ukulele"),
+    (24, 2, "Open Nylon Guitar"),
+    (24, 3, "Nylon Guitar 2"),
+    (25, 1, "12-String Guitar"),
+    (25, 2, "Mandolin"),
+    (25, 3, "Steel Guitar with Body Sound"),
+    (26, 1, "Pedal Steel Guitar"),
+    (27, 1, "Detuned Clean Electric Guitar"),
+    (27, 2, "Mid Tone Guitar"),
+    (28, 1, "Funk Cutting Muted Guitar"),
+    (28, 2, "Muted Pick Guitar"),
+    (28, 3, "Jazz Man"),
+    (29, 1, "Guitar Pinch"),
+    (30, 1, "Distortion Guitar (with Feedback)"),
+    (30, 2, "Distorted Rhythm Guitar"),
+    (31, 1, "Guitar Feedback"),
+    (33, 1, "Finger Slap Bass"),
+    (38, 1, "Synth Bass (warm)"),
+    (38, 2, "Synth Bass 3 (resonance)"),
+    (38, 3, "Clavi Bass"),
+    (38, 4, "Hammer"),
+    (39, 1, "Synth Bass 4 (attack)"),
+    (39, 2, "Synth Bass (rubber)"),
+    (39, 3, "Attack Pulse"),
+    (40, 1, "Slow Violin"),
+    (46, 1, "Yang Qin"),
+    (48, 1, "Strings and Brass"),
+    (48, 2, "60s Strings"),
+    (50, 1, "Synth Strings 3"),
+    (52, 1, "Choir Aahs 2"),
+    (53, 1, "Humming"),
+    (54, 1, "Analog Voice"),
+    (55, 1, "Bass Hit"),
+    (55, 2, "6th Hit"),
+    (55, 3, "Euro Hit"),
+    (56, 1, "Dark Trumpet"),
+    (57, 1, "Trombone 2"),
+    (57, 2, "Bright Trombone"),
+    (59, 1, "Muted Trumpet 2"),
+    (60, 1, "French Horn 2 (warm)"),
+    (61, 1, "Brass Section 2 (octave mix)"),
+    (62, 1, "Synth Brass 3"),
+    (62, 2, "Analog Synth Brass 1"),
+    (62, 3, "Jump Brass"),
+    (63, 1, "Synth Brass 4"),
+    (63, 2, "Analog Synth Brass 2"),
+    (80, 1, "Square"),
+    (80, 2, "Sine Wave"),
+    (81, 1, "Saw"),
+    (81, 2, "Doctor Solo"),
+    (81, 3, "Natural Lead"),
+    (81, 4, "Sequenced Saw"),
+    (84, 1, "Wire Lead"),
+    (87, 1, "Delayed Lead"),
+    (89, 1, "Sine Pad"),
+    (91, 1, "Itopia"),
+    (98, 1, "Synth Mallet"),
+    (102, 1, "Echo Bell"),
+    (102, 2, "Echo Pan"),
+    (104, 1, "Sitar 2"),
+    (107, 1, "Taisho Koto"),
+    (115, 1, "Castanets"),
+    (116, 1, "Concert Bass Drum"),
+    (117, 1, "Melodic Tom 2"),
+    (118, 1, "Rhythm Box Tom"),
+    (118, 2, "Electric Drum"),
+    (120, 1, "Guitar Cutting Noise"),
+    (120, 2, "Acoustic Bass String Slap"),
+    (121, 1, "Flute Key Click"),
+    (122, 1, "Rain"),
+    (122, 2, "Thunder"),
+    (122, 3, "Wind"),
+    (122, 4, "Stream"),
+    (122, 5, "Bubble"),
+    (123, 1, "Dog"),
+    (123, 2, "Horse Gallop"),
+    (123, 3, "Bird Tweet 2"),
+    (124, 1, "Telephone Ring 2"),
+    (124, 2, "Door Creaking"),
+    (124, 3, "Door"),
+    (124, 4, "Scratch"),
+    (124, 5, "Wind Chime"),
+    (125, 1, "Car Engine"),
+    (125, 2, "Car Stop"),
+    (125, 3, "Car Pass"),
+    (125, 4, "Car Crash"),
+    (125, 5, "Siren"),
+    (125, 6, "Train"),
+    (125, 7, "Jetplane"),
+    (125, 8, "Starship"),
+    (125, 9, "Burst Noise"),
+    (126, 1, "Laughing"),
+    (126, 2, "Screaming"),
+    (126, 3, "Punch"),
+    (126, 4, "Heart Beat"),
+    (126, 5, "Footsteps"),
+    (127, 1, "Machine Gun"),
+    (127, 2, "Lasergun"),
+    (127, 3, "Explosion"),
+];
+
+/// The Roland GS variation tones of the SC-55 map, by program and bank MSB
+const GS_VARIATION_NAMES: [(u8, u8, &str); 88] = [
+    (0, 8, "Piano 1w"),
+    (0, 16, "Piano 1d"),
+    (1, 8, "Piano 2w"),
+    (2, 8, "Piano 3w"),
+    (3, 8, "Honky-tonk w"),
+    (4, 8, "Detuned EP 1"),
+    (4, 16, "E.Piano 1w"),
+    (4, 24, "60's E.Piano"),
+    (5, 8, "Detuned EP 2"),
+    (5, 16, "E.Piano 2w"),
+    (6, 8, "Coupled Hps."),
+    (6, 16, "Harpsi.w"),
+    (6, 24, "Harpsi.o"),
+    (11, 8, "Vib.w"),
+    (12, 8, "Marimba w"),
+    (14, 8, "Church Bell"),
+    (14, 9, "Carillon"),
+    (16, 8, "Detuned Or.1"),
+    (16, 16, "60's Organ 1"),
+    (16, 32, "Organ 4"),
+    (17, 8, "Detuned Or.2"),
+    (17, 32, "Organ 5"),
+    (19, 8, "Church Org.2"),
+    (19, 16, "Church Org.3"),
+    (21, 8, "Accordion It"),
+    (24, 8, "Ukulele"),
+    (24, 16, "Nylon Gt.o"),
+    (24, 32, "Nylon Gt.2"),
+    (25, 8, "12-str.Gt"),
+    (25, 16, "Mandolin"),
+    (26, 8, "Hawaiian Gt."),
+    (27, 8, "Chorus Gt."),
+    (28, 8, "Funk Gt."),
+    (30, 8, "Feedback Gt."),
+    (31, 8, "Gt. Feedback"),
+    (38, 8, "Synth Bass 3"),
+    (39, 8, "Synth Bass 4"),
+    (48, 8, "Orchestra"),
+    (50, 8, "Syn.Strings3"),
+    (61, 8, "Brass 2"),
+    (62, 8, "Synth Brass3"),
+    (63, 8, "Synth Brass4"),
+    (80, 1, "Square"),
+    (80, 8, "Sine Wave"),
+    (81, 1, "Saw"),
+    (81, 8, "Doctor Solo"),
+    (98, 1, "Syn Mallet"),
+    (102, 1, "Echo Bell"),
+    (102, 2, "Echo Pan"),
+    (104, 1, "Sitar 2"),
+    (107, 8, "Taisho Koto"),
+    (115, 8, "Castanets"),
+    (116, 8, "Concert BD"),
+    (117, 8, "Melo. Tom 2"),
+    (118, 8, "808 Tom"),
+    (120, 1, "Gt.Cut Noise"),
+    (120, 2, "String Slap"),
+    (121, 1, "Fl.Key Click"),
+    (122, 1, "Rain"),
+    (122, 2, "Thunder"),
+    (122, 3, "Wind"),
+    (122, 4, "Stream"),
+    (122, 5, "Bubble"),
+    (123, 1, "Dog"),
+    (123, 2, "Horse-Gallop"),
+    (123, 3, "Bird 2"),
+    (124, 1, "Telephone 2"),
+    (124, 2, "DoorCreaking"),
+    (124, 3, "Door"),
+    (124, 4, "Scratch"),
+    (124, 5, "Wind Chimes"),
+    (125, 1, "Car-Engine"),
+    (125, 2, "Car-Stop"),
+    (125, 3, "Car-Pass"),
+    (125, 4, "Car-Crash"),
+    (125, 5, "Siren"),
+    (125, 6, "Train"),
+    (125, 7, "Jetplane"),
+    (125, 8, "Starship"),
+    (125, 9, "Burst Noise"),
+    (126, 1, "Laughing"),
+    (126, 2, "Screaming"),
+    (126, 3, "Punch"),
+    (126, 4, "Heart Beat"),
+    (126, 5, "Footsteps"),
+    (127, 1, "Machine Gun"),
+    (127, 2, "Lasergun"),
+    (127, 3, "Explosion"),
+];
+
+/// The General MIDI 1 percussion names, from note 35
+const PERCUSSION_NAMES: [&str; 47] = [
+    "Acoustic Bass Drum",
+    "Bass Drum 1",
+    "Side Stick",
+    "Acoustic Snare",
+    "Hand Clap",
+    "Electric Snare",
+    "Low Floor Tom",
+    "Closed Hi-Hat",
+    "High Floor Tom",
+    "Pedal Hi-Hat",
+    "Low Tom",
+    "Open Hi-Hat",
+    "Low-Mid Tom",
+    "Hi-Mid Tom",
+    "Crash Cymbal 1",
+    "High Tom",
+    "Ride Cymbal 1",
+    "Chinese Cymbal",
+    "Ride Bell",
+    "Tambourine",
+    "Splash Cymbal",
+    "Cowbell",
+    "Crash Cymbal 2",
+    "Vibraslap",
+    "Ride Cymbal 2",
+    "Hi Bongo",
+    "Low Bongo",
+    "Mute Hi Conga",
+    "Open Hi Conga",
+    "Low Conga",
+    "High Timbale",
+    "Low Timbale",
+    "High Agogo",
+    "Low Agogo",
+    "Cabasa",
+    "Maracas",
+    "Short Whistle",
+    "Long Whistle",
+    "Short Guiro",
+    "Long Guiro",
+    "Claves",
+    "Hi Wood Block",
+    "Low Wood Block",
+    "Mute Cuica",
+    "Open Cuica",
+    "Mute Triangle",
+    "Open Triangle",
+];
+
+/// The General MIDI 2 and GS percussion names below note 35, from note 27
+const GM2_LOW_PERCUSSION_NAMES: [&str; 8] = [
+    "High Q",
+    "Slap",
+    "Scratch Push",
+    "Scratch Pull",
+    "Sticks",
+    "Square Click",
+    "Metronome Click",
+    "Metronome Bell",
+];
+
+/// The General MIDI 2 and GS percussion names above note 81, from note 82
+const GM2_HIGH_PERCUSSION_NAMES: [&str; 6] = [
+    "Shaker",
+    "Jingle Bell",
+    "Bell Tree",
+    "Castanets",
+    "Mute Surdo",
+    "Open Surdo",
+];
+
+/// The XG percussion names below note 35, from note 13
+const XG_LOW_PERCUSSION_NAMES: [&str; 22] = [
+    "Surdo Mute",
+    "Surdo Open",
+    "Hi Q",
+    "Whip Slap",
+    "Scratch Push",
+    "Scratch Pull",
+    "Finger Snap",
+    "Click Noise",
+    "Metronome Click",
+    "Metronome Bell",
+    "Seq Click L",
+    "Seq Click H",
+    "Brush Tap",
+    "Brush Swirl L",
+    "Brush Slap",
+    "Brush Swirl H",
+    "Snare Roll",
+    "Castanet",
+    "Snare L",
+    "Sticks",
+    "Bass Drum L",
+    "Open Rim Shot",
+];
+
+/// The XG percussion names above note 81, from note 82
+const XG_HIGH_PERCUSSION_NAMES: [&str; 3] = ["Shaker", "Jingle Bells", "Bell Tree"];
+
+/// The sixteen groups of eight programs in General MIDI 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InstrumentFamily {
+    /// Programs 0 to 7
+    Piano,
+    /// Programs 8 to 15
+    ChromaticPercussion,
+    /// Programs 16 to 23
+    Organ,
+    /// Programs 24 to 31
+    Guitar,
+    /// Programs 32 to 39
+    Bass,
+    /// Programs 40 to 47
+    Strings,
+    /// Programs 48 to 55
+    Ensemble,
+    /// Programs 56 to 63
+    Brass,
+    /// Programs 64 to 71
+    Reed,
+    /// Programs 72 to 79
+    Pipe,
+    /// Programs 80 to 87
+    SynthLead,
+    /// Programs 88 to 95
+    SynthPad,
+    /// Programs 96 to 103
+    SynthEffects,
+    /// Programs 104 to 111
+    Ethnic,
+    /// Programs 112 to 119
+    Percussive,
+    /// Programs 120 to 127
+    SoundEffects,
+}
+
+impl InstrumentFamily {
+    /// Every family, by program number
+    pub const ALL: [InstrumentFamily; 16] = [
+        InstrumentFamily::Piano,
+        InstrumentFamily::ChromaticPercussion,
+        InstrumentFamily::Organ,
+        InstrumentFamily::Guitar,
+        InstrumentFamily::Bass,
+        InstrumentFamily::Strings,
+        InstrumentFamily::Ensemble,
+        InstrumentFamily::Brass,
+        InstrumentFamily::Reed,
+        InstrumentFamily::Pipe,
+        InstrumentFamily::SynthLead,
+        InstrumentFamily::SynthPad,
+        InstrumentFamily::SynthEffects,
+        InstrumentFamily::Ethnic,
+        InstrumentFamily::Percussive,
+        InstrumentFamily::SoundEffects,
+    ];
+
+    /// Returns the name of the family, as in the General MIDI 1 specification
+    pub const fn name(&self) -> &'static str {
+        match self {
+            InstrumentFamily::Piano => "Piano",
+            InstrumentFamily::ChromaticPercussion => "Chromatic Percussion",
+            InstrumentFamily::Organ => "Organ",
+            InstrumentFamily::Guitar => "Guitar",
+            InstrumentFamily::Bass => "Bass",
+            InstrumentFamily::Strings => "Strings",
+            InstrumentFamily::Ensemble => "Ensemble",
+            InstrumentFamily::Brass => "Brass",
+            InstrumentFamily::Reed => "Reed",
+            InstrumentFamily::Pipe => "Pipe",
+            InstrumentFamily::SynthLead => "Synth Lead",
+            InstrumentFamily::SynthPad => "Synth Pad",
+            InstrumentFamily::SynthEffects => "Synth Effects",
+            InstrumentFamily::Ethnic => "Ethnic",
+            InstrumentFamily::Percussive => "Percussive",
+            InstrumentFamily::SoundEffects => "Sound Effects",
+        }
+    }
+
+    /// Returns the numbers of the programs in the family
+    pub const fn programs(&self) -> RangeInclusive<u8> {
+        let first = *self as u8 * 8;
+        first..=first + 7
+    }
+}
+
+impl fmt::Display for InstrumentFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A standard for the sounds of a synthesizer, which names drum kits and percussion
+/// beyond General MIDI 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SoundSet {
+    /// General MIDI 1, with one drum kit
+    #[default]
+    GeneralMidi,
+    /// General MIDI 2
+    GeneralMidi2,
+    /// Roland GS
+    Gs,
+    /// Yamaha XG, whose drum kits and percussion are named but whose voices are not
+    Xg,
+}
+
+impl SoundSet {
+    /// Returns the sound set a bank select can only come from, if any.
+    ///
+    /// Banks 0 and 0, the General MIDI sounds in every set, return `None`.
+    /// A bank LSB of 1 to 4 is a GS map select, and an LSB above that with an MSB of 0
+    /// is an XG variation.
+    pub const fn from_bank(msb: u8, lsb: u8) -> Option<SoundSet> {
+        match (msb, lsb) {
+            (0, 0) => None,
+            (120 | 121, _) => Some(SoundSet::GeneralMidi2),
+            (64 | 126 | 127, 0) => Some(SoundSet::Xg),
+            (_, 0..=4) => Some(SoundSet::Gs),
+            (0, _) => Some(SoundSet::Xg),
+            _ => None,
+        }
+    }
+
+    /// Returns true if a bank select picks drum kits on any channel in this set.
+    ///
+    /// GS has no drum banks: its kits play on the rhythm channel, [`Channel::Ten`],
+    /// unless a system exclusive message moves it.
+    pub const fn is_drum_bank(&self, msb: u8) -> bool {
+        matches!(
+            (self, msb),
+            (SoundSet::GeneralMidi2, 120) | (SoundSet::Xg, 126 | 127)
+        )
+    }
+
+    /// Returns the name of the drum kit a program picks, if it is one in this set.
+    ///
+    /// In General MIDI 1 every program is the one kit.
+    pub const fn drum_kit(&self, program: Program) -> Option<&'static str> {
+        let name = match (self, program.byte()) {
+            (SoundSet::GeneralMidi, _) => "Standard Kit",
+            (SoundSet::GeneralMidi2, 0) => "Standard Set",
+            (SoundSet::GeneralMidi2, 8) => "Room Set",
+            (SoundSet::GeneralMidi2, 16) => "Power Set",
+            (SoundSet::GeneralMidi2, 24) => "Electronic Set",
+            (SoundSet::GeneralMidi2, 25) => "Analog Set",
+            (SoundSet::GeneralMidi2, 32) => "Jazz Set",
+            (SoundSet::GeneralMidi2, 40) => "Brush Set",
+            (SoundSet::GeneralMidi2, 48) => "Orchestra Set",
+            (SoundSet::GeneralMidi2, 56) => "SFX Set",
+            (SoundSet::Gs, 0) => "Standard",
+            (SoundSet::Gs, 8) => "Room",
+            (SoundSet::Gs, 16) => "Power",
+            (SoundSet::Gs, 24) => "Electronic",
+            (SoundSet::Gs, 25) => "TR-808",
+            (SoundSet::Gs, 32) => "Jazz",
+            (SoundSet::Gs, 40) => "Brush",
+            (SoundSet::Gs, 48) => "Orchestra",
+            (SoundSet::Gs, 56) => "SFX",
+            (SoundSet::Gs, 127) => "CM-64/32L",
+            (SoundSet::Xg, 0) => "Standard Kit 1",
+            (SoundSet::Xg, 1) => "Standard Kit 2",
+            (SoundSet::Xg, 8) => "Room Kit",
+            (SoundSet::Xg, 16) => "Rock Kit",
+            (SoundSet::Xg, 24) => "Electro Kit",
+            (SoundSet::Xg, 25) => "Analog Kit",
+            (SoundSet::Xg, 32) => "Jazz Kit",
+            (SoundSet::Xg, 40) => "Brush Kit",
+            (SoundSet::Xg, 48) => "Classic Kit",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// Returns the name of the percussion a note plays in the standard kit of this set, if any
+    pub fn percussion(&self, note: Note) -> Option<&'static str> {
+        let byte = note.byte() as usize;
+        if (35..=81).contains(&byte) {
+            return Some(PERCUSSION_NAMES[byte - 35]);
+        }
+        let name = match self {
+            SoundSet::GeneralMidi => return None,
+            SoundSet::GeneralMidi2 | SoundSet::Gs => match byte {
+                27..=34 => GM2_LOW_PERCUSSION_NAMES[byte - 27],
+                82..=87 => GM2_HIGH_PERCUSSION_NAMES[byte - 82],
+                _ => return None,
+            },
+            SoundSet::Xg => match byte {
+                13..=34 => XG_LOW_PERCUSSION_NAMES[byte - 13],
+                82..=84 => XG_HIGH_PERCUSSION_NAMES[byte - 82],
+                _ => return None,
+            },
+        };
+        Some(name)
+    }
+}
+
+impl Program {
+    /// Returns the General MIDI 1 name of the program, such as `"Acoustic Grand Piano"`
+    pub const fn name(&self) -> &'static str {
+        PROGRAM_NAMES[self.byte() as usize]
+    }
+
+    /// Returns the General MIDI 1 family of the program
+    pub const fn family(&self) -> InstrumentFamily {
+        InstrumentFamily::ALL[self.byte() as usize / 8]
+    }
+
+    /// Returns the name of the program on a channel, after a bank select of
+    /// controllers 0 and 32.
+    ///
+    /// [`Channel::Ten`] and drum banks name a kit. General MIDI 2 variations, in bank 121,
+    /// and the GS variation tones of the SC-55 map, in the bank MSB, have their own names.
+    ///
+    /// XG voice names are not included, so XG banks are named by the General MIDI 1 sound
+    /// they vary. So are variations a set doesn't have, which every set falls back to.
+    pub fn name_in_bank(
+        &self,
+        set: SoundSet,
+        (msb, lsb): (u8, u8),
+        channel: Channel,
+    ) -> &'static str {
+        if channel == Channel::Ten || set.is_drum_bank(msb) {
+            return set.drum_kit(*self).unwrap_or("Drum Kit");
+        }
+        if set == SoundSet::GeneralMidi2
+            && msb == 121
+            && let Ok(index) = GM2_VARIATION_NAMES
+                .binary_search_by_key(&(self.byte(), lsb), |(program, lsb, _)| (*program, *lsb))
+        {
+            return GM2_VARIATION_NAMES[index].2;
+        }
+        if set == SoundSet::Gs
+            && let Ok(index) = GS_VARIATION_NAMES
+                .binary_search_by_key(&(self.byte(), msb), |(program, msb, _)| (*program, *msb))
+        {
+            return GS_VARIATION_NAMES[index].2;
+        }
+        self.name()
+    }
+}
+
+impl Note {
+    /// Returns the General MIDI 1 percussion the note plays on [`Channel::Ten`], such as
+    /// `"Closed Hi-Hat"`. See [`SoundSet::percussion`] for the notes of other sets.
+    pub fn drum_name(&self) -> Option<&'static str> {
+        SoundSet::GeneralMidi.percussion(*self)
+    }
+}
+
+#[test]
+fn program_names() {
+    let program = |byte: u8| Program::new(byte).unwrap();
+    assert_eq!(program(0).name(), "Acoustic Grand Piano");
+    assert_eq!(program(40).name(), "Violin");
+    assert_eq!(program(127).name(), "Gunshot");
+    assert_eq!(program(0).family(), InstrumentFamily::Piano);
+    assert_eq!(program(81).family(), InstrumentFamily::SynthLead);
+    assert_eq!(program(127).family(), InstrumentFamily::SoundEffects);
+    for family in InstrumentFamily::ALL {
+        for byte in family.programs() {
+            assert_eq!(program(byte).family(), family);
+        }
+    }
+    assert_eq!(InstrumentFamily::ChromaticPercussion.programs(), 8..=15);
+}
+
+#[test]
+fn percussion_names() {
+    let note = |byte: u8| Note::from_databyte(byte).unwrap();
+    assert_eq!(note(42).drum_name(), Some("Closed Hi-Hat"));
+    assert_eq!(note(35).drum_name(), Some("Acoustic Bass Drum"));
+    assert_eq!(note(81).drum_name(), Some("Open Triangle"));
+    assert_eq!(note(34).drum_name(), None);
+    assert_eq!(note(82).drum_name(), None);
+
+    assert_eq!(SoundSet::GeneralMidi2.percussion(note(27)), Some("High Q"));
+    assert_eq!(SoundSet::Gs.percussion(note(87)), Some("Open Surdo"));
+    assert_eq!(SoundSet::Gs.percussion(note(88)), None);
+    assert_eq!(SoundSet::Xg.percussion(note(13)), Some("Surdo Mute"));
+    assert_eq!(SoundSet::Xg.percussion(note(38)), Some("Acoustic Snare"));
+    assert_eq!(SoundSet::Xg.percussion(note(85)), None);
+}
+
+#[test]
+fn banks_and_kits() {
+    let program = |byte: u8| Program::new(byte).unwrap();
+    assert_eq!(SoundSet::from_bank(0, 0), None);
+    assert_eq!(SoundSet::from_bank(121, 1), Some(SoundSet::GeneralMidi2));
+    assert_eq!(SoundSet::from_bank(127, 0), Some(SoundSet::Xg));
+    assert_eq!(SoundSet::from_bank(0, 40), Some(SoundSet::Xg));
+    assert_eq!(SoundSet::from_bank(8, 0), Some(SoundSet::Gs));
+    assert_eq!(SoundSet::from_bank(0, 1), Some(SoundSet::Gs));
+    assert_eq!(SoundSet::from_bank(0, 4), Some(SoundSet::Gs));
+    assert_eq!(SoundSet::from_bank(127, 3), Some(SoundSet::Gs));
+    assert_eq!(SoundSet::from_bank(0, 5), Some(SoundSet::Xg));
+
+    assert_eq!(SoundSet::Gs.drum_kit(program(25)), Some("TR-808"));
+    assert_eq!(
+        SoundSet::GeneralMidi2.drum_kit(program(25)),
+        Some("Analog Set")
+    );
+    assert_eq!(SoundSet::Xg.drum_kit(program(3)), None);
+    assert_eq!(
+        SoundSet::GeneralMidi.drum_kit(program(3)),
+        Some("Standard Kit")
+    );
+
+    let one = Channel::One;
+    assert_eq!(
+        program(32).name_in_bank(SoundSet::Xg, (127, 0), one),
+        "Jazz Kit"
+    );
+    assert_eq!(
+        program(32).name_in_bank(SoundSet::Xg, (0, 0), one),
+        "Acoustic Bass"
+    );
+    assert_eq!(
+        program(32).name_in_bank(SoundSet::GeneralMidi2, (121, 0), one),
+        "Acoustic Bass"
+    );
+    assert_eq!(
+        program(3).name_in_bank(SoundSet::GeneralMidi2, (120, 0), one),
+        "Drum Kit"
+    );
+    assert_eq!(
+        program(25).name_in_bank(SoundSet::Gs, (0, 0), Channel::Ten),
+        "TR-808"
+    );
+    assert_eq!(
+        program(25).name_in_bank(SoundSet::Gs, (8, 0), one),
+        "12-str.Gt"
+    );
+}
+
+#[test]
+fn gm2_variations() {
+    let program = |byte: u8| Program::new(byte).unwrap();
+    let gm2 = |byte: u8, lsb: u8| {
+        program(byte).name_in_bank(SoundSet::GeneralMidi2, (121, lsb), Channel::One)
+    };
+    assert!(
+        GM2_VARIATION_NAMES
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1))
+    );
+    assert_eq!(gm2(0, 2), "Dark Acoustic Grand");
+    assert_eq!(gm2(25, 2), "Mandolin");
+    assert_eq!(gm2(125, 9), "Burst Noise");
+    assert_eq!(gm2(127, 3), "Explosion");
+    // unknown variations fall back to the capital sound
+    assert_eq!(gm2(25, 9), "Acoustic Guitar (steel)");
+    assert_eq!(
+        program(25).name_in_bank(SoundSet::Xg, (0, 2), Channel::One),
+        "Acoustic Guitar (steel)"
+    );
+}
+
+#[test]
+fn gs_variations() {
+    let gs = |byte: u8, msb: u8| {
+        Program::new(byte)
+            .unwrap()
+            .name_in_bank(SoundSet::Gs, (msb, 0), Channel::One)
+    };
+    assert!(
+        GS_VARIATION_NAMES
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1))
+    );
+    assert_eq!(gs(0, 8), "Piano 1w");
+    assert_eq!(gs(14, 9), "Carillon");
+    assert_eq!(gs(80, 1), "Square");
+    assert_eq!(gs(125, 9), "Burst Noise");
+    // the capital tone, and variations the map doesn't have
+    assert_eq!(gs(25, 0), "Acoustic Guitar (steel)");
+    assert_eq!(gs(25, 24), "Acoustic Guitar (steel)");
+    // the bank LSB selects a map, not a variation
+    assert_eq!(
+        Program::new(25)
+            .unwrap()
+            .name_in_bank(SoundSet::Gs, (16, 2), Channel::One),
+        "Mandolin"
+    );
+}
@@ -123,6 +123,12 @@ pub use pitch_bend::*;
 mod program;
 pub use program::*;
 
+mod general_midi;
+pub use general_midi::*;
+
+mod patch;
+pub use patch::*;
+
 mod velocity;
 pub use velocity::*;
 
@@ -141,8 +147,12 @@ pub mod message;
 mod song_position_pointer;
 pub use song_position_pointer::*;
 
-mod target;
-pub use target::*;
+pub mod target;
+pub use target::MidiTarget;
+
+pub mod transform;
+
+pub mod theory;
 
 mod micros;
 pub use micros::*;
@@ -152,15 +162,18 @@ pub mod prelude {
         Common re-exports when working with `midix`
     "#]
     pub use crate::{
-        ChunkError, Controller, CowExt, DataByte, Dynamic, FileError, HeaderError, Key, Note,
-        Octave, ParseError, PitchBend, Program, SmpteError, SongPositionPointer, StatusByte,
-        TrackError, Velocity,
+        ChunkError, Controller, CowExt, DataByte, Dynamic, DynamicTable, EditError, FileError,
+        HeaderError, InstrumentFamily, Key, MidiTarget, Note, Octave, ParseError, Patch,
+        PatchDecoder, PitchBend, Program, SmpteError, SongPositionPointer, SoundSet, StatusByte,
+        SymbolError, TrackError, Velocity,
         channel::*,
         events::*,
         file::*,
         message::{MidiMessage, channel::*, system::*, time::*},
         micros::*,
         note,
+        theory::*,
+        transform::*,
     };
 
     pub use crate::reader::{MidiSource, ReadResult, Reader, ReaderError, ReaderErrorKind};
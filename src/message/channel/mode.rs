@@ -43,7 +43,7 @@ c = 127, v = 0: Poly Mode On (Mono Off) (Note:
 These four messages also cause All Notes Off)
 "#]
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChannelModeMessage {
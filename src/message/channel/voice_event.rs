@@ -115,6 +115,38 @@ impl VoiceEvent {
         }
     }
 
+    /// Returns a mutable reference to the note if a note is present in the variant
+    pub fn note_mut(&mut self) -> Option<&mut Note> {
+        match self {
+            VoiceEvent::NoteOn { note, .. }
+            | VoiceEvent::NoteOff { note, .. }
+            | VoiceEvent::Aftertouch { note, .. } => Some(note),
+            _ => None,
+        }
+    }
+
+    /// Returns the velocity if a velocity is present in the variant
+    pub fn velocity(&self) -> Option<Velocity> {
+        match self {
+            VoiceEvent::NoteOn { velocity, .. }
+            | VoiceEvent::NoteOff { velocity, .. }
+            | VoiceEvent::Aftertouch { velocity, .. }
+            | VoiceEvent::ChannelPressureAfterTouch { velocity } => Some(*velocity),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the velocity if a velocity is present in the variant
+    pub fn velocity_mut(&mut self) -> Option<&mut Velocity> {
+        match self {
+            VoiceEvent::NoteOn { velocity, .. }
+            | VoiceEvent::NoteOff { velocity, .. }
+            | VoiceEvent::Aftertouch { velocity, .. }
+            | VoiceEvent::ChannelPressureAfterTouch { velocity } => Some(velocity),
+            _ => None,
+        }
+    }
+
     /// Returns Some(note) if the note is off. This includes note on where the velocity is zero.
     pub fn is_note_off(&self) -> Option<Note> {
         use VoiceEvent::*;
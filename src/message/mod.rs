@@ -54,7 +54,7 @@ pub use time::*;
 #[doc = r#"
 An enumeration of all possible midi messages
 "#]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MidiMessage<'a> {
@@ -74,6 +74,19 @@ pub enum MidiMessage<'a> {
     ChannelMode(ChannelModeMessage),
 }
 
+impl MidiMessage<'_> {
+    /// Clone any borrowed data so that the message no longer borrows from its source
+    pub fn into_owned(self) -> MidiMessage<'static> {
+        match self {
+            MidiMessage::SysCommon(c) => MidiMessage::SysCommon(c.into_owned()),
+            MidiMessage::SysRealTime(r) => MidiMessage::SysRealTime(r),
+            MidiMessage::SysExclusive(e) => MidiMessage::SysExclusive(e.into_owned()),
+            MidiMessage::ChannelVoice(v) => MidiMessage::ChannelVoice(v),
+            MidiMessage::ChannelMode(m) => MidiMessage::ChannelMode(m),
+        }
+    }
+}
+
 impl<'a> From<SystemMessage<'a>> for MidiMessage<'a> {
     fn from(value: SystemMessage<'a>) -> Self {
         match value {
@@ -27,6 +27,18 @@ pub enum SystemCommonMessage<'a> {
     TuneRequest,
 }
 impl SystemCommonMessage<'_> {
+    /// Clone any borrowed data so that the message no longer borrows from its source
+    pub fn into_owned(self) -> SystemCommonMessage<'static> {
+        use SystemCommonMessage::*;
+        match self {
+            SystemExclusive(e) => SystemExclusive(e.into_owned()),
+            Undefined(s) => Undefined(s),
+            SongPositionPointer(spp) => SongPositionPointer(spp),
+            SongSelect(s) => SongSelect(s),
+            TuneRequest => TuneRequest,
+        }
+    }
+
     #[allow(dead_code)]
     const fn status(&self) -> u8 {
         use SystemCommonMessage::*;
@@ -56,6 +56,11 @@ impl<'a> SystemExclusiveMessage<'a> {
     //     self.0.to_mut()
     // }
 
+    /// Clone the data if borrowed
+    pub fn into_owned(self) -> SystemExclusiveMessage<'static> {
+        SystemExclusiveMessage(Cow::Owned(self.0.into_owned()))
+    }
+
     /// Get the length of the sysex data
     pub fn len(&self) -> usize {
         self.0.len()
@@ -27,10 +27,24 @@ impl<T> Ticked<T> {
         self.accumulated_ticks
     }
 
+    pub(crate) fn set_accumulated_ticks(&mut self, accumulated_ticks: u32) {
+        self.accumulated_ticks = accumulated_ticks;
+    }
+
     /// Returns the timed event
     pub const fn event(&self) -> &T {
         &self.event
     }
+
+    /// Returns a mutable reference to the timed event
+    pub fn event_mut(&mut self) -> &mut T {
+        &mut self.event
+    }
+
+    /// Returns the owned event
+    pub fn into_event(self) -> T {
+        self.event
+    }
 }
 
 /// A wrapper around some type with an associated timestamp in micros.
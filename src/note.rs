@@ -1,7 +1,7 @@
 use core::fmt;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 
-use crate::{DataByte, ParseError};
+use crate::{DataByte, ParseError, theory::OctaveNumbering};
 
 #[doc = r#"
 Identifies a key for some message.
@@ -105,6 +105,18 @@ impl Note {
     pub fn byte(&self) -> u8 {
         self.0.0
     }
+
+    /// Move the note up (or down, if negative) by a number of semitones.
+    ///
+    /// Returns `None` if the result is outside of the MIDI range (0-127),
+    /// unlike `+` and `-`, which saturate.
+    pub const fn checked_transpose(&self, semitones: i8) -> Option<Note> {
+        let next = self.0.0 as i16 + semitones as i16;
+        if next < 0 || next > 127 {
+            return None;
+        }
+        Some(Self(DataByte(next as u8)))
+    }
 }
 /// Create a note the easy way.
 ///
@@ -125,9 +137,10 @@ macro_rules! note {
     };
 }
 
+/// Writes the note as it is read, with sharps and middle C as C4, such as `C#4`
 impl fmt::Display for Note {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.key(), self.octave())
+        write!(f, "{}", OctaveNumbering::MiddleC4.name(*self))
     }
 }
 
@@ -204,6 +217,21 @@ fn subassign_note() {
     assert_eq!(key, Note::new(Key::C, Octave::new(-1)));
 }
 
+#[test]
+fn checked_transpose() {
+    let note = Note::new(Key::C, Octave::new(4));
+    assert_eq!(
+        note.checked_transpose(7),
+        Some(Note::new(Key::G, Octave::new(4)))
+    );
+    assert_eq!(
+        note.checked_transpose(-12),
+        Some(Note::new(Key::C, Octave::new(3)))
+    );
+    assert_eq!(note.checked_transpose(68), None);
+    assert_eq!(note.checked_transpose(-61), None);
+}
+
 #[test]
 fn test_note() {
     let c = Note::from_databyte(12).unwrap();
@@ -347,6 +375,16 @@ impl Key {
             _ => unreachable!(),
         }
     }
+    /// Returns the pitch class of the key, from 0 for [`Key::C`] to 11 for [`Key::B`]
+    pub const fn pitch_class(&self) -> u8 {
+        self.get_mod_12()
+    }
+
+    /// Returns the key of a pitch class, wrapping values of 12 and above
+    pub const fn from_pitch_class(pitch_class: u8) -> Self {
+        Self::from_data_byte(&DataByte(pitch_class % 12))
+    }
+
     const fn get_mod_12(&self) -> u8 {
         use Key::*;
         match self {
@@ -370,21 +408,22 @@ impl Key {
         Note::new(self, octave)
     }
 }
+/// Writes the key as it is read, with sharps, such as `C#`
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Key::*;
         match self {
             C => write!(f, "C"),
-            CSharp => write!(f, "C#/Db"),
+            CSharp => write!(f, "C#"),
             D => write!(f, "D"),
-            DSharp => write!(f, "D#/Eb"),
+            DSharp => write!(f, "D#"),
             E => write!(f, "E"),
             F => write!(f, "F"),
-            FSharp => write!(f, "F#/Gb"),
+            FSharp => write!(f, "F#"),
             G => write!(f, "G"),
-            GSharp => write!(f, "G#Ab"),
+            GSharp => write!(f, "G#"),
             A => write!(f, "A"),
-            ASharp => write!(f, "A#/Bb"),
+            ASharp => write!(f, "A#"),
             B => write!(f, "B"),
         }
     }
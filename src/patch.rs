@@ -0,0 +1,291 @@
+use crate::prelude::*;
+
+#[doc = r#"
+A sound, chosen by a bank select and a program change.
+
+General MIDI only has the 128 programs, but GS, XG and General MIDI 2 modules pick
+from banks of them with controllers 0 and 32, which take effect at the next program change.
+
+# Example
+```rust
+# use midix::prelude::*;
+// the XG jazz kit
+let patch = Patch::new(127, 0, Program::new(32).unwrap()).unwrap();
+assert_eq!(patch.name(SoundSet::Xg, Channel::One), "Jazz Kit");
+
+let mut track = Track::new(Vec::new());
+patch.write(&mut track, Channel::One, 0);
+
+let mut decoder = PatchDecoder::new();
+let patches: Vec<_> = track
+    .events()
+    .iter()
+    .filter_map(|event| decoder.read(event.event().channel_voice()?))
+    .collect();
+assert_eq!(patches, [(Channel::One, patch)]);
+```
+"#]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Patch {
+    bank_msb: DataByte,
+    bank_lsb: DataByte,
+    program: Program,
+}
+
+impl Patch {
+    /// Creates a patch from the two bytes of a bank select and a program.
+    ///
+    /// Checks for byte correctness (leading 0 bit)
+    pub fn new<B>(bank_msb: B, bank_lsb: B, program: Program) -> Result<Self, ParseError>
+    where
+        B: TryInto<DataByte, Error = ParseError>,
+    {
+        Ok(Self {
+            bank_msb: bank_msb.try_into()?,
+            bank_lsb: bank_lsb.try_into()?,
+            program,
+        })
+    }
+
+    /// Creates a patch from a program in bank 0
+    pub const fn from_program(program: Program) -> Self {
+        Self {
+            bank_msb: DataByte::ZERO,
+            bank_lsb: DataByte::ZERO,
+            program,
+        }
+    }
+
+    /// Creates a patch from the two bytes of a bank select and a program.
+    ///
+    /// Does not check that the bytes are valid!
+    pub const fn new_unchecked(bank_msb: u8, bank_lsb: u8, program: u8) -> Self {
+        Self {
+            bank_msb: DataByte::new_unchecked(bank_msb),
+            bank_lsb: DataByte::new_unchecked(bank_lsb),
+            program: Program::new_unchecked(program),
+        }
+    }
+
+    /// Returns the bank select sent with controller 0
+    pub const fn bank_msb(&self) -> u8 {
+        self.bank_msb.value()
+    }
+
+    /// Returns the bank select sent with controller 32
+    pub const fn bank_lsb(&self) -> u8 {
+        self.bank_lsb.value()
+    }
+
+    /// Returns the bank as 14 bits, the most significant byte first
+    pub const fn bank(&self) -> u16 {
+        ((self.bank_msb() as u16) << 7) | self.bank_lsb() as u16
+    }
+
+    /// Returns the program
+    pub const fn program(&self) -> Program {
+        self.program
+    }
+
+    /// Returns the events that select the patch, in the order they must be sent:
+    /// controller 0, controller 32, then the program change
+    pub const fn events(&self) -> [VoiceEvent; 3] {
+        [
+            VoiceEvent::control_change(Controller::BankSelection(self.bank_msb)),
+            VoiceEvent::control_change(Controller::BankSelectionFine(self.bank_lsb)),
+            VoiceEvent::program_change(self.program),
+        ]
+    }
+
+    /// Returns the messages that select the patch on a channel
+    pub const fn messages(&self, channel: Channel) -> [ChannelVoiceMessage; 3] {
+        let [msb, lsb, program] = self.events();
+        [
+            channel.send_event(msb),
+            channel.send_event(lsb),
+            channel.send_event(program),
+        ]
+    }
+
+    /// Select the patch on a channel in a track, at a tick
+    pub fn write(&self, track: &mut Track<'_>, channel: Channel, tick: u32) {
+        for message in self.messages(channel) {
+            track.insert(tick, message.into());
+        }
+    }
+
+    /// Returns the name of the patch on a channel.
+    ///
+    /// See [`Program::name_in_bank`].
+    pub fn name(&self, set: SoundSet, channel: Channel) -> &'static str {
+        self.program
+            .name_in_bank(set, (self.bank_msb(), self.bank_lsb()), channel)
+    }
+}
+
+impl From<Program> for Patch {
+    fn from(program: Program) -> Self {
+        Self::from_program(program)
+    }
+}
+
+/// Follows the bank selects of every channel, to report the patch each program change picks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PatchDecoder {
+    banks: [(DataByte, DataByte); 16],
+    patches: [Option<Patch>; 16],
+}
+
+impl Default for PatchDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatchDecoder {
+    /// Starts every channel in bank 0, with no patch
+    pub const fn new() -> Self {
+        Self {
+            banks: [(DataByte::ZERO, DataByte::ZERO); 16],
+            patches: [None; 16],
+        }
+    }
+
+    /// Read a message, returning its channel and new patch if it is a program change
+    pub fn read(&mut self, message: &ChannelVoiceMessage) -> Option<(Channel, Patch)> {
+        let channel = message.channel();
+        let index = channel.to_byte() as usize;
+        match message.event() {
+            // by number, as a bank select may also be built as an unlisted controller
+            VoiceEvent::ControlChange(controller) => match controller.to_bytes() {
+                [0x00, msb] => self.banks[index].0 = DataByte::new_unchecked(msb),
+                [0x20, lsb] => self.banks[index].1 = DataByte::new_unchecked(lsb),
+                _ => {}
+            },
+            VoiceEvent::ProgramChange { program } => {
+                let (bank_msb, bank_lsb) = self.banks[index];
+                let patch = Patch {
+                    bank_msb,
+                    bank_lsb,
+                    program: *program,
+                };
+                self.patches[index] = Some(patch);
+                return Some((channel, patch));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Returns the patch of a channel, if it has had a program change
+    pub const fn patch(&self, channel: Channel) -> Option<Patch> {
+        self.patches[channel.to_byte() as usize]
+    }
+
+    /// Returns the bank select a channel's next program change will use,
+    /// as the bytes of controllers 0 and 32
+    pub const fn pending_bank(&self, channel: Channel) -> (u8, u8) {
+        let (msb, lsb) = self.banks[channel.to_byte() as usize];
+        (msb.value(), lsb.value())
+    }
+}
+
+#[test]
+fn patch_messages() {
+    let patch = Patch::new(121, 1, Program::new(4).unwrap()).unwrap();
+    assert_eq!(patch.bank(), 121 << 7 | 1);
+    let bytes: alloc::vec::Vec<_> = patch
+        .messages(Channel::Three)
+        .iter()
+        .map(|message| {
+            (
+                message.status(),
+                message.data_1_byte(),
+                message.data_2_byte(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        bytes,
+        [(0xB2, 0, Some(121)), (0xB2, 32, Some(1)), (0xC2, 4, None)]
+    );
+    assert!(Patch::new(128, 0, Program::new(0).unwrap()).is_err());
+    assert_eq!(
+        Patch::from(Program::new(7).unwrap()),
+        Patch::new_unchecked(0, 0, 7)
+    );
+}
+
+#[test]
+fn patch_names() {
+    let patch = Patch::new_unchecked(0, 0, 25);
+    assert_eq!(
+        patch.name(SoundSet::Gs, Channel::One),
+        "Acoustic Guitar (steel)"
+    );
+    assert_eq!(patch.name(SoundSet::Gs, Channel::Ten), "TR-808");
+    assert_eq!(
+        Patch::new_unchecked(120, 0, 40).name(SoundSet::GeneralMidi2, Channel::Two),
+        "Brush Set"
+    );
+    assert_eq!(
+        Patch::new_unchecked(121, 1, 24).name(SoundSet::GeneralMidi2, Channel::Two),
+        "Ukulele"
+    );
+}
+
+#[test]
+fn decode_patches() {
+    let mut decoder = PatchDecoder::new();
+    let mut read = |channel: Channel, event: VoiceEvent| decoder.read(&channel.send_event(event));
+
+    let program = |byte: u8| VoiceEvent::program_change(Program::new(byte).unwrap());
+    let msb = |byte: u8| {
+        VoiceEvent::control_change(Controller::BankSelection(DataByte::new_unchecked(byte)))
+    };
+    let lsb = |byte: u8| {
+        VoiceEvent::control_change(Controller::BankSelectionFine(DataByte::new_unchecked(byte)))
+    };
+
+    assert_eq!(
+        read(Channel::One, program(0)),
+        Some((Channel::One, Patch::new_unchecked(0, 0, 0)))
+    );
+    assert_eq!(read(Channel::One, msb(8)), None);
+    assert_eq!(read(Channel::Two, lsb(3)), None);
+    // bank selects wait for a program change on their own channel
+    assert_eq!(
+        read(Channel::One, program(16)),
+        Some((Channel::One, Patch::new_unchecked(8, 0, 16)))
+    );
+    assert_eq!(
+        read(Channel::Two, program(16)),
+        Some((Channel::Two, Patch::new_unchecked(0, 3, 16)))
+    );
+    // and last until the next bank select
+    assert_eq!(
+        read(Channel::One, program(17)),
+        Some((Channel::One, Patch::new_unchecked(8, 0, 17)))
+    );
+
+    assert_eq!(
+        decoder.patch(Channel::One),
+        Some(Patch::new_unchecked(8, 0, 17))
+    );
+    assert_eq!(decoder.patch(Channel::Three), None);
+    assert_eq!(decoder.pending_bank(Channel::Two), (0, 3));
+
+    let unlisted = Controller::Other {
+        byte_1: DataByte::new_unchecked(0x20),
+        byte_2: DataByte::new_unchecked(5),
+    };
+    let unlisted = Channel::Two.send_event(VoiceEvent::control_change(unlisted));
+    assert_eq!(decoder.read(&unlisted), None);
+    assert_eq!(decoder.pending_bank(Channel::Two), (0, 5));
+    assert_eq!(
+        Controller::other(DataByte::new_unchecked(0), DataByte::new_unchecked(8)),
+        Controller::BankSelection(DataByte::new_unchecked(8))
+    );
+}
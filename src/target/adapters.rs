@@ -0,0 +1,498 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::prelude::*;
+
+/// A [`MidiTarget`] that only handles messages accepted by a predicate.
+///
+/// Created by [`MidiTarget::filter`].
+#[derive(Debug, Clone)]
+pub struct Filter<T, P> {
+    inner: T,
+    predicate: P,
+}
+
+impl<T, P> Filter<T, P> {
+    pub(crate) fn new(inner: T, predicate: P) -> Self {
+        Self { inner, predicate }
+    }
+
+    /// Returns a reference to the wrapped target
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped target
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped target
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, P> MidiTarget for Filter<T, P>
+where
+    T: MidiTarget,
+    P: FnMut(&MidiMessage<'_>) -> bool,
+{
+    type Error = T::Error;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        if (self.predicate)(&event) {
+            self.inner.handle_event(event)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A [`MidiTarget`] that transforms messages before handling them.
+///
+/// Created by [`MidiTarget::map`].
+#[derive(Debug, Clone)]
+pub struct Map<T, F> {
+    inner: T,
+    f: F,
+}
+
+impl<T, F> Map<T, F> {
+    pub(crate) fn new(inner: T, f: F) -> Self {
+        Self { inner, f }
+    }
+
+    /// Returns a reference to the wrapped target
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped target
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped target
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, F> MidiTarget for Map<T, F>
+where
+    T: MidiTarget,
+    F: FnMut(MidiMessage<'_>) -> MidiMessage<'_>,
+{
+    type Error = T::Error;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        let event = (self.f)(event);
+        self.inner.handle_event(event)
+    }
+}
+
+/// A [`MidiTarget`] that moves channel voice messages to another channel.
+///
+/// A [`Route`] through a [`ChannelMap`] that sends each channel to exactly one channel.
+///
+/// Created by [`MidiTarget::remap_channels`].
+#[derive(Debug, Clone)]
+pub struct RemapChannels<T> {
+    route: Route<T>,
+    table: [Channel; 16],
+}
+
+impl<T> RemapChannels<T> {
+    pub(crate) fn new<F>(inner: T, f: F) -> Self
+    where
+        F: FnMut(Channel) -> Channel,
+    {
+        Self::from_table(inner, Channel::all().map(f))
+    }
+
+    /// Create a remapping target from a table.
+    ///
+    /// Messages on channel `n` are sent to `map[n]`.
+    pub fn from_table(inner: T, map: [Channel; 16]) -> Self {
+        Self {
+            route: Route::new(inner, ChannelMap::from_table(map)),
+            table: map,
+        }
+    }
+
+    /// Returns the table used to remap channels
+    pub fn table(&self) -> &[Channel; 16] {
+        &self.table
+    }
+
+    /// Returns a reference to the wrapped target
+    pub fn inner(&self) -> &T {
+        self.route.inner()
+    }
+
+    /// Returns a mutable reference to the wrapped target
+    pub fn inner_mut(&mut self) -> &mut T {
+        self.route.inner_mut()
+    }
+
+    /// Returns the wrapped target
+    pub fn into_inner(self) -> T {
+        self.route.into_inner()
+    }
+}
+
+impl<T: MidiTarget> MidiTarget for RemapChannels<T> {
+    type Error = T::Error;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        self.route.handle_event(event)
+    }
+}
+
+/// A [`MidiTarget`] that sends channel voice messages to every channel of a [`ChannelMap`].
+///
+/// Created by [`MidiTarget::route`].
+#[derive(Debug, Clone)]
+pub struct Route<T> {
+    inner: T,
+    map: ChannelMap,
+}
+
+impl<T> Route<T> {
+    pub(crate) fn new(inner: T, map: ChannelMap) -> Self {
+        Self { inner, map }
+    }
+
+    /// Returns the map used to route channels
+    pub fn map(&self) -> &ChannelMap {
+        &self.map
+    }
+
+    /// Returns a mutable reference to the map used to route channels
+    pub fn map_mut(&mut self) -> &mut ChannelMap {
+        &mut self.map
+    }
+
+    /// Returns a reference to the wrapped target
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped target
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped target
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: MidiTarget> MidiTarget for Route<T> {
+    type Error = T::Error;
+    /// Every copy of the message is handled.
+    ///
+    /// If any fail, the first error is returned.
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        let MidiMessage::ChannelVoice(msg) = event else {
+            return self.inner.handle_event(event);
+        };
+        let mut result = Ok(());
+        for routed in self.map.route(msg) {
+            let res = self.inner.handle_event(routed.into());
+            if result.is_ok() {
+                result = res;
+            }
+        }
+        result
+    }
+}
+
+/// A [`MidiTarget`] that changes the velocity of note ons before handling them.
+///
+/// Created by [`MidiTarget::map_velocity`].
+#[derive(Debug, Clone)]
+pub struct MapVelocity<T> {
+    inner: T,
+    transform: VelocityTransform,
+}
+
+impl<T> MapVelocity<T> {
+    pub(crate) fn new(inner: T, transform: VelocityTransform) -> Self {
+        Self { inner, transform }
+    }
+
+    /// Returns the transform applied to note ons
+    pub fn transform(&self) -> &VelocityTransform {
+        &self.transform
+    }
+
+    /// Returns a mutable reference to the transform applied to note ons
+    pub fn transform_mut(&mut self) -> &mut VelocityTransform {
+        &mut self.transform
+    }
+
+    /// Returns a reference to the wrapped target
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped target
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped target
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: MidiTarget> MidiTarget for MapVelocity<T> {
+    type Error = T::Error;
+    fn handle_event(&mut self, mut event: MidiMessage) -> Result<(), Self::Error> {
+        if let MidiMessage::ChannelVoice(msg) = &mut event {
+            self.transform.apply_to_message(msg);
+        }
+        self.inner.handle_event(event)
+    }
+}
+
+/// A [`MidiTarget`] that passes every message to two targets.
+///
+/// Created by [`MidiTarget::tee`].
+#[derive(Debug, Clone)]
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Tee<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns references to both targets
+    pub fn inner(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Returns both targets
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> MidiTarget for Tee<A, B>
+where
+    A: MidiTarget,
+    B: MidiTarget<Error = A::Error>,
+{
+    type Error = A::Error;
+    /// Both targets always receive the message.
+    ///
+    /// If both fail, the first target's error is returned.
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        let first = self.first.handle_event(event.clone());
+        let second = self.second.handle_event(event);
+        first.and(second)
+    }
+}
+
+#[doc = r#"
+A [`MidiTarget`] that passes every message to any number of targets.
+
+Targets may be of different types, as long as they share an error type.
+
+# Example
+```rust
+# use midix::prelude::*;
+# use core::convert::Infallible;
+use midix::target::FanOut;
+
+let mut count = 0;
+let mut fan_out = FanOut::<Infallible>::new()
+    .with(|_: MidiMessage<'_>| {
+        count += 1;
+        Ok(())
+    })
+    .with(Vec::new());
+
+fan_out.handle_event(SystemRealTimeMessage::Start.into()).unwrap();
+assert_eq!(fan_out.len(), 2);
+drop(fan_out);
+assert_eq!(count, 1);
+```
+"#]
+pub struct FanOut<'t, E> {
+    targets: Vec<Box<dyn MidiTarget<Error = E> + 't>>,
+}
+
+impl<E> Default for FanOut<'_, E> {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl<'t, E> FanOut<'t, E> {
+    /// Create a fan out without any targets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a target
+    pub fn push<T>(&mut self, target: T)
+    where
+        T: MidiTarget<Error = E> + 't,
+    {
+        self.targets.push(Box::new(target));
+    }
+
+    /// Add a target, returning self
+    pub fn with<T>(mut self, target: T) -> Self
+    where
+        T: MidiTarget<Error = E> + 't,
+    {
+        self.push(target);
+        self
+    }
+
+    /// The number of targets
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns true if there are no targets
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+impl<E> MidiTarget for FanOut<'_, E> {
+    type Error = E;
+    /// Every target always receives the message.
+    ///
+    /// If any fail, the first error is returned.
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        let mut result = Ok(());
+        for target in self.targets.iter_mut() {
+            let res = target.handle_event(event.clone());
+            if result.is_ok() {
+                result = res;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+fn note_on(channel: Channel, note: Note) -> MidiMessage<'static> {
+    channel
+        .send_event(VoiceEvent::note_on(note, Velocity::MAX))
+        .into()
+}
+
+#[test]
+fn filter_and_map() {
+    let mut target = Vec::new()
+        .filter(|msg: &MidiMessage<'_>| matches!(msg, MidiMessage::ChannelVoice(_)))
+        .map(|msg| match msg {
+            MidiMessage::ChannelVoice(mut voice) => {
+                if let VoiceEvent::NoteOn { note, .. } = &mut voice.event {
+                    *note += 12;
+                }
+                MidiMessage::ChannelVoice(voice)
+            }
+            other => other,
+        });
+
+    target
+        .handle_event(note_on(Channel::One, note!(C, 4)))
+        .unwrap();
+    target
+        .handle_event(SystemRealTimeMessage::Stop.into())
+        .unwrap();
+
+    let recorded = target.into_inner().into_inner();
+    assert_eq!(recorded, [note_on(Channel::One, note!(C, 5))]);
+}
+
+#[test]
+fn remap_and_tee() {
+    let mut target = RemapChannels::from_table(Vec::new(), [Channel::Sixteen; 16]).tee(Vec::new());
+
+    target
+        .handle_event(note_on(Channel::Three, note!(A, 2)))
+        .unwrap();
+
+    let (remapped, untouched) = target.into_inner();
+    assert_eq!(
+        remapped.into_inner(),
+        [note_on(Channel::Sixteen, note!(A, 2))]
+    );
+    assert_eq!(untouched, [note_on(Channel::Three, note!(A, 2))]);
+}
+
+#[test]
+fn route_copies_messages() {
+    let map = ChannelMap::new()
+        .add(Channel::One, Channel::Nine)
+        .mute(Channel::Two);
+    let mut target = Vec::new().route(map);
+    target
+        .handle_event(note_on(Channel::One, note!(B, 3)))
+        .unwrap();
+    target
+        .handle_event(note_on(Channel::Two, note!(B, 3)))
+        .unwrap();
+    assert_eq!(
+        target.into_inner(),
+        [
+            note_on(Channel::One, note!(B, 3)),
+            note_on(Channel::Nine, note!(B, 3))
+        ]
+    );
+}
+
+#[test]
+fn map_velocity_changes_note_ons() {
+    let fixed = VelocityTransform::new().with_fixed(Velocity::new_unchecked(64));
+    let mut target = Vec::new().map_velocity(fixed);
+    target
+        .handle_event(note_on(Channel::One, note!(B, 3)))
+        .unwrap();
+    let off = VoiceEvent::note_off(note!(B, 3), Velocity::MAX);
+    target
+        .handle_event(Channel::One.send_event(off).into())
+        .unwrap();
+
+    let velocities: Vec<_> = target
+        .into_inner()
+        .iter()
+        .map(|msg| match msg {
+            MidiMessage::ChannelVoice(msg) => msg.velocity().unwrap().byte(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(velocities, [64, 127]);
+}
+
+#[test]
+fn fan_out_delivers_to_all_targets() {
+    let mut first = 0;
+    let mut second = 0;
+    let mut fan_out = FanOut::new()
+        .with(|_: MidiMessage<'_>| {
+            first += 1;
+            Err("first failed")
+        })
+        .with(|_: MidiMessage<'_>| {
+            second += 1;
+            Err("second failed")
+        });
+
+    let res = fan_out.handle_event(note_on(Channel::One, note!(C, 4)));
+    assert_eq!(res, Err("first failed"));
+    drop(fan_out);
+    assert_eq!((first, second), (1, 1));
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+/// A [`MidiTarget`] that logs every message with [`tracing::debug!`] before passing it on.
+///
+/// Created by [`MidiTarget::debug`].
+#[derive(Debug, Clone)]
+pub struct DebugTarget<T> {
+    inner: T,
+}
+
+impl<T> DebugTarget<T> {
+    /// Log every message passed to `inner`
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped target
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: MidiTarget> MidiTarget for DebugTarget<T> {
+    type Error = T::Error;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        tracing::debug!("Handling {:?}", event);
+        self.inner.handle_event(event)
+    }
+}
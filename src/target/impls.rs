@@ -0,0 +1,76 @@
+use core::convert::Infallible;
+
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+impl<F, E> MidiTarget for F
+where
+    F: FnMut(MidiMessage<'_>) -> Result<(), E>,
+{
+    type Error = E;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        self(event)
+    }
+}
+
+/// Records every message handled.
+impl MidiTarget for Vec<MidiMessage<'static>> {
+    type Error = Infallible;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        self.push(event.into_owned());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl MidiTarget for std::sync::mpsc::Sender<MidiMessage<'static>> {
+    type Error = std::sync::mpsc::SendError<MidiMessage<'static>>;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        self.send(event.into_owned())
+    }
+}
+
+#[cfg(feature = "std")]
+impl MidiTarget for std::sync::mpsc::SyncSender<MidiMessage<'static>> {
+    type Error = std::sync::mpsc::SendError<MidiMessage<'static>>;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        self.send(event.into_owned())
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl MidiTarget for crossbeam_channel::Sender<MidiMessage<'static>> {
+    type Error = crossbeam_channel::SendError<MidiMessage<'static>>;
+    fn handle_event(&mut self, event: MidiMessage) -> Result<(), Self::Error> {
+        self.send(event.into_owned())
+    }
+}
+
+#[test]
+fn closure_target() {
+    let mut notes = Vec::new();
+    let mut target = |msg: MidiMessage<'_>| {
+        if let MidiMessage::ChannelVoice(voice) = msg {
+            notes.extend(voice.note());
+        }
+        Ok::<_, Infallible>(())
+    };
+    let msg = Channel::One.send_event(VoiceEvent::note_off(note!(D, 3), Velocity::ZERO));
+    target.handle_event(msg.into()).unwrap();
+    assert_eq!(notes, [note!(D, 3)]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mpsc_target() {
+    let (mut sender, receiver) = std::sync::mpsc::channel();
+    let sysex = [0x43, 0x12, 0x00];
+    sender
+        .handle_event(SystemExclusiveMessage::new(sysex.as_slice()).into())
+        .unwrap();
+    assert_eq!(
+        receiver.recv().unwrap(),
+        MidiMessage::SysExclusive(SystemExclusiveMessage::new(sysex.to_vec()))
+    );
+}
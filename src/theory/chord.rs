@@ -0,0 +1,599 @@
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+use super::scale::fifths_of;
+
+#[cfg(test)]
+use alloc::string::ToString;
+
+/// The chord tones of a [`Chord`], above its root
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChordQuality {
+    /// A major triad, such as `C`
+    Major,
+    /// A minor triad, such as `Cm`
+    Minor,
+    /// A diminished triad, such as `Cdim`
+    Diminished,
+    /// An augmented triad, such as `Caug`
+    Augmented,
+    /// A second in place of the third, such as `Csus2`
+    Sus2,
+    /// A fourth in place of the third, such as `Csus4`
+    Sus4,
+    /// A root and fifth, such as `C5`
+    Power,
+    /// A major triad with a major sixth, such as `C6`
+    Major6,
+    /// A minor triad with a major sixth, such as `Cm6`
+    Minor6,
+    /// A major triad with a minor seventh, such as `C7`
+    Dominant7,
+    /// A major triad with a major seventh, such as `Cmaj7`
+    Major7,
+    /// A minor triad with a minor seventh, such as `Cm7`
+    Minor7,
+    /// A minor triad with a major seventh, such as `Cm(maj7)`
+    MinorMajor7,
+    /// A diminished triad with a minor seventh, such as `Cm7b5`
+    HalfDiminished7,
+    /// A diminished triad with a diminished seventh, such as `Cdim7`
+    Diminished7,
+    /// An augmented triad with a minor seventh, such as `Caug7`
+    Augmented7,
+    /// A suspended fourth with a minor seventh, such as `C7sus4`
+    Dominant7Sus4,
+}
+
+impl ChordQuality {
+    /// Every chord quality
+    pub const ALL: [ChordQuality; 17] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Diminished,
+        ChordQuality::Augmented,
+        ChordQuality::Sus2,
+        ChordQuality::Sus4,
+        ChordQuality::Power,
+        ChordQuality::Major6,
+        ChordQuality::Minor6,
+        ChordQuality::Dominant7,
+        ChordQuality::Major7,
+        ChordQuality::Minor7,
+        ChordQuality::MinorMajor7,
+        ChordQuality::HalfDiminished7,
+        ChordQuality::Diminished7,
+        ChordQuality::Augmented7,
+        ChordQuality::Dominant7Sus4,
+    ];
+
+    /// Returns the chord tones as intervals above the root, starting with the root
+    pub const fn intervals(&self) -> &'static [Interval] {
+        use ChordQuality::*;
+        const P1: Interval = Interval::PERFECT_UNISON;
+        const M2: Interval = Interval::MAJOR_SECOND;
+        const M3: Interval = Interval::MAJOR_THIRD;
+        const MIN3: Interval = Interval::MINOR_THIRD;
+        const P4: Interval = Interval::PERFECT_FOURTH;
+        const D5: Interval = Interval::DIMINISHED_FIFTH;
+        const P5: Interval = Interval::PERFECT_FIFTH;
+        const A5: Interval = Interval::AUGMENTED_FIFTH;
+        const M6: Interval = Interval::MAJOR_SIXTH;
+        const D7: Interval = Interval::DIMINISHED_SEVENTH;
+        const MIN7: Interval = Interval::MINOR_SEVENTH;
+        const M7: Interval = Interval::MAJOR_SEVENTH;
+        match self {
+            Major => &[P1, M3, P5],
+            Minor => &[P1, MIN3, P5],
+            Diminished => &[P1, MIN3, D5],
+            Augmented => &[P1, M3, A5],
+            Sus2 => &[P1, M2, P5],
+            Sus4 => &[P1, P4, P5],
+            Power => &[P1, P5],
+            Major6 => &[P1, M3, P5, M6],
+            Minor6 => &[P1, MIN3, P5, M6],
+            Dominant7 => &[P1, M3, P5, MIN7],
+            Major7 => &[P1, M3, P5, M7],
+            Minor7 => &[P1, MIN3, P5, MIN7],
+            MinorMajor7 => &[P1, MIN3, P5, M7],
+            HalfDiminished7 => &[P1, MIN3, D5, MIN7],
+            Diminished7 => &[P1, MIN3, D5, D7],
+            Augmented7 => &[P1, M3, A5, MIN7],
+            Dominant7Sus4 => &[P1, P4, P5, MIN7],
+        }
+    }
+
+    /// Returns true if the quality has a seventh
+    pub const fn has_seventh(&self) -> bool {
+        use ChordQuality::*;
+        matches!(
+            self,
+            Dominant7
+                | Major7
+                | Minor7
+                | MinorMajor7
+                | HalfDiminished7
+                | Diminished7
+                | Augmented7
+                | Dominant7Sus4
+        )
+    }
+
+    /// The symbol of the quality, split around the 7 that extensions replace
+    pub(super) const fn symbol(&self) -> (&'static str, &'static str) {
+        use ChordQuality::*;
+        match self {
+            Major => ("", ""),
+            Minor => ("m", ""),
+            Diminished => ("dim", ""),
+            Augmented => ("aug", ""),
+            Sus2 => ("sus2", ""),
+            Sus4 => ("sus4", ""),
+            Power => ("5", ""),
+            Major6 => ("6", ""),
+            Minor6 => ("m6", ""),
+            Dominant7 => ("", ""),
+            Major7 => ("maj", ""),
+            Minor7 => ("m", ""),
+            MinorMajor7 => ("m(maj", ")"),
+            HalfDiminished7 => ("m", "b5"),
+            Diminished7 => ("dim", ""),
+            Augmented7 => ("aug", ""),
+            Dominant7Sus4 => ("", "sus4"),
+        }
+    }
+
+    /// How much less likely the quality is than a plain triad or seventh chord
+    const fn rarity(&self) -> i32 {
+        use ChordQuality::*;
+        match self {
+            Major | Minor | Power | Dominant7 | Major7 | Minor7 => 0,
+            Major6 | Minor6 => 2,
+            Diminished | Sus2 | Sus4 | HalfDiminished7 | Diminished7 | Dominant7Sus4 => 3,
+            Augmented => 5,
+            MinorMajor7 | Augmented7 => 8,
+        }
+    }
+
+    /// One bit for each semitone above the root
+    const fn mask(&self) -> u16 {
+        let intervals = self.intervals();
+        let mut mask = 0;
+        let mut index = 0;
+        while index < intervals.len() {
+            mask |= 1 << (intervals[index].semitones() % 12);
+            index += 1;
+        }
+        mask
+    }
+}
+
+/// A tension added above the chord tones of a [`Chord`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Extension {
+    /// A minor ninth
+    FlatNine,
+    /// A major ninth
+    Nine,
+    /// An augmented ninth
+    SharpNine,
+    /// A perfect eleventh
+    Eleven,
+    /// An augmented eleventh
+    SharpEleven,
+    /// A minor thirteenth
+    FlatThirteen,
+    /// A major thirteenth
+    Thirteen,
+}
+
+impl Extension {
+    /// Every extension, from the lowest
+    pub const ALL: [Extension; 7] = [
+        Extension::FlatNine,
+        Extension::Nine,
+        Extension::SharpNine,
+        Extension::Eleven,
+        Extension::SharpEleven,
+        Extension::FlatThirteen,
+        Extension::Thirteen,
+    ];
+
+    /// Returns the interval of the extension above the root
+    pub const fn interval(&self) -> Interval {
+        match self {
+            Extension::FlatNine => Interval::MINOR_NINTH,
+            Extension::Nine => Interval::MAJOR_NINTH,
+            Extension::SharpNine => Interval::AUGMENTED_NINTH,
+            Extension::Eleven => Interval::PERFECT_ELEVENTH,
+            Extension::SharpEleven => Interval::AUGMENTED_ELEVENTH,
+            Extension::FlatThirteen => Interval::MINOR_THIRTEENTH,
+            Extension::Thirteen => Interval::MAJOR_THIRTEENTH,
+        }
+    }
+
+    /// Returns true for the ninth, eleventh and thirteenth, which are written without an accidental
+    pub const fn is_natural(&self) -> bool {
+        matches!(
+            self,
+            Extension::Nine | Extension::Eleven | Extension::Thirteen
+        )
+    }
+
+    /// The extension a semitone above the root names, given the chord tones
+    const fn from_semitone(semitone: u8, tones: u16) -> Option<Self> {
+        Some(match semitone {
+            1 => Extension::FlatNine,
+            2 => Extension::Nine,
+            // a minor third is a chord tone, not a sharp ninth
+            3 if tones & (1 << 4) != 0 => Extension::SharpNine,
+            5 => Extension::Eleven,
+            6 => Extension::SharpEleven,
+            8 => Extension::FlatThirteen,
+            9 => Extension::Thirteen,
+            _ => return None,
+        })
+    }
+
+    const fn bit(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Extension::FlatNine => "b9",
+            Extension::Nine => "9",
+            Extension::SharpNine => "#9",
+            Extension::Eleven => "11",
+            Extension::SharpEleven => "#11",
+            Extension::FlatThirteen => "b13",
+            Extension::Thirteen => "13",
+        })
+    }
+}
+
+#[doc = r#"
+A chord: a root, a [`ChordQuality`], any [`Extension`]s and an optional slash bass.
+
+Chords display as lead sheet symbols. A seventh chord with a ninth, eleventh or
+thirteenth is written with the highest of them, so `C9` is a `C7` with a ninth,
+`C11` adds a ninth and eleventh, and `C13` a ninth and thirteenth.
+Other extensions follow as alterations (`C7b9`), or as additions on chords without
+a seventh (`Cadd9`, `C(#11)`).
+
+# Example
+```rust
+# use midix::prelude::*;
+// C E G Bb D, with E in the bass
+let notes = [note!(E, 3), note!(C, 4), note!(G, 4), note!(ASharp, 4), note!(D, 5)];
+let chords = Chord::recognize(notes);
+
+let best = chords[0];
+assert_eq!(best.root().key(), Key::C);
+assert_eq!(best.quality(), ChordQuality::Dominant7);
+assert!(best.has_extension(Extension::Nine));
+assert_eq!(best.to_string(), "C9/E");
+
+// less likely readings follow
+assert!(chords.len() > 1);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    root: PitchName,
+    quality: ChordQuality,
+    /// One bit for each [`Extension`]
+    extensions: u8,
+    bass: Option<PitchName>,
+}
+
+impl Chord {
+    /// Create a chord in root position, without extensions
+    pub const fn new(root: PitchName, quality: ChordQuality) -> Self {
+        Self {
+            root,
+            quality,
+            extensions: 0,
+            bass: None,
+        }
+    }
+
+    /// Add an extension to the chord
+    pub const fn with_extension(mut self, extension: Extension) -> Self {
+        self.extensions |= extension.bit();
+        self
+    }
+
+    /// Put a note other than the root in the bass. A bass on the root is root position.
+    pub const fn with_bass(mut self, bass: PitchName) -> Self {
+        self.bass = if bass.pitch_class() == self.root.pitch_class() {
+            None
+        } else {
+            Some(bass)
+        };
+        self
+    }
+
+    /// Returns the root of the chord
+    pub const fn root(&self) -> PitchName {
+        self.root
+    }
+
+    /// Returns the quality of the chord
+    pub const fn quality(&self) -> ChordQuality {
+        self.quality
+    }
+
+    /// Returns true if the chord has the extension
+    pub const fn has_extension(&self, extension: Extension) -> bool {
+        self.extensions & extension.bit() != 0
+    }
+
+    /// Returns the extensions of the chord, from the lowest
+    pub fn extensions(&self) -> impl Iterator<Item = Extension> + use<> {
+        let chord = *self;
+        Extension::ALL
+            .into_iter()
+            .filter(move |extension| chord.has_extension(*extension))
+    }
+
+    /// Returns the slash bass, if the chord isn't in root position
+    pub const fn bass(&self) -> Option<PitchName> {
+        self.bass
+    }
+
+    /// Returns the chord tones and then the extensions, as intervals above the root
+    pub fn intervals(&self) -> Vec<Interval> {
+        let mut intervals = self.quality.intervals().to_vec();
+        intervals.extend(self.extensions().map(|extension| extension.interval()));
+        intervals
+    }
+
+    /// Returns the names of the chord's notes, from the root up,
+    /// spelled by their intervals above the root
+    pub fn names(&self) -> Vec<PitchName> {
+        let fifths = fifths_of(self.root.key(), 0);
+        self.intervals()
+            .into_iter()
+            .map(|interval| {
+                self.root.transpose(interval).unwrap_or_else(|| {
+                    let pitch_class = self.root.pitch_class() + interval.semitones() as u8;
+                    PitchName::in_signature(pitch_class, fifths)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the keys of the chord's notes, from the root up
+    pub fn keys(&self) -> Vec<Key> {
+        self.names().into_iter().map(|name| name.key()).collect()
+    }
+
+    /// Spell the root and bass as a key signature would
+    pub const fn respell(mut self, key_signature: KeySignature) -> Self {
+        self.root = key_signature.spell(self.root.key());
+        if let Some(bass) = self.bass {
+            self.bass = Some(key_signature.spell(bass.key()));
+        }
+        self
+    }
+
+    #[doc = r#"
+    Name the chord formed by a set of notes, most likely first.
+
+    Every note present is tried as the root of every quality. A reading must contain
+    all of its chord tones, except that a perfect fifth may be left out, and every
+    other note must be an extension. Readings rank lower for less common qualities,
+    for each extension (most of all altered ones), without a fifth, and with a note
+    other than the root in the bass (most of all when the bass is an extension).
+
+    Roots are spelled as in their major key, such as `Bb` and `F#`.
+    Returns no chords for fewer than two different pitch classes.
+    "#]
+    pub fn recognize<I>(notes: I) -> Vec<Chord>
+    where
+        I: IntoIterator<Item = Note>,
+    {
+        let mut present = 0u16;
+        let mut lowest: Option<Note> = None;
+        for note in notes {
+            present |= 1 << note.key().pitch_class();
+            lowest = Some(lowest.map_or(note, |lowest| lowest.min(note)));
+        }
+        let Some(lowest) = lowest else {
+            return Vec::new();
+        };
+        if present.count_ones() < 2 {
+            return Vec::new();
+        }
+        let bass = lowest.key();
+
+        let mut candidates: Vec<(i32, Chord)> = Vec::new();
+        for root in (0..12).filter(|root| present & (1 << root) != 0) {
+            // the notes present, as semitones above this root
+            let relative = (present >> root | present << (12 - root)) & 0xFFF;
+            let root = Key::from_pitch_class(root);
+            let name = PitchName::in_signature(root.pitch_class(), fifths_of(root, 0));
+
+            'quality: for quality in ChordQuality::ALL {
+                let tones = quality.mask();
+                let mut score = 100 - quality.rarity();
+                match tones & !relative {
+                    0 => {}
+                    // the fifth is often left out of fuller chords
+                    missing if missing == 1 << 7 && tones.count_ones() >= 3 => score -= 5,
+                    _ => continue,
+                }
+                let mut chord = Chord::new(name, quality);
+                for semitone in (1..12).filter(|semitone| relative & !tones & (1 << semitone) != 0)
+                {
+                    let Some(extension) = Extension::from_semitone(semitone, tones) else {
+                        continue 'quality;
+                    };
+                    chord = chord.with_extension(extension);
+                    score -= if extension.is_natural() { 10 } else { 20 };
+                }
+                if bass != root {
+                    let above_root = (bass.pitch_class() + 12 - root.pitch_class()) % 12;
+                    // a slash bass is usually a chord tone
+                    score -= if tones & (1 << above_root) != 0 {
+                        5
+                    } else {
+                        20
+                    };
+                    let bass = PitchName::in_signature(bass.pitch_class(), fifths_of(root, 0));
+                    chord = chord.with_bass(bass);
+                }
+                candidates.push((score, chord));
+            }
+        }
+        candidates.sort_by_key(|(score, _)| -score);
+        candidates.into_iter().map(|(_, chord)| chord).collect()
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (symbol, suffix) = self.quality.symbol();
+        write!(f, "{}{}", self.root, symbol)?;
+        let mut rest = self.extensions;
+        if self.quality.has_seventh() {
+            let nine = Extension::Nine.bit();
+            let eleven = Extension::Eleven.bit();
+            let thirteen = Extension::Thirteen.bit();
+            let highest = if rest & (nine | thirteen) == nine | thirteen {
+                rest &= !(nine | thirteen);
+                13
+            } else if rest & (nine | eleven) == nine | eleven {
+                rest &= !(nine | eleven);
+                11
+            } else if rest & nine != 0 {
+                rest &= !nine;
+                9
+            } else {
+                7
+            };
+            write!(f, "{highest}{suffix}")?;
+            for extension in Extension::ALL {
+                if rest & extension.bit() == 0 {
+                    continue;
+                }
+                if extension.is_natural() {
+                    write!(f, "add{extension}")?;
+                } else {
+                    write!(f, "{extension}")?;
+                }
+            }
+        } else {
+            f.write_str(suffix)?;
+            for extension in Extension::ALL {
+                if rest & extension.bit() == 0 {
+                    continue;
+                }
+                if extension.is_natural() {
+                    write!(f, "add{extension}")?;
+                } else {
+                    write!(f, "({extension})")?;
+                }
+            }
+        }
+        if let Some(bass) = self.bass {
+            write!(f, "/{bass}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn best(notes: &[Note]) -> alloc::string::String {
+    Chord::recognize(notes.iter().copied())[0].to_string()
+}
+
+#[test]
+fn recognize_triads() {
+    assert_eq!(best(&[note!(C, 4), note!(E, 4), note!(G, 4)]), "C");
+    assert_eq!(best(&[note!(A, 3), note!(C, 4), note!(E, 4)]), "Am");
+    assert_eq!(best(&[note!(D, 4), note!(F, 4), note!(ASharp, 4)]), "Bb/D");
+    assert_eq!(best(&[note!(FSharp, 3), note!(A, 3), note!(C, 4)]), "F#dim");
+    assert_eq!(best(&[note!(C, 4), note!(F, 4), note!(G, 4)]), "Csus4");
+    assert_eq!(best(&[note!(C, 4), note!(D, 4), note!(G, 4)]), "Csus2");
+    assert_eq!(best(&[note!(E, 2), note!(B, 2), note!(E, 3)]), "E5");
+    assert!(Chord::recognize([note!(C, 4), note!(C, 5)]).is_empty());
+    assert!(Chord::recognize([]).is_empty());
+}
+
+#[test]
+fn recognize_sevenths_and_extensions() {
+    assert_eq!(
+        best(&[note!(G, 3), note!(B, 3), note!(D, 4), note!(F, 4)]),
+        "G7"
+    );
+    assert_eq!(
+        best(&[note!(C, 4), note!(E, 4), note!(G, 4), note!(A, 4)]),
+        "C6"
+    );
+    assert_eq!(
+        best(&[note!(A, 3), note!(C, 4), note!(E, 4), note!(G, 4)]),
+        "Am7"
+    );
+    assert_eq!(
+        best(&[note!(B, 3), note!(D, 4), note!(F, 4), note!(A, 4)]),
+        "Bm7b5"
+    );
+    // the fifth left out
+    assert_eq!(best(&[note!(C, 3), note!(E, 4), note!(ASharp, 4)]), "C7");
+    assert_eq!(
+        best(&[note!(C, 3), note!(E, 4), note!(ASharp, 4), note!(CSharp, 5)]),
+        "C7b9"
+    );
+    assert_eq!(
+        best(&[
+            note!(C, 3),
+            note!(E, 3),
+            note!(G, 3),
+            note!(B, 3),
+            note!(D, 4)
+        ]),
+        "Cmaj9"
+    );
+    assert_eq!(
+        best(&[note!(C, 4), note!(E, 4), note!(G, 4), note!(D, 5)]),
+        "Cadd9"
+    );
+    assert_eq!(
+        best(&[
+            note!(G, 2),
+            note!(D, 3),
+            note!(F, 3),
+            note!(A, 3),
+            note!(C, 4),
+            note!(E, 4)
+        ]),
+        "G13sus4"
+    );
+}
+
+#[test]
+fn chord_notes() {
+    let b_flat = PitchName::new(Letter::B, Accidental::Flat);
+    let chord = Chord::new(b_flat, ChordQuality::Dominant7).with_extension(Extension::SharpEleven);
+    let names: Vec<_> = chord.names().iter().map(ToString::to_string).collect();
+    assert_eq!(names, ["Bb", "D", "F", "Ab", "E"]);
+    assert_eq!(chord.to_string(), "Bb7#11");
+    let d = PitchName::new(Letter::D, Accidental::Natural);
+    assert_eq!(
+        Chord::new(b_flat, ChordQuality::Major)
+            .with_bass(d)
+            .to_string(),
+        "Bb/D"
+    );
+    let g_flat_major = KeySignature::new(-6, false);
+    let chord = Chord::recognize([note!(FSharp, 3), note!(ASharp, 3), note!(CSharp, 4)])[0];
+    assert_eq!(chord.to_string(), "F#");
+    assert_eq!(chord.respell(g_flat_major).to_string(), "Gb");
+}
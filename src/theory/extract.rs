@@ -0,0 +1,266 @@
+use alloc::vec::Vec;
+
+use crate::{prelude::*, transform::note_spans};
+
+impl Track<'_> {
+    /// Returns the notes sounding at a tick, on every channel, from the lowest.
+    ///
+    /// A note sounds from its note on up to, but not including, its note off.
+    pub fn notes_at(&self, tick: u32) -> Vec<Note> {
+        let mut notes: Vec<Note> = sounding(self, true)
+            .filter(|(on, off, _)| *on <= tick && tick < *off)
+            .map(|(_, _, note)| note)
+            .collect();
+        notes.sort();
+        notes
+    }
+
+    /// Name the chord sounding at a tick, most likely first.
+    ///
+    /// [`Channel::Ten`] is left out, since its notes pick drum sounds rather than pitches.
+    /// See [`Chord::recognize`].
+    pub fn chords_at(&self, tick: u32) -> Vec<Chord> {
+        Chord::recognize(
+            sounding(self, false)
+                .filter(|(on, off, _)| *on <= tick && tick < *off)
+                .map(|(_, _, note)| note),
+        )
+    }
+}
+
+#[doc = r#"
+Finds the chords of a file over time, for a lead sheet.
+
+The notes of every track are combined, and the most likely chord is named each time
+the notes sounding change. A chord is held until a different one is found, so single
+notes and passing moments that form no chord don't interrupt it.
+Chords are spelled in the file's key signature, if it has one.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+let chords = [(0, [note!(C, 4), note!(E, 4), note!(G, 4)]), (96, [note!(F, 4), note!(A, 4), note!(C, 5)])];
+for (tick, notes) in chords {
+    for note in notes {
+        track.insert(tick, Channel::One.send_event(VoiceEvent::note_on(note, Velocity::MAX)).into());
+        track.insert(tick + 96, Channel::One.send_event(VoiceEvent::note_off(note, Velocity::ZERO)).into());
+    }
+}
+let file = MidiFile::new(
+    Format::SingleMultiChannel(track),
+    Timing::new_ticks_per_quarter_note(96),
+);
+
+let symbols: Vec<_> = ChordExtractor::new()
+    .extract(&file)
+    .iter()
+    .map(|chord| (chord.accumulated_ticks(), chord.event().to_string()))
+    .collect();
+assert_eq!(symbols, [(0, "C".to_string()), (96, "F".to_string())]);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ChordExtractor {
+    min_length: u32,
+    drums: bool,
+}
+
+impl ChordExtractor {
+    /// Extract every chord, leaving out [`Channel::Ten`]
+    pub const fn new() -> Self {
+        Self {
+            min_length: 0,
+            drums: false,
+        }
+    }
+
+    /// Ignore chords that last less than a number of ticks, such as passing notes
+    pub const fn with_min_length(mut self, ticks: u32) -> Self {
+        self.min_length = ticks;
+        self
+    }
+
+    /// Include [`Channel::Ten`], for files that use it for pitched instruments
+    pub const fn with_drums(mut self) -> Self {
+        self.drums = true;
+        self
+    }
+
+    /// Returns the chords of every track, each at the tick it begins
+    pub fn extract(&self, file: &MidiFile<'_>) -> Vec<Ticked<Chord>> {
+        let tracks = file.tracks();
+        let key_signature = tracks.iter().find_map(|track| track.info().key_signature);
+        let notes = tracks
+            .into_iter()
+            .flat_map(|track| sounding(track, self.drums))
+            .collect();
+        self.chords(notes, key_signature)
+    }
+
+    /// Returns the chords of a track, each at the tick it begins
+    pub fn extract_track(&self, track: &Track<'_>) -> Vec<Ticked<Chord>> {
+        let notes = sounding(track, self.drums).collect();
+        self.chords(notes, track.info().key_signature)
+    }
+
+    fn chords(
+        &self,
+        notes: Vec<(u32, u32, Note)>,
+        key_signature: Option<KeySignature>,
+    ) -> Vec<Ticked<Chord>> {
+        // every note on and off in order, swept with a count of each sounding note
+        let mut changes: Vec<(u32, bool, Note)> = notes
+            .into_iter()
+            .flat_map(|(on, off, note)| [(on, true, note), (off, false, note)])
+            .collect();
+        // ons first, so a count never drops below zero
+        changes.sort_unstable_by_key(|(tick, on, _)| (*tick, !*on));
+        let mut counts = [0u32; 128];
+
+        let mut chords: Vec<Ticked<Chord>> = Vec::new();
+        let mut index = 0;
+        while index < changes.len() {
+            let start = changes[index].0;
+            while let Some((tick, on, note)) = changes.get(index)
+                && *tick == start
+            {
+                let count = &mut counts[note.byte() as usize];
+                if *on {
+                    *count += 1;
+                } else {
+                    *count -= 1;
+                }
+                index += 1;
+            }
+            let Some((end, ..)) = changes.get(index) else {
+                break;
+            };
+            if end - start < self.min_length {
+                continue;
+            }
+            let sounding = Note::all()
+                .into_iter()
+                .filter(|note| counts[note.byte() as usize] > 0);
+            let Some(mut chord) = Chord::recognize(sounding).first().copied() else {
+                continue;
+            };
+            if let Some(key_signature) = key_signature {
+                chord = chord.respell(key_signature);
+            }
+            if chords.last().is_none_or(|last| *last.event() != chord) {
+                chords.push(Ticked::new(start, chord));
+            }
+        }
+        chords
+    }
+}
+
+/// The start tick, end tick and note of every note in a track.
+/// Notes that are never released last until the end of time.
+fn sounding<'t>(
+    track: &'t Track<'_>,
+    drums: bool,
+) -> impl Iterator<Item = (u32, u32, Note)> + use<'t> {
+    let events = track.events();
+    note_spans(events)
+        .into_iter()
+        .filter(move |span| drums || span.channel != Channel::Ten)
+        .map(move |span| {
+            let off = span
+                .off
+                .map_or(u32::MAX, |off| events[off].accumulated_ticks());
+            (events[span.on].accumulated_ticks(), off, span.note)
+        })
+}
+
+#[cfg(test)]
+fn play(track: &mut Track<'_>, channel: Channel, start: u32, end: u32, notes: &[Note]) {
+    for note in notes {
+        let on = VoiceEvent::note_on(*note, Velocity::MAX);
+        let off = VoiceEvent::note_off(*note, Velocity::ZERO);
+        track.insert(start, channel.send_event(on).into());
+        track.insert(end, channel.send_event(off).into());
+    }
+}
+
+#[test]
+fn chords_at_tick() {
+    let mut track = Track::new(Vec::new());
+    play(
+        &mut track,
+        Channel::One,
+        0,
+        96,
+        &[note!(A, 3), note!(C, 4), note!(E, 4)],
+    );
+    play(
+        &mut track,
+        Channel::Ten,
+        0,
+        96,
+        &[note!(C, 2), note!(FSharp, 2)],
+    );
+    assert_eq!(track.notes_at(96), []);
+    assert_eq!(track.notes_at(0).len(), 5);
+    assert_eq!(track.chords_at(95)[0].root().key(), Key::A);
+    assert_eq!(track.chords_at(95)[0].quality(), ChordQuality::Minor);
+}
+
+#[test]
+fn extract_chord_changes() {
+    use alloc::string::{String, ToString};
+
+    let mut track = Track::new(Vec::new());
+    // a bass line under held chords, with a short passing chord
+    play(
+        &mut track,
+        Channel::One,
+        0,
+        192,
+        &[note!(E, 4), note!(G, 4)],
+    );
+    play(&mut track, Channel::Two, 0, 96, &[note!(C, 3)]);
+    play(&mut track, Channel::Two, 96, 192, &[note!(E, 3)]);
+    play(
+        &mut track,
+        Channel::One,
+        192,
+        200,
+        &[note!(F, 4), note!(A, 4)],
+    );
+    play(
+        &mut track,
+        Channel::One,
+        200,
+        384,
+        &[note!(F, 4), note!(A, 4), note!(D, 5)],
+    );
+    play(&mut track, Channel::Two, 200, 384, &[note!(D, 3)]);
+    // a lone note forms no chord, so D minor is held
+    play(&mut track, Channel::Two, 384, 480, &[note!(D, 2)]);
+    // a note with no length never sounds
+    play(&mut track, Channel::Three, 96, 96, &[note!(B, 5)]);
+
+    let symbols = |extractor: ChordExtractor| -> Vec<(u32, String)> {
+        extractor
+            .extract_track(&track)
+            .iter()
+            .map(|chord| (chord.accumulated_ticks(), chord.event().to_string()))
+            .collect()
+    };
+    let expected = |symbols: &[(u32, &str)]| -> Vec<(u32, String)> {
+        symbols
+            .iter()
+            .map(|(tick, symbol)| (*tick, symbol.to_string()))
+            .collect()
+    };
+    assert_eq!(
+        symbols(ChordExtractor::new()),
+        expected(&[(0, "C"), (96, "Em"), (192, "F"), (200, "Dm")])
+    );
+    assert_eq!(
+        symbols(ChordExtractor::new().with_min_length(24)),
+        expected(&[(0, "C"), (96, "Em"), (200, "Dm")])
+    );
+}
@@ -0,0 +1,485 @@
+use core::fmt;
+use core::ops::{Add, Sub};
+
+use crate::prelude::*;
+
+#[cfg(test)]
+use alloc::string::ToString;
+
+/// The quality of an [`Interval`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Quality {
+    /// A semitone smaller than minor or perfect
+    Diminished,
+    /// A semitone smaller than major
+    Minor,
+    /// The quality of unisons, fourths, fifths and octaves
+    Perfect,
+    /// The quality of seconds, thirds, sixths and sevenths in a major scale
+    Major,
+    /// A semitone larger than major or perfect
+    Augmented,
+}
+
+impl Quality {
+    /// Returns the quality of the inverted interval
+    pub const fn invert(&self) -> Quality {
+        match self {
+            Quality::Diminished => Quality::Augmented,
+            Quality::Minor => Quality::Major,
+            Quality::Perfect => Quality::Perfect,
+            Quality::Major => Quality::Minor,
+            Quality::Augmented => Quality::Diminished,
+        }
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Quality::Diminished => "d",
+            Quality::Minor => "m",
+            Quality::Perfect => "P",
+            Quality::Major => "M",
+            Quality::Augmented => "A",
+        })
+    }
+}
+
+#[doc = r#"
+The distance between two notes, named by quality and number, such as `M3` or `P5`.
+
+Numbers above 8 are compound intervals: a `M10` is a major third and an octave.
+Intervals are ascending unless made [`descending`](Interval::descending).
+
+# Example
+```rust
+# use midix::prelude::*;
+let c4 = note!(C, 4);
+assert_eq!(c4 + Interval::MAJOR_THIRD, note!(E, 4));
+assert_eq!(c4 - Interval::PERFECT_FIFTH, note!(F, 3));
+
+let ninth = Interval::between(c4, note!(D, 5));
+assert_eq!(ninth.to_string(), "M9");
+assert_eq!(ninth.simple(), Interval::MAJOR_SECOND);
+assert_eq!(Interval::MAJOR_THIRD.invert(), Some(Interval::MINOR_SIXTH));
+
+let down = Interval::between(c4, note!(A, 3));
+assert_eq!(down, Interval::MINOR_THIRD.descending());
+assert_eq!(down.semitones(), -3);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    quality: Quality,
+    number: u8,
+    descending: bool,
+}
+
+/// Semitones of each simple major or perfect interval above the tonic
+const MAJOR_SCALE: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+impl Interval {
+    /// P1
+    pub const PERFECT_UNISON: Self = Self::simple_unchecked(Quality::Perfect, 1);
+    /// m2
+    pub const MINOR_SECOND: Self = Self::simple_unchecked(Quality::Minor, 2);
+    /// M2
+    pub const MAJOR_SECOND: Self = Self::simple_unchecked(Quality::Major, 2);
+    /// m3
+    pub const MINOR_THIRD: Self = Self::simple_unchecked(Quality::Minor, 3);
+    /// M3
+    pub const MAJOR_THIRD: Self = Self::simple_unchecked(Quality::Major, 3);
+    /// P4
+    pub const PERFECT_FOURTH: Self = Self::simple_unchecked(Quality::Perfect, 4);
+    /// A4
+    pub const AUGMENTED_FOURTH: Self = Self::simple_unchecked(Quality::Augmented, 4);
+    /// d5
+    pub const DIMINISHED_FIFTH: Self = Self::simple_unchecked(Quality::Diminished, 5);
+    /// P5
+    pub const PERFECT_FIFTH: Self = Self::simple_unchecked(Quality::Perfect, 5);
+    /// A5
+    pub const AUGMENTED_FIFTH: Self = Self::simple_unchecked(Quality::Augmented, 5);
+    /// m6
+    pub const MINOR_SIXTH: Self = Self::simple_unchecked(Quality::Minor, 6);
+    /// M6
+    pub const MAJOR_SIXTH: Self = Self::simple_unchecked(Quality::Major, 6);
+    /// d7
+    pub const DIMINISHED_SEVENTH: Self = Self::simple_unchecked(Quality::Diminished, 7);
+    /// m7
+    pub const MINOR_SEVENTH: Self = Self::simple_unchecked(Quality::Minor, 7);
+    /// M7
+    pub const MAJOR_SEVENTH: Self = Self::simple_unchecked(Quality::Major, 7);
+    /// P8
+    pub const OCTAVE: Self = Self::simple_unchecked(Quality::Perfect, 8);
+    /// m9
+    pub const MINOR_NINTH: Self = Self::simple_unchecked(Quality::Minor, 9);
+    /// M9
+    pub const MAJOR_NINTH: Self = Self::simple_unchecked(Quality::Major, 9);
+    /// A9
+    pub const AUGMENTED_NINTH: Self = Self::simple_unchecked(Quality::Augmented, 9);
+    /// P11
+    pub const PERFECT_ELEVENTH: Self = Self::simple_unchecked(Quality::Perfect, 11);
+    /// A11
+    pub const AUGMENTED_ELEVENTH: Self = Self::simple_unchecked(Quality::Augmented, 11);
+    /// m13
+    pub const MINOR_THIRTEENTH: Self = Self::simple_unchecked(Quality::Minor, 13);
+    /// M13
+    pub const MAJOR_THIRTEENTH: Self = Self::simple_unchecked(Quality::Major, 13);
+
+    const fn simple_unchecked(quality: Quality, number: u8) -> Self {
+        Self {
+            quality,
+            number,
+            descending: false,
+        }
+    }
+
+    /// Create an ascending interval.
+    ///
+    /// Returns `None` if the quality doesn't apply to the number (such as a major fifth
+    /// or a perfect third), for a diminished unison, or if the interval is wider than
+    /// the MIDI range.
+    pub const fn new(quality: Quality, number: u8) -> Option<Self> {
+        if number == 0 || (number == 1 && matches!(quality, Quality::Diminished)) {
+            return None;
+        }
+        let perfect = Self::is_perfect_number(number);
+        match quality {
+            Quality::Perfect if !perfect => return None,
+            Quality::Major | Quality::Minor if perfect => return None,
+            _ => {}
+        }
+        let interval = Self::simple_unchecked(quality, number);
+        // wider than the MIDI range, and too wide to count in semitones
+        if number > 76 || interval.size() > 127 {
+            return None;
+        }
+        Some(interval)
+    }
+
+    /// Returns the most common interval spanning a number of semitones,
+    /// descending if negative.
+    ///
+    /// A tritone is an augmented fourth. Intervals span at most 127 semitones,
+    /// so `i8::MIN` is read as -127.
+    pub const fn from_semitones(semitones: i8) -> Self {
+        let semitones = if semitones == i8::MIN {
+            -127
+        } else {
+            semitones
+        };
+        let size = semitones.unsigned_abs();
+        let (quality, number) = match size % 12 {
+            0 => (Quality::Perfect, 1),
+            1 => (Quality::Minor, 2),
+            2 => (Quality::Major, 2),
+            3 => (Quality::Minor, 3),
+            4 => (Quality::Major, 3),
+            5 => (Quality::Perfect, 4),
+            6 => (Quality::Augmented, 4),
+            7 => (Quality::Perfect, 5),
+            8 => (Quality::Minor, 6),
+            9 => (Quality::Major, 6),
+            10 => (Quality::Minor, 7),
+            _ => (Quality::Major, 7),
+        };
+        Self {
+            quality,
+            number: number + size / 12 * 7,
+            descending: semitones < 0,
+        }
+    }
+
+    /// Returns the interval from one note to another, descending if `to` is lower
+    pub fn between(from: Note, to: Note) -> Self {
+        Self::from_semitones((to.byte() as i16 - from.byte() as i16) as i8)
+    }
+
+    /// Returns the simple ascending interval from one spelled pitch up to another.
+    ///
+    /// Unlike [`Interval::between`], the spelling decides the interval:
+    /// `C` to `D#` is an augmented second, while `C` to `Eb` is a minor third.
+    /// Returns `None` if the interval is more than once augmented or diminished.
+    pub const fn between_names(from: PitchName, to: PitchName) -> Option<Self> {
+        let steps = (to.letter() as i8 - from.letter() as i8).rem_euclid(7);
+        let semitones = (to.pitch_class() as i8 - from.pitch_class() as i8).rem_euclid(12);
+        let mut difference = semitones - MAJOR_SCALE[steps as usize] as i8;
+        if difference > 6 {
+            difference -= 12;
+        } else if difference < -6 {
+            difference += 12;
+        }
+        let number = steps as u8 + 1;
+        let quality = match (Self::is_perfect_number(number), difference) {
+            (true, -1) | (false, -2) => Quality::Diminished,
+            (false, -1) => Quality::Minor,
+            (true, 0) => Quality::Perfect,
+            (false, 0) => Quality::Major,
+            (_, 1) => Quality::Augmented,
+            _ => return None,
+        };
+        Self::new(quality, number)
+    }
+
+    /// Returns the quality of the interval
+    pub const fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Returns the number of the interval, counting both notes, so a third is 3
+    pub const fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Returns true if the interval is descending
+    pub const fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Returns the interval pointing down
+    pub const fn descending(self) -> Self {
+        Self {
+            descending: true,
+            ..self
+        }
+    }
+
+    /// Returns the interval pointing up
+    pub const fn ascending(self) -> Self {
+        Self {
+            descending: false,
+            ..self
+        }
+    }
+
+    /// Returns true if the interval spans more than an octave
+    pub const fn is_compound(&self) -> bool {
+        self.number > 8
+    }
+
+    /// Returns the interval reduced to within an octave, keeping its direction.
+    ///
+    /// Octaves and their compounds reduce to an octave.
+    pub const fn simple(self) -> Self {
+        let number = if self.number == 1 {
+            1
+        } else {
+            (self.number - 2) % 7 + 2
+        };
+        Self { number, ..self }
+    }
+
+    /// Returns the inversion of the simple interval, which together with it spans an octave.
+    ///
+    /// A major third inverts to a minor sixth, and a perfect unison to an octave.
+    /// Compound intervals are reduced first, so a major ninth inverts to a minor seventh.
+    ///
+    /// Returns `None` for an augmented octave (or a compound of one),
+    /// as it would invert to a diminished unison.
+    pub const fn invert(self) -> Option<Self> {
+        let simple = self.simple();
+        if simple.number == 8 && matches!(simple.quality, Quality::Augmented) {
+            return None;
+        }
+        Some(Self {
+            quality: simple.quality.invert(),
+            number: 9 - simple.number,
+            descending: self.descending,
+        })
+    }
+
+    /// Returns the number of semitones spanned, negative if descending
+    pub const fn semitones(&self) -> i8 {
+        let size = self.size() as i8;
+        if self.descending { -size } else { size }
+    }
+
+    const fn size(&self) -> u8 {
+        let index = (self.number - 1) % 7;
+        let octaves = (self.number - 1) / 7;
+        let base = MAJOR_SCALE[index as usize] + octaves * 12;
+        let perfect = Self::is_perfect_number(self.number);
+        match self.quality {
+            Quality::Diminished if perfect => base - 1,
+            Quality::Diminished => base - 2,
+            Quality::Minor => base - 1,
+            Quality::Perfect | Quality::Major => base,
+            Quality::Augmented => base + 1,
+        }
+    }
+
+    const fn is_perfect_number(number: u8) -> bool {
+        matches!((number - 1) % 7, 0 | 3 | 4)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.descending {
+            f.write_str("-")?;
+        }
+        write!(f, "{}{}", self.quality, self.number)
+    }
+}
+
+impl Add<Interval> for Note {
+    type Output = Note;
+    /// Saturates at the ends of the MIDI range, like adding semitones.
+    /// Use [`Note::checked_transpose`] with [`Interval::semitones`] to detect this.
+    fn add(self, rhs: Interval) -> Self::Output {
+        let next = (self.byte() as i16 + rhs.semitones() as i16).clamp(0, 127);
+        Note::from_databyte_unchecked(next as u8)
+    }
+}
+
+impl Sub<Interval> for Note {
+    type Output = Note;
+    fn sub(self, rhs: Interval) -> Self::Output {
+        let opposite = Interval {
+            descending: !rhs.descending,
+            ..rhs
+        };
+        self + opposite
+    }
+}
+
+impl PitchName {
+    /// Returns the name an interval away, spelled by the interval's number.
+    ///
+    /// A major third above `Bb` is `D`, while a diminished fourth is `Ebb`.
+    /// Returns `None` if the name would need more than a double sharp or flat.
+    pub const fn transpose(&self, interval: Interval) -> Option<PitchName> {
+        let steps = (interval.number - 1) as i8;
+        let (steps, semitones) = if interval.descending {
+            (-steps, -(interval.size() as i8 % 12))
+        } else {
+            (steps, interval.size() as i8 % 12)
+        };
+        let letter = self.letter().step(steps);
+        let pitch_class = (self.pitch_class() as i8 + semitones).rem_euclid(12);
+        let mut accidental = (pitch_class - letter.natural_pitch_class() as i8).rem_euclid(12);
+        if accidental > 6 {
+            accidental -= 12;
+        }
+        match Accidental::from_semitones(accidental) {
+            Some(accidental) => Some(PitchName::new(letter, accidental)),
+            None => None,
+        }
+    }
+}
+
+#[test]
+fn interval_sizes() {
+    for semitones in -127..=127 {
+        let interval = Interval::from_semitones(semitones);
+        assert_eq!(interval.semitones(), semitones);
+    }
+    assert_eq!(Interval::from_semitones(i8::MIN).semitones(), -127);
+    assert_eq!(
+        Interval::new(Quality::Diminished, 8).unwrap().semitones(),
+        11
+    );
+    assert_eq!(
+        Interval::new(Quality::Augmented, 11).unwrap().semitones(),
+        18
+    );
+    assert_eq!(Interval::new(Quality::Minor, 13).unwrap().semitones(), 20);
+    assert_eq!(Interval::new(Quality::Major, 5), None);
+    assert_eq!(Interval::new(Quality::Perfect, 3), None);
+    assert_eq!(Interval::new(Quality::Diminished, 1), None);
+    assert_eq!(Interval::new(Quality::Perfect, 0), None);
+    assert_eq!(Interval::new(Quality::Perfect, 76), None);
+}
+
+#[test]
+fn simple_and_inverted() {
+    let ninth = Interval::new(Quality::Major, 9).unwrap();
+    assert!(ninth.is_compound());
+    assert_eq!(ninth.simple(), Interval::MAJOR_SECOND);
+    assert_eq!(ninth.invert(), Some(Interval::MINOR_SEVENTH));
+    assert_eq!(
+        Interval::new(Quality::Perfect, 15).unwrap().simple(),
+        Interval::OCTAVE
+    );
+    assert_eq!(Interval::PERFECT_UNISON.invert(), Some(Interval::OCTAVE));
+    assert_eq!(Interval::OCTAVE.invert(), Some(Interval::PERFECT_UNISON));
+    assert_eq!(
+        Interval::AUGMENTED_FOURTH.invert(),
+        Some(Interval::DIMINISHED_FIFTH)
+    );
+    let down = Interval::MINOR_SIXTH.descending();
+    assert_eq!(down.invert(), Some(Interval::MAJOR_THIRD.descending()));
+
+    let augmented_unison = Interval::new(Quality::Augmented, 1).unwrap();
+    let diminished_octave = Interval::new(Quality::Diminished, 8).unwrap();
+    assert_eq!(augmented_unison.invert(), Some(diminished_octave));
+    assert_eq!(diminished_octave.invert(), Some(augmented_unison));
+    assert_eq!(diminished_octave.invert().unwrap().semitones(), 1);
+    assert_eq!(Interval::new(Quality::Augmented, 8).unwrap().invert(), None);
+    assert_eq!(
+        Interval::new(Quality::Augmented, 15).unwrap().invert(),
+        None
+    );
+    assert_eq!(down.to_string(), "-m6");
+}
+
+#[test]
+fn note_arithmetic() {
+    let g9 = Note::from_databyte(127).unwrap();
+    assert_eq!(g9 + Interval::MINOR_SECOND, g9);
+    assert_eq!(
+        note!(C, 4) - Interval::MINOR_THIRD.descending(),
+        note!(DSharp, 4)
+    );
+    assert_eq!(
+        Interval::between(note!(C, 4), note!(G, 6)),
+        Interval::new(Quality::Perfect, 19).unwrap()
+    );
+    assert_eq!(
+        Interval::between(note!(C, 4), note!(C, 4)),
+        Interval::PERFECT_UNISON
+    );
+}
+
+#[test]
+fn spelled_intervals() {
+    let name = |letter, accidental| PitchName::new(letter, accidental);
+    let c = name(Letter::C, Accidental::Natural);
+    let d_sharp = name(Letter::D, Accidental::Sharp);
+    let e_flat = name(Letter::E, Accidental::Flat);
+    assert_eq!(
+        Interval::between_names(c, d_sharp),
+        Interval::new(Quality::Augmented, 2)
+    );
+    assert_eq!(
+        Interval::between_names(c, e_flat),
+        Some(Interval::MINOR_THIRD)
+    );
+    assert_eq!(
+        Interval::between_names(c, name(Letter::B, Accidental::Sharp)),
+        Interval::new(Quality::Augmented, 7)
+    );
+    assert_eq!(
+        Interval::between_names(c, name(Letter::E, Accidental::DoubleSharp)),
+        None
+    );
+
+    let b_flat = name(Letter::B, Accidental::Flat);
+    assert_eq!(
+        b_flat.transpose(Interval::MAJOR_THIRD),
+        Some(name(Letter::D, Accidental::Natural))
+    );
+    assert_eq!(
+        b_flat.transpose(Interval::new(Quality::Diminished, 4).unwrap()),
+        Some(name(Letter::E, Accidental::DoubleFlat))
+    );
+    assert_eq!(
+        e_flat.transpose(Interval::MAJOR_SIXTH.descending()),
+        Some(name(Letter::G, Accidental::Flat))
+    );
+    assert_eq!(
+        d_sharp.transpose(Interval::new(Quality::Major, 10).unwrap()),
+        Some(name(Letter::F, Accidental::DoubleSharp))
+    );
+}
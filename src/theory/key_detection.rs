@@ -0,0 +1,408 @@
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::{prelude::*, transform::note_spans};
+
+/// Krumhansl and Kessler's ratings of how well each pitch class fits a major key, from the tonic
+const MAJOR_PROFILE: [f64; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+
+/// Krumhansl and Kessler's ratings of how well each pitch class fits a minor key, from the tonic
+const MINOR_PROFILE: [f64; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+/// A major or minor key found by a [`KeyDetector`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyEstimate {
+    tonic: Key,
+    minor: bool,
+    correlation: f64,
+    confidence: f64,
+}
+
+impl KeyEstimate {
+    /// Returns the tonic of the key
+    pub const fn tonic(&self) -> Key {
+        self.tonic
+    }
+
+    /// Returns true for a minor key
+    pub const fn is_minor(&self) -> bool {
+        self.minor
+    }
+
+    /// Returns [`Mode::Aeolian`] for a minor key, and [`Mode::Ionian`] for a major key
+    pub const fn mode(&self) -> Mode {
+        if self.minor {
+            Mode::Aeolian
+        } else {
+            Mode::Ionian
+        }
+    }
+
+    /// Returns how closely the notes fit the key's profile, from -1 to 1
+    pub const fn correlation(&self) -> f64 {
+        self.correlation
+    }
+
+    /// Returns how much better the notes fit this key than any other.
+    ///
+    /// Near 0, another key is almost as likely. It is negative for keys that aren't the best fit.
+    pub const fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    /// Returns the major or natural minor scale of the key
+    pub const fn scale(&self) -> Scale {
+        Scale::new(self.tonic, self.mode())
+    }
+
+    /// Returns the key signature of the key, preferring six sharps to six flats
+    pub const fn key_signature(&self) -> KeySignature {
+        KeySignature::new(self.scale().fifths(), self.minor)
+    }
+
+    /// Returns a key signature meta message for the key
+    pub const fn meta(&self) -> MetaMessage<'static> {
+        MetaMessage::KeySignature(self.key_signature())
+    }
+
+    fn same_key(&self, other: &KeyEstimate) -> bool {
+        (self.tonic, self.minor) == (other.tonic, other.minor)
+    }
+}
+
+#[doc = r#"
+Estimates the key of a file from its notes.
+
+The time each pitch class sounds is compared with the Krumhansl-Schmuckler profiles
+of all 24 major and minor keys, and the key that correlates best is chosen.
+This works for files with no key signature, or the wrong one.
+
+[`Channel::Ten`] is left out by default, since its notes pick drum sounds rather than pitches.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+// an A harmonic minor scale, leaning on the tonic
+let notes = [note!(A, 3), note!(B, 3), note!(C, 4), note!(D, 4), note!(E, 4), note!(F, 4), note!(GSharp, 4), note!(A, 4)];
+for (index, note) in notes.into_iter().enumerate() {
+    let tick = index as u32 * 96;
+    let length = if note.key() == Key::A { 192 } else { 96 };
+    track.insert(tick, Channel::One.send_event(VoiceEvent::note_on(note, Velocity::MAX)).into());
+    track.insert(tick + length, Channel::One.send_event(VoiceEvent::note_off(note, Velocity::ZERO)).into());
+}
+let mut file = MidiFile::new(
+    Format::SingleMultiChannel(track),
+    Timing::new_ticks_per_quarter_note(96),
+);
+
+let key = KeyDetector::new().estimate(&file).unwrap();
+assert_eq!(key.tonic(), Key::A);
+assert!(key.is_minor());
+assert!(key.confidence() > 0.);
+
+// write the key signature into the file
+file.tracks_mut()[0].info_mut().key_signature = Some(key.key_signature());
+assert_eq!(key.key_signature(), KeySignature::new(0, true));
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KeyDetector {
+    window: Option<u32>,
+    hop: Option<u32>,
+    drums: bool,
+}
+
+impl KeyDetector {
+    /// Create a detector with windows of sixteen quarter notes, moving by four
+    pub const fn new() -> Self {
+        Self {
+            window: None,
+            hop: None,
+            drums: false,
+        }
+    }
+
+    /// The length in ticks of the region each key in a [`KeyDetector::timeline`] is estimated over
+    pub const fn with_window(mut self, ticks: u32) -> Self {
+        self.window = Some(ticks);
+        self
+    }
+
+    /// The distance in ticks between the keys estimated for a [`KeyDetector::timeline`]
+    pub const fn with_hop(mut self, ticks: u32) -> Self {
+        self.hop = Some(ticks);
+        self
+    }
+
+    /// Include [`Channel::Ten`], for files that use it for pitched instruments
+    pub const fn with_drums(mut self) -> Self {
+        self.drums = true;
+        self
+    }
+
+    /// Returns the most likely key of the whole file, or `None` if it has no notes
+    pub fn estimate(&self, file: &MidiFile<'_>) -> Option<KeyEstimate> {
+        self.estimate_region(file, 0..u32::MAX)
+    }
+
+    /// Returns the most likely key of the notes sounding in a region of ticks,
+    /// or `None` if there are none
+    pub fn estimate_region(&self, file: &MidiFile<'_>, region: Range<u32>) -> Option<KeyEstimate> {
+        self.rank_region(file, region).into_iter().next()
+    }
+
+    /// Returns all 24 major and minor keys for a region of ticks, most likely first,
+    /// or nothing if no notes sound in it
+    pub fn rank_region(&self, file: &MidiFile<'_>, region: Range<u32>) -> Vec<KeyEstimate> {
+        rank(&self.durations(&self.spans(file), region))
+    }
+
+    /// Returns the estimated key over time, each at the tick it begins.
+    ///
+    /// A key is estimated every hop from the notes in a window centered on it,
+    /// and only changes of key are kept. A key that lasts less than a window
+    /// is taken to be passing, and ignored.
+    /// Without a tempo-independent resolution, a quarter note is taken to be
+    /// half a second for the default window and hop.
+    pub fn timeline(&self, file: &MidiFile<'_>) -> Vec<Ticked<KeyEstimate>> {
+        let tracks = file.tracks();
+        let tempo = tracks
+            .first()
+            .map(|track| track.info().tempo)
+            .unwrap_or_default();
+        let quarter = file
+            .timing()
+            .micros_to_ticks(UMicros::new(tempo.micros_per_quarter_note() as u64), tempo)
+            .max(1);
+        let hop = self.hop.unwrap_or(quarter * 4).max(1);
+        let window = self.window.unwrap_or(quarter * 16).max(hop);
+
+        let spans = self.spans(file);
+        let end = spans.iter().map(|(_, off, _)| *off).max().unwrap_or(0);
+        // runs of the same key, as the tick, key and number of hops
+        let mut runs: Vec<(u32, KeyEstimate, u32)> = Vec::new();
+        for tick in (0..end).step_by(hop as usize) {
+            let start = tick.saturating_add(hop / 2).saturating_sub(window / 2);
+            let Some(key) = rank(&self.durations(&spans, start..start.saturating_add(window)))
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some((_, last, hops)) if last.same_key(&key) => *hops += 1,
+                _ => runs.push((tick, key, 1)),
+            }
+        }
+
+        // a key lasting less than a window is passing between its neighbours
+        let passing = (window / hop).max(1);
+        let mut timeline: Vec<Ticked<KeyEstimate>> = Vec::new();
+        for (index, (tick, key, hops)) in runs.iter().enumerate() {
+            if index > 0 && *hops < passing {
+                continue;
+            }
+            if timeline
+                .last()
+                .is_none_or(|last| !last.event().same_key(key))
+            {
+                timeline.push(Ticked::new(*tick, *key));
+            }
+        }
+        timeline
+    }
+
+    /// The start, end and pitch class of every note, with unreleased notes ending with their track
+    fn spans(&self, file: &MidiFile<'_>) -> Vec<(u32, u32, u8)> {
+        let mut spans = Vec::new();
+        for track in file.tracks() {
+            let events = track.events();
+            for span in note_spans(events) {
+                if !self.drums && span.channel == Channel::Ten {
+                    continue;
+                }
+                let on = events[span.on].accumulated_ticks();
+                let off = span
+                    .off
+                    .map_or(track.end_tick(), |off| events[off].accumulated_ticks());
+                spans.push((on, off, span.note.key().pitch_class()));
+            }
+        }
+        spans
+    }
+
+    /// The ticks each pitch class sounds for inside a region
+    fn durations(&self, spans: &[(u32, u32, u8)], region: Range<u32>) -> [f64; 12] {
+        let mut durations = [0.; 12];
+        for (on, off, pitch_class) in spans {
+            let start = (*on).max(region.start);
+            let end = (*off).min(region.end);
+            if start < end {
+                durations[*pitch_class as usize] += (end - start) as f64;
+            }
+        }
+        durations
+    }
+}
+
+/// Correlate the durations with every key's profile, best first
+fn rank(durations: &[f64; 12]) -> Vec<KeyEstimate> {
+    if durations.iter().all(|duration| *duration == 0.) {
+        return Vec::new();
+    }
+    let mut keys = Vec::with_capacity(24);
+    for minor in [false, true] {
+        let profile = if minor {
+            &MINOR_PROFILE
+        } else {
+            &MAJOR_PROFILE
+        };
+        for tonic in 0..12 {
+            let rotated: [f64; 12] =
+                core::array::from_fn(|pitch_class| profile[(pitch_class + 12 - tonic) % 12]);
+            keys.push(KeyEstimate {
+                tonic: Key::from_pitch_class(tonic as u8),
+                minor,
+                correlation: correlation(durations, &rotated),
+                confidence: 0.,
+            });
+        }
+    }
+    keys.sort_by(|a, b| b.correlation.total_cmp(&a.correlation));
+    let (best, second) = (keys[0].correlation, keys[1].correlation);
+    for (index, key) in keys.iter_mut().enumerate() {
+        key.confidence = key.correlation - if index == 0 { second } else { best };
+    }
+    keys
+}
+
+/// Pearson's correlation coefficient
+fn correlation(a: &[f64; 12], b: &[f64; 12]) -> f64 {
+    let mean = |values: &[f64; 12]| values.iter().sum::<f64>() / 12.;
+    let (mean_a, mean_b) = (mean(a), mean(b));
+    let (mut covariance, mut variance_a, mut variance_b) = (0., 0., 0.);
+    for (a, b) in a.iter().zip(b) {
+        covariance += (a - mean_a) * (b - mean_b);
+        variance_a += (a - mean_a) * (a - mean_a);
+        variance_b += (b - mean_b) * (b - mean_b);
+    }
+    if variance_a == 0. {
+        return 0.;
+    }
+    covariance / libm::sqrt(variance_a * variance_b)
+}
+
+#[cfg(test)]
+fn scale_file(sections: &[(Scale, u32)]) -> MidiFile<'static> {
+    let mut track = Track::new(Vec::new());
+    let mut tick = 0;
+    for (scale, bars) in sections {
+        for _ in 0..*bars {
+            // a bar of the scale, with the tonic and fifth held longer
+            for (degree, key) in scale.keys().into_iter().enumerate() {
+                let note = key.with_octave(Octave::new(4));
+                let length = if matches!(degree, 0 | 4) { 96 } else { 48 };
+                track.insert(
+                    tick,
+                    Channel::One
+                        .send_event(VoiceEvent::note_on(note, Velocity::MAX))
+                        .into(),
+                );
+                track.insert(
+                    tick + length,
+                    Channel::One
+                        .send_event(VoiceEvent::note_off(note, Velocity::ZERO))
+                        .into(),
+                );
+                tick += length;
+            }
+        }
+    }
+    MidiFile::new(
+        Format::SingleMultiChannel(track),
+        Timing::new_ticks_per_quarter_note(96),
+    )
+}
+
+#[test]
+fn estimate_keys() {
+    for tonic in Key::all() {
+        for scale in [Scale::major(tonic), Scale::new(tonic, Mode::HarmonicMinor)] {
+            let key = KeyDetector::new()
+                .estimate(&scale_file(&[(scale, 1)]))
+                .unwrap();
+            assert_eq!(key.tonic(), tonic, "{scale:?}");
+            assert_eq!(key.is_minor(), scale.mode() == Some(Mode::HarmonicMinor));
+        }
+    }
+    let key = KeyDetector::new()
+        .estimate(&scale_file(&[(Scale::major(Key::FSharp), 1)]))
+        .unwrap();
+    assert_eq!(key.key_signature(), KeySignature::new(6, false));
+    assert_eq!(
+        key.meta(),
+        MetaMessage::KeySignature(KeySignature::new(6, false))
+    );
+
+    let empty = MidiFile::new(
+        Format::SingleMultiChannel(Track::new(Vec::new())),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    assert_eq!(KeyDetector::new().estimate(&empty), None);
+    assert!(KeyDetector::new().rank_region(&empty, 0..96).is_empty());
+}
+
+#[test]
+fn ranked_keys() {
+    let file = scale_file(&[(Scale::major(Key::G), 2)]);
+    let ranked = KeyDetector::new().rank_region(&file, 0..u32::MAX);
+    assert_eq!(ranked.len(), 24);
+    assert!(ranked[0].confidence() > 0.);
+    assert!(ranked[1..].iter().all(|key| key.confidence() <= 0.));
+    assert!(
+        ranked
+            .windows(2)
+            .all(|pair| pair[0].correlation() >= pair[1].correlation())
+    );
+}
+
+#[test]
+fn key_changes() {
+    let file = scale_file(&[(Scale::major(Key::C), 4), (Scale::major(Key::E), 4)]);
+    let timeline = KeyDetector::new().timeline(&file);
+    let keys: Vec<_> = timeline
+        .iter()
+        .map(|key| (key.event().tonic(), key.event().is_minor()))
+        .collect();
+    assert_eq!(keys, [(Key::C, false), (Key::E, false)]);
+    // each bar is 432 ticks long, so E major begins at tick 1728
+    let change = timeline[1].accumulated_ticks();
+    assert!((1344..=2112).contains(&change), "{change}");
+}
+
+#[test]
+fn notes_at_the_last_tick() {
+    let mut track = Track::new(Vec::new());
+    for (index, key) in Scale::major(Key::D).keys().into_iter().enumerate() {
+        let note = key.with_octave(Octave::new(4));
+        let tick = u32::MAX - 700 + index as u32 * 96;
+        let on = Channel::One.send_event(VoiceEvent::note_on(note, Velocity::MAX));
+        let off = Channel::One.send_event(VoiceEvent::note_off(note, Velocity::ZERO));
+        track.insert(tick, on.into());
+        track.insert(tick + 96, off.into());
+    }
+    let file = MidiFile::new(
+        Format::SingleMultiChannel(track),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    // the second hop's window reaches past the last tick
+    let timeline = KeyDetector::new().with_hop(0xC000_0000).timeline(&file);
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].event().tonic(), Key::D);
+}
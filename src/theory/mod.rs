@@ -0,0 +1,32 @@
+#![doc = r#"
+Music theory: scales, modes, intervals, chords, tuning and the naming and spelling of notes
+"#]
+
+mod spelling;
+pub use spelling::*;
+
+mod scale;
+pub use scale::*;
+
+mod interval;
+pub use interval::*;
+
+mod chord;
+pub use chord::*;
+
+mod extract;
+pub use extract::*;
+
+mod symbol;
+
+mod note_name;
+pub use note_name::*;
+
+mod key_detection;
+pub use key_detection::*;
+
+mod voicing;
+pub use voicing::*;
+
+mod tuning;
+pub use tuning::*;
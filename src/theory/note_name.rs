@@ -0,0 +1,289 @@
+use core::{fmt, str::FromStr};
+
+use crate::prelude::*;
+
+#[cfg(test)]
+use alloc::string::ToString;
+
+#[doc = r#"
+How octaves are numbered, by the octave of middle C (MIDI note 60).
+
+Notes are read by name and octave, such as `C#4`, `Db-1` or `B♭3`.
+Names may also be German, with `H` for B and `is` and `es` for sharps and flats,
+or fixed-do solfège, from `Do` to `Si`. A `B` is always B natural.
+
+# Example
+```rust
+# use midix::prelude::*;
+let note: Note = "C#4".parse().unwrap();
+assert_eq!(note, note!(CSharp, 4));
+
+let yamaha = OctaveNumbering::MiddleC3;
+assert_eq!(yamaha.parse("C3"), Ok(note!(C, 4)));
+assert_eq!(yamaha.parse("Fis2"), Ok(note!(FSharp, 3)));
+assert_eq!(yamaha.parse("Sol-2"), Ok(note!(G, -1)));
+assert_eq!(yamaha.name(note!(A, 4)).to_string(), "A3");
+
+assert_eq!("Eb".parse(), Ok(Key::DSharp));
+assert_eq!("h".parse(), Ok(Key::B));
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OctaveNumbering {
+    /// Middle C is C3, as on Yamaha instruments and in many DAWs
+    MiddleC3,
+    /// Middle C is C4, in scientific pitch notation, as with [`Octave`]
+    #[default]
+    MiddleC4,
+    /// Middle C is C5
+    MiddleC5,
+}
+
+impl OctaveNumbering {
+    /// Returns the octave number of middle C
+    pub const fn middle_c(&self) -> i8 {
+        match self {
+            OctaveNumbering::MiddleC3 => 3,
+            OctaveNumbering::MiddleC4 => 4,
+            OctaveNumbering::MiddleC5 => 5,
+        }
+    }
+
+    /// Returns the octave number of a note
+    pub fn octave(&self, note: Note) -> i8 {
+        (note.byte() / 12) as i8 - 5 + self.middle_c()
+    }
+
+    /// Name a note with this numbering, with sharps for the black keys
+    pub const fn name(&self, note: Note) -> NoteName {
+        NoteName {
+            note,
+            numbering: *self,
+        }
+    }
+
+    /// Read a note name followed by its octave in this numbering
+    pub fn parse(&self, text: &str) -> Result<Note, SymbolError> {
+        let (name, read) = parse_name(text)?;
+        let octave = &text[read..];
+        let digits = octave.strip_prefix('-').unwrap_or(octave);
+        if digits.is_empty() {
+            return Err(SymbolError::MissingOctave);
+        }
+        if let Some(at) = digits.find(|char: char| !char.is_ascii_digit()) {
+            return Err(SymbolError::Unexpected(text.len() - digits.len() + at));
+        }
+        let octave: i32 = octave.parse().map_err(|_| SymbolError::OutOfRange)?;
+        let byte = (octave - self.middle_c() as i32 + 5) * 12
+            + name.letter().natural_pitch_class() as i32
+            + name.accidental().semitones() as i32;
+        if !(0..=127).contains(&byte) {
+            return Err(SymbolError::OutOfRange);
+        }
+        Ok(Note::from_databyte_unchecked(byte as u8))
+    }
+}
+
+/// A note named with an [`OctaveNumbering`], such as `C#4`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoteName {
+    note: Note,
+    numbering: OctaveNumbering,
+}
+
+impl NoteName {
+    /// Returns the note
+    pub const fn note(&self) -> Note {
+        self.note
+    }
+
+    /// Returns how the octave is numbered
+    pub const fn numbering(&self) -> OctaveNumbering {
+        self.numbering
+    }
+}
+
+impl fmt::Display for NoteName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = PitchName::in_signature(self.note.key().pitch_class(), 0);
+        write!(f, "{}{}", name, self.numbering.octave(self.note))
+    }
+}
+
+impl FromStr for Note {
+    type Err = SymbolError;
+    /// Reads a note name and its octave, with middle C as C4. See [`OctaveNumbering`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OctaveNumbering::MiddleC4.parse(s)
+    }
+}
+
+impl FromStr for Key {
+    type Err = SymbolError;
+    /// Reads a note name without an octave. See [`OctaveNumbering`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, read) = parse_name(s)?;
+        if read != s.len() {
+            return Err(SymbolError::Unexpected(read));
+        }
+        Ok(name.key())
+    }
+}
+
+/// Read a letter, German or solfège name from the start of some text,
+/// returning it and the bytes read
+fn parse_name(text: &str) -> Result<(PitchName, usize), SymbolError> {
+    if let Some((letter, read)) = solfege(text) {
+        let (accidental, accidental_read) =
+            Accidental::parse_prefix(&text[read..]).map_err(|error| error.after(read))?;
+        return Ok((PitchName::new(letter, accidental), read + accidental_read));
+    }
+    let (name, read) = match text.chars().next() {
+        Some('H' | 'h') => {
+            let (accidental, read) =
+                Accidental::parse_prefix(&text[1..]).map_err(|error| error.after(1))?;
+            (PitchName::new(Letter::B, accidental), read + 1)
+        }
+        _ => PitchName::parse_prefix(text)?,
+    };
+    if read == 1
+        && let Some((accidental, german)) = german(name.letter(), &text[1..])
+    {
+        return Ok((PitchName::new(name.letter(), accidental), german + 1));
+    }
+    Ok((name, read))
+}
+
+/// A fixed-do solfège syllable at the start of some text, in any case
+fn solfege(text: &str) -> Option<(Letter, usize)> {
+    const SYLLABLES: [(&str, Letter); 9] = [
+        ("sol", Letter::G),
+        ("do", Letter::C),
+        ("re", Letter::D),
+        ("mi", Letter::E),
+        ("fa", Letter::F),
+        ("so", Letter::G),
+        ("la", Letter::A),
+        ("si", Letter::B),
+        ("ti", Letter::B),
+    ];
+    SYLLABLES.iter().find_map(|(syllable, letter)| {
+        text.get(..syllable.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(syllable))
+            .then_some((*letter, syllable.len()))
+    })
+}
+
+/// German sharps and flats after a letter, such as `Fis`, `Es` and `Ceses`
+fn german(letter: Letter, text: &str) -> Option<(Accidental, usize)> {
+    let mut rest = text;
+    let mut semitones: i8 = 0;
+    // As and Es, rather than Aes and Ees
+    if matches!(letter, Letter::A | Letter::E)
+        && let Some(after) = rest.strip_prefix('s')
+    {
+        semitones = -1;
+        rest = after;
+    }
+    while semitones.abs() < 2 {
+        if semitones >= 0
+            && let Some(after) = rest.strip_prefix("is")
+        {
+            semitones += 1;
+            rest = after;
+        } else if semitones <= 0
+            && let Some(after) = rest.strip_prefix("es")
+        {
+            semitones -= 1;
+            rest = after;
+        } else {
+            break;
+        }
+    }
+    let accidental = Accidental::from_semitones(semitones)?;
+    (semitones != 0).then_some((accidental, text.len() - rest.len()))
+}
+
+#[test]
+fn parse_notes() {
+    let parse = |text: &str| text.parse::<Note>();
+    assert_eq!(parse("C4"), Ok(note!(C, 4)));
+    assert_eq!(parse("c#4"), Ok(note!(CSharp, 4)));
+    assert_eq!(parse("Db-1"), Ok(note!(CSharp, -1)));
+    assert_eq!(parse("B♭3"), Ok(note!(ASharp, 3)));
+    assert_eq!(parse("bb3"), Ok(note!(ASharp, 3)));
+    assert_eq!(parse("Cb4"), Ok(note!(B, 3)));
+    assert_eq!(parse("B#3"), Ok(note!(C, 4)));
+    assert_eq!(parse("Fx4"), Ok(note!(G, 4)));
+    assert_eq!(parse("G9"), Ok(note!(G, 9)));
+    assert_eq!(parse("C-1").map(|note| note.byte()), Ok(0));
+
+    assert_eq!(parse("h2"), Ok(note!(B, 2)));
+    assert_eq!(parse("Fis4"), Ok(note!(FSharp, 4)));
+    assert_eq!(parse("es4"), Ok(note!(DSharp, 4)));
+    assert_eq!(parse("As4"), Ok(note!(GSharp, 4)));
+    assert_eq!(parse("Ceses4"), Ok(note!(ASharp, 3)));
+    assert_eq!(parse("His3"), Ok(note!(C, 4)));
+
+    assert_eq!(parse("Do4"), Ok(note!(C, 4)));
+    assert_eq!(parse("SOL#4"), Ok(note!(GSharp, 4)));
+    assert_eq!(parse("sib3"), Ok(note!(ASharp, 3)));
+    assert_eq!(parse("Ti5"), Ok(note!(B, 5)));
+}
+
+#[test]
+fn parse_errors() {
+    let parse = |text: &str| text.parse::<Note>();
+    assert_eq!(parse(""), Err(SymbolError::Empty));
+    assert_eq!(parse("Q4"), Err(SymbolError::Letter('Q')));
+    assert_eq!(parse("C#"), Err(SymbolError::MissingOctave));
+    assert_eq!(parse("C-"), Err(SymbolError::MissingOctave));
+    assert_eq!(parse("C4x"), Err(SymbolError::Unexpected(2)));
+    assert_eq!(parse("Fa#4 "), Err(SymbolError::Unexpected(4)));
+    assert_eq!(parse("G#9"), Err(SymbolError::OutOfRange));
+    assert_eq!(parse("Cb-1"), Err(SymbolError::OutOfRange));
+    assert_eq!(parse("C99999999999"), Err(SymbolError::OutOfRange));
+    assert_eq!(
+        OctaveNumbering::MiddleC5.parse("C-1"),
+        Err(SymbolError::OutOfRange)
+    );
+
+    assert_eq!("C4".parse::<Key>(), Err(SymbolError::Unexpected(1)));
+    assert_eq!("Gis".parse::<Key>(), Ok(Key::GSharp));
+    assert_eq!("Re".parse::<Key>(), Ok(Key::D));
+}
+
+#[test]
+fn numbering_round_trip() {
+    for numbering in [
+        OctaveNumbering::MiddleC3,
+        OctaveNumbering::MiddleC4,
+        OctaveNumbering::MiddleC5,
+    ] {
+        for note in Note::all() {
+            let name = numbering.name(note);
+            assert_eq!(name.note(), note);
+            assert_eq!(numbering.parse(&name.to_string()), Ok(note));
+        }
+    }
+    let middle_c = note!(C, 4);
+    assert_eq!(OctaveNumbering::MiddleC3.name(middle_c).to_string(), "C3");
+    assert_eq!(OctaveNumbering::MiddleC4.name(middle_c).to_string(), "C4");
+    assert_eq!(OctaveNumbering::MiddleC5.name(middle_c).to_string(), "C5");
+    assert_eq!(
+        OctaveNumbering::MiddleC3
+            .name(Note::from_databyte(1).unwrap())
+            .to_string(),
+        "C#-2"
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    for note in Note::all() {
+        assert_eq!(note.to_string().parse(), Ok(note));
+        assert_eq!(note.key().to_string().parse(), Ok(note.key()));
+    }
+    assert_eq!(note!(GSharp, -1).to_string(), "G#-1");
+    assert_eq!(Key::ASharp.to_string(), "A#");
+}
@@ -0,0 +1,374 @@
+#[cfg(test)]
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+/// A standard set of intervals for a [`Scale`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// The major scale
+    Ionian,
+    /// A minor mode with a raised sixth
+    Dorian,
+    /// A minor mode with a lowered second
+    Phrygian,
+    /// A major mode with a raised fourth
+    Lydian,
+    /// A major mode with a lowered seventh
+    Mixolydian,
+    /// The natural minor scale
+    Aeolian,
+    /// A diminished mode with a lowered second and fifth
+    Locrian,
+    /// The natural minor scale with a raised seventh
+    HarmonicMinor,
+    /// The natural minor scale with a raised sixth and seventh
+    MelodicMinor,
+    /// Five notes of the major scale, without its fourth and seventh
+    MajorPentatonic,
+    /// Five notes of the natural minor scale, without its second and sixth
+    MinorPentatonic,
+    /// The minor pentatonic scale with an added lowered fifth
+    Blues,
+    /// Six notes a whole tone apart
+    WholeTone,
+    /// All twelve notes
+    Chromatic,
+}
+
+impl Mode {
+    /// Every mode
+    pub const ALL: [Mode; 14] = [
+        Mode::Ionian,
+        Mode::Dorian,
+        Mode::Phrygian,
+        Mode::Lydian,
+        Mode::Mixolydian,
+        Mode::Aeolian,
+        Mode::Locrian,
+        Mode::HarmonicMinor,
+        Mode::MelodicMinor,
+        Mode::MajorPentatonic,
+        Mode::MinorPentatonic,
+        Mode::Blues,
+        Mode::WholeTone,
+        Mode::Chromatic,
+    ];
+
+    /// Returns the semitones of each degree above the tonic, starting with the tonic
+    pub const fn intervals(&self) -> &'static [u8] {
+        match self {
+            Mode::Ionian => &[0, 2, 4, 5, 7, 9, 11],
+            Mode::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            Mode::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            Mode::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            Mode::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            Mode::Aeolian => &[0, 2, 3, 5, 7, 8, 10],
+            Mode::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            Mode::HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
+            Mode::MelodicMinor => &[0, 2, 3, 5, 7, 9, 11],
+            Mode::MajorPentatonic => &[0, 2, 4, 7, 9],
+            Mode::MinorPentatonic => &[0, 3, 5, 7, 10],
+            Mode::Blues => &[0, 3, 5, 6, 7, 10],
+            Mode::WholeTone => &[0, 2, 4, 6, 8, 10],
+            Mode::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+
+    /// The key signature of the mode on C, as fifths away from C major
+    const fn fifths(&self) -> i8 {
+        match self {
+            Mode::Lydian => 1,
+            Mode::Ionian | Mode::MajorPentatonic | Mode::WholeTone | Mode::Chromatic => 0,
+            Mode::Mixolydian => -1,
+            Mode::Dorian => -2,
+            Mode::Aeolian
+            | Mode::HarmonicMinor
+            | Mode::MelodicMinor
+            | Mode::MinorPentatonic
+            | Mode::Blues => -3,
+            Mode::Phrygian => -4,
+            Mode::Locrian => -5,
+        }
+    }
+}
+
+#[doc = r#"
+A tonic and a set of intervals above it.
+
+Scales are built from a standard [`Mode`], from custom intervals, or from a
+[`KeySignature`]. Each scale also carries a key signature, used to spell its notes.
+
+# Example
+```rust
+# use midix::prelude::*;
+let f_major = Scale::from_key_signature(KeySignature::new(-1, false));
+assert_eq!(f_major.tonic(), Key::F);
+assert!(f_major.contains(Key::ASharp));
+assert_eq!(f_major.spell(Key::ASharp).to_string(), "Bb");
+
+// snap a wrong note into the scale
+assert_eq!(f_major.snap(note!(B, 4)), note!(ASharp, 4));
+
+let d_harmonic_minor = Scale::new(Key::D, Mode::HarmonicMinor);
+let names: Vec<_> = d_harmonic_minor.names().iter().map(ToString::to_string).collect();
+assert_eq!(names, ["D", "E", "F", "G", "A", "Bb", "C#"]);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Scale {
+    tonic: Key,
+    /// One bit for each semitone above the tonic in the scale
+    intervals: u16,
+    /// The key signature used for spelling, as sharps (or flats, if negative)
+    fifths: i8,
+}
+
+impl Scale {
+    /// Create a scale from a tonic and a standard mode
+    pub const fn new(tonic: Key, mode: Mode) -> Self {
+        let intervals = mode.intervals();
+        let mut mask = 0;
+        let mut index = 0;
+        while index < intervals.len() {
+            mask |= 1 << intervals[index];
+            index += 1;
+        }
+        Self {
+            tonic,
+            intervals: mask,
+            fifths: fifths_of(tonic, mode.fifths()),
+        }
+    }
+
+    /// The major scale of a tonic
+    pub const fn major(tonic: Key) -> Self {
+        Self::new(tonic, Mode::Ionian)
+    }
+
+    /// The natural minor scale of a tonic
+    pub const fn minor(tonic: Key) -> Self {
+        Self::new(tonic, Mode::Aeolian)
+    }
+
+    /// Create a scale from semitones above the tonic, in any order.
+    ///
+    /// Semitones of 12 or more wrap into the octave, and the tonic is always included.
+    /// Notes are spelled as in the major key of the tonic.
+    pub fn custom(tonic: Key, semitones: &[u8]) -> Self {
+        let intervals = semitones
+            .iter()
+            .fold(1, |mask, semitone| mask | 1 << (semitone % 12));
+        Self {
+            tonic,
+            intervals,
+            fifths: fifths_of(tonic, 0),
+        }
+    }
+
+    /// The major or natural minor scale of a key signature, spelled as it is
+    pub const fn from_key_signature(key_signature: KeySignature) -> Self {
+        let mode = if key_signature.minor_key() {
+            Mode::Aeolian
+        } else {
+            Mode::Ionian
+        };
+        let mut scale = Self::new(key_signature.tonic(), mode);
+        scale.fifths = key_signature.sharp_flat_count();
+        scale
+    }
+
+    /// Returns the tonic of the scale
+    pub const fn tonic(&self) -> Key {
+        self.tonic
+    }
+
+    /// The key signature the scale is spelled in, as sharps (or flats, if negative)
+    pub(super) const fn fifths(&self) -> i8 {
+        self.fifths
+    }
+
+    /// Returns the name of the tonic
+    pub const fn tonic_name(&self) -> PitchName {
+        PitchName::in_signature(self.tonic.pitch_class(), self.fifths)
+    }
+
+    /// Returns the standard mode with the scale's intervals, if there is one
+    pub fn mode(&self) -> Option<Mode> {
+        Mode::ALL
+            .into_iter()
+            .find(|mode| Scale::new(self.tonic, *mode).intervals == self.intervals)
+    }
+
+    /// Returns the semitones of each degree above the tonic, starting with the tonic
+    pub fn intervals(&self) -> Vec<u8> {
+        (0..12)
+            .filter(|semitone| self.intervals & (1 << semitone) != 0)
+            .collect()
+    }
+
+    /// Returns the number of notes in the scale
+    pub const fn len(&self) -> usize {
+        self.intervals.count_ones() as usize
+    }
+
+    /// Always false, since a scale contains its tonic
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the keys of the scale, ascending from the tonic
+    pub fn keys(&self) -> Vec<Key> {
+        let tonic = self.tonic.pitch_class();
+        self.intervals()
+            .into_iter()
+            .map(|semitone| Key::from_pitch_class(tonic + semitone))
+            .collect()
+    }
+
+    /// Returns the names of the keys of the scale, ascending from the tonic
+    pub fn names(&self) -> Vec<PitchName> {
+        self.keys().into_iter().map(|key| self.spell(key)).collect()
+    }
+
+    /// Returns true if the key is in the scale
+    pub const fn contains(&self, key: Key) -> bool {
+        self.intervals & (1 << self.semitones_above_tonic(key)) != 0
+    }
+
+    /// Returns the degree of a key in the scale, counted from 0 for the tonic
+    pub fn degree(&self, key: Key) -> Option<usize> {
+        if !self.contains(key) {
+            return None;
+        }
+        let below = self.intervals & ((1 << self.semitones_above_tonic(key)) - 1);
+        Some(below.count_ones() as usize)
+    }
+
+    /// Move a note to the nearest note in the scale.
+    ///
+    /// A note halfway between two notes of the scale moves down,
+    /// unless that would leave the MIDI range.
+    pub fn snap(&self, note: Note) -> Note {
+        let byte = note.byte() as i16;
+        for distance in 0..12 {
+            for candidate in [byte - distance, byte + distance] {
+                if (0..=127).contains(&candidate) {
+                    let candidate = Note::from_databyte_unchecked(candidate as u8);
+                    if self.contains(candidate.key()) {
+                        return candidate;
+                    }
+                }
+            }
+        }
+        note
+    }
+
+    #[doc = r#"
+    Name a key in the context of the scale.
+
+    In a scale of seven notes, each degree takes the next letter up from the tonic,
+    so D harmonic minor has a `C#` rather than a `Db`.
+    Other notes are spelled by the scale's key signature, as [`KeySignature::spell`] does.
+    "#]
+    pub const fn spell(&self, key: Key) -> PitchName {
+        if self.len() == 7 && self.contains(key) {
+            let below = self.intervals & ((1 << self.semitones_above_tonic(key)) - 1);
+            let letter = self.tonic_name().letter().step(below.count_ones() as i8);
+            let mut semitones =
+                (key.pitch_class() as i8 - letter.natural_pitch_class() as i8).rem_euclid(12);
+            if semitones > 6 {
+                semitones -= 12;
+            }
+            if let Some(accidental) = Accidental::from_semitones(semitones) {
+                return PitchName::new(letter, accidental);
+            }
+        }
+        PitchName::in_signature(key.pitch_class(), self.fifths)
+    }
+
+    const fn semitones_above_tonic(&self, key: Key) -> u8 {
+        (key.pitch_class() + 12 - self.tonic.pitch_class()) % 12
+    }
+}
+
+impl KeySignature {
+    /// Returns the major or natural minor scale of the key signature
+    pub const fn scale(&self) -> Scale {
+        Scale::from_key_signature(*self)
+    }
+}
+
+/// The key signature of a mode with `mode_fifths` on C, moved to `tonic`.
+///
+/// Keys are kept within six sharps or flats, preferring sharps at six.
+pub(super) const fn fifths_of(tonic: Key, mode_fifths: i8) -> i8 {
+    // a semitone is seven steps around the circle of fifths
+    let fifths = (tonic.pitch_class() as i8 * 7 + mode_fifths).rem_euclid(12);
+    if fifths > 6 { fifths - 12 } else { fifths }
+}
+
+#[test]
+fn modes_and_degrees() {
+    let dorian = Scale::new(Key::D, Mode::Dorian);
+    assert_eq!(
+        dorian.keys(),
+        Scale::major(Key::C).keys()[1..]
+            .iter()
+            .copied()
+            .chain([Key::C])
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(dorian.mode(), Some(Mode::Dorian));
+    assert_eq!(dorian.degree(Key::A), Some(4));
+    assert_eq!(dorian.degree(Key::ASharp), None);
+
+    let custom = Scale::custom(Key::E, &[0, 1, 4, 5, 19, 8, 10]);
+    assert_eq!(custom.mode(), None);
+    assert_eq!(custom.len(), 7);
+    assert_eq!(
+        custom.keys(),
+        [Key::E, Key::F, Key::GSharp, Key::A, Key::B, Key::C, Key::D]
+    );
+}
+
+#[test]
+fn scale_spelling() {
+    let names =
+        |scale: Scale| -> Vec<String> { scale.names().iter().map(ToString::to_string).collect() };
+    assert_eq!(
+        names(Scale::major(Key::ASharp)),
+        ["Bb", "C", "D", "Eb", "F", "G", "A"]
+    );
+    assert_eq!(
+        names(Scale::minor(Key::DSharp)),
+        ["D#", "E#", "F#", "G#", "A#", "B", "C#"]
+    );
+    assert_eq!(
+        names(Scale::new(Key::FSharp, Mode::Locrian)),
+        ["F#", "G", "A", "B", "C", "D", "E"]
+    );
+    let c_sharp = Scale::from_key_signature(KeySignature::new(7, false));
+    assert_eq!(names(c_sharp), ["C#", "D#", "E#", "F#", "G#", "A#", "B#"]);
+    assert_eq!(
+        names(Scale::new(Key::A, Mode::MinorPentatonic)),
+        ["A", "C", "D", "E", "G"]
+    );
+    // outside the scale
+    assert_eq!(Scale::major(Key::F).spell(Key::FSharp).to_string(), "Gb");
+}
+
+#[test]
+fn snapping() {
+    let pentatonic = Scale::new(Key::C, Mode::MajorPentatonic);
+    assert_eq!(pentatonic.snap(note!(F, 4)), note!(E, 4));
+    assert_eq!(pentatonic.snap(note!(FSharp, 4)), note!(G, 4));
+    assert_eq!(pentatonic.snap(note!(B, 4)), note!(C, 5));
+    // a tie at the bottom of the range moves up
+    let lowest = Note::from_databyte(0).unwrap();
+    assert_eq!(
+        Scale::major(Key::B).snap(lowest),
+        Note::from_databyte(1).unwrap()
+    );
+}
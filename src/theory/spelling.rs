@@ -0,0 +1,304 @@
+#[cfg(test)]
+use alloc::string::ToString;
+use core::fmt;
+
+use crate::prelude::*;
+
+/// The letter of a note name, without its accidental
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Letter {
+    /// C
+    C,
+    /// D
+    D,
+    /// E
+    E,
+    /// F
+    F,
+    /// G
+    G,
+    /// A
+    A,
+    /// B
+    B,
+}
+
+impl Letter {
+    /// Every letter, from C to B
+    pub const ALL: [Letter; 7] = [
+        Letter::C,
+        Letter::D,
+        Letter::E,
+        Letter::F,
+        Letter::G,
+        Letter::A,
+        Letter::B,
+    ];
+
+    /// Returns the letter a number of steps above this one, wrapping from B to C
+    pub const fn step(&self, steps: i8) -> Letter {
+        Self::ALL[(*self as i8 + steps).rem_euclid(7) as usize]
+    }
+
+    /// Returns the pitch class of the letter without an accidental
+    pub const fn natural_pitch_class(&self) -> u8 {
+        match self {
+            Letter::C => 0,
+            Letter::D => 2,
+            Letter::E => 4,
+            Letter::F => 5,
+            Letter::G => 7,
+            Letter::A => 9,
+            Letter::B => 11,
+        }
+    }
+
+    /// The position of the letter in the order sharps are added to a key signature
+    const fn fifths_index(&self) -> i8 {
+        match self {
+            Letter::F => 0,
+            Letter::C => 1,
+            Letter::G => 2,
+            Letter::D => 3,
+            Letter::A => 4,
+            Letter::E => 5,
+            Letter::B => 6,
+        }
+    }
+
+    /// The accidental a key signature of `fifths` sharps (or flats, if negative) gives this letter
+    const fn accidental_in(&self, fifths: i8) -> Accidental {
+        let index = self.fifths_index();
+        if fifths > 0 && index < fifths {
+            Accidental::Sharp
+        } else if fifths < 0 && index >= 7 + fifths {
+            Accidental::Flat
+        } else {
+            Accidental::Natural
+        }
+    }
+}
+
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Raises or lowers a [`Letter`] by semitones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Accidental {
+    /// Two semitones lower
+    DoubleFlat,
+    /// One semitone lower
+    Flat,
+    /// Unchanged
+    Natural,
+    /// One semitone higher
+    Sharp,
+    /// Two semitones higher
+    DoubleSharp,
+}
+
+impl Accidental {
+    /// Returns the accidental that moves a letter by `semitones`, if there is one
+    pub const fn from_semitones(semitones: i8) -> Option<Self> {
+        Some(match semitones {
+            -2 => Self::DoubleFlat,
+            -1 => Self::Flat,
+            0 => Self::Natural,
+            1 => Self::Sharp,
+            2 => Self::DoubleSharp,
+            _ => return None,
+        })
+    }
+
+    /// Returns the number of semitones the accidental moves a letter by
+    pub const fn semitones(&self) -> i8 {
+        *self as i8 - 2
+    }
+}
+
+impl fmt::Display for Accidental {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::DoubleFlat => "bb",
+            Self::Flat => "b",
+            Self::Natural => "",
+            Self::Sharp => "#",
+            Self::DoubleSharp => "##",
+        })
+    }
+}
+
+#[doc = r#"
+The written name of a pitch, such as `Bb` or `A#`.
+
+A [`Key`] only identifies a pitch class. Its name depends on the musical context,
+given by a [`KeySignature`] or a [`Scale`].
+
+# Example
+```rust
+# use midix::prelude::*;
+let b_flat = PitchName::new(Letter::B, Accidental::Flat);
+assert_eq!(b_flat.key(), Key::ASharp);
+assert_eq!(b_flat.to_string(), "Bb");
+
+let f_major = KeySignature::new(-1, false);
+assert_eq!(f_major.spell(Key::ASharp), b_flat);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PitchName {
+    letter: Letter,
+    accidental: Accidental,
+}
+
+impl PitchName {
+    /// Create a name from a letter and accidental
+    pub const fn new(letter: Letter, accidental: Accidental) -> Self {
+        Self { letter, accidental }
+    }
+
+    /// Returns the letter of the name
+    pub const fn letter(&self) -> Letter {
+        self.letter
+    }
+
+    /// Returns the accidental of the name
+    pub const fn accidental(&self) -> Accidental {
+        self.accidental
+    }
+
+    /// Returns the pitch class named, from 0 for C to 11 for B
+    pub const fn pitch_class(&self) -> u8 {
+        (self.letter.natural_pitch_class() as i8 + self.accidental.semitones()).rem_euclid(12) as u8
+    }
+
+    /// Returns the key named
+    pub const fn key(&self) -> Key {
+        Key::from_pitch_class(self.pitch_class())
+    }
+
+    /// Name a pitch class in a key signature of `fifths` sharps (or flats, if negative).
+    ///
+    /// Notes of the key signature are named as it spells them. A note outside it is
+    /// named without an accidental if it can be, and otherwise as a sharp in sharp
+    /// keys and C major, or as a flat in flat keys.
+    pub(crate) const fn in_signature(pitch_class: u8, fifths: i8) -> Self {
+        let pitch_class = pitch_class % 12;
+        let mut index = 0;
+        while index < 7 {
+            let letter = Letter::ALL[index];
+            let name = Self::new(letter, letter.accidental_in(fifths));
+            if name.pitch_class() == pitch_class {
+                return name;
+            }
+            index += 1;
+        }
+
+        let mut index = 0;
+        while index < 7 {
+            let letter = Letter::ALL[index];
+            if letter.natural_pitch_class() == pitch_class {
+                return Self::new(letter, Accidental::Natural);
+            }
+            index += 1;
+        }
+
+        let (neighbour, accidental) = if fifths >= 0 {
+            ((pitch_class + 11) % 12, Accidental::Sharp)
+        } else {
+            ((pitch_class + 1) % 12, Accidental::Flat)
+        };
+        let mut index = 0;
+        while index < 7 {
+            let letter = Letter::ALL[index];
+            if letter.natural_pitch_class() == neighbour {
+                return Self::new(letter, accidental);
+            }
+            index += 1;
+        }
+        panic!("every pitch class is a natural or next to one")
+    }
+}
+
+impl fmt::Display for PitchName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.letter, self.accidental)
+    }
+}
+
+impl KeySignature {
+    /// Returns the name of the key's tonic, such as `Bb` for B flat major or `F#` for F sharp minor
+    pub const fn tonic_name(&self) -> PitchName {
+        // a minor key's tonic is three fifths above its relative major
+        let fifths = self.sharp_flat_count() + if self.minor_key() { 3 } else { 0 };
+        let position = fifths + 1;
+        let letter = [
+            Letter::F,
+            Letter::C,
+            Letter::G,
+            Letter::D,
+            Letter::A,
+            Letter::E,
+            Letter::B,
+        ][position.rem_euclid(7) as usize];
+        // at most seven sharps and three fifths, so never more than one accidental
+        let accidental = match position.div_euclid(7) {
+            ..0 => Accidental::Flat,
+            0 => Accidental::Natural,
+            _ => Accidental::Sharp,
+        };
+        PitchName::new(letter, accidental)
+    }
+
+    /// Returns the key of the tonic
+    pub const fn tonic(&self) -> Key {
+        self.tonic_name().key()
+    }
+
+    /// Name a key as this key signature would spell it.
+    ///
+    /// Notes outside the key signature are named without an accidental if they can be,
+    /// and otherwise as sharps in sharp keys and C major, or as flats in flat keys.
+    pub const fn spell(&self, key: Key) -> PitchName {
+        PitchName::in_signature(key.pitch_class(), self.sharp_flat_count())
+    }
+}
+
+#[test]
+fn tonics() {
+    let tonic = |count, minor| KeySignature::new(count, minor).tonic_name().to_string();
+    assert_eq!(tonic(0, false), "C");
+    assert_eq!(tonic(-2, false), "Bb");
+    assert_eq!(tonic(-7, false), "Cb");
+    assert_eq!(tonic(7, false), "C#");
+    assert_eq!(tonic(0, true), "A");
+    assert_eq!(tonic(3, true), "F#");
+    assert_eq!(tonic(-6, true), "Eb");
+    assert_eq!(tonic(7, true), "A#");
+
+    // files are read without checking the count
+    let unchecked = |count: i8, minor| KeySignature::new_from_bytes([count as u8, minor as u8]);
+    assert_eq!(unchecked(100, false).tonic_name().to_string(), "C#");
+    assert_eq!(unchecked(125, true).tonic_name().to_string(), "A#");
+    assert_eq!(unchecked(-128, true).tonic(), Key::GSharp);
+    assert_eq!(
+        unchecked(-100, false).scale(),
+        KeySignature::new(-7, false).scale()
+    );
+}
+
+#[test]
+fn signature_spelling() {
+    let spell = |count, key| KeySignature::new(count, false).spell(key).to_string();
+    assert_eq!(spell(-1, Key::ASharp), "Bb");
+    assert_eq!(spell(0, Key::ASharp), "A#");
+    assert_eq!(spell(-6, Key::B), "Cb");
+    assert_eq!(spell(7, Key::F), "E#");
+    // outside the key of D major
+    assert_eq!(spell(2, Key::F), "F");
+    assert_eq!(spell(2, Key::GSharp), "G#");
+    assert_eq!(spell(-3, Key::FSharp), "Gb");
+}
@@ -0,0 +1,436 @@
+use core::str::FromStr;
+
+use crate::prelude::*;
+
+#[cfg(test)]
+use alloc::string::ToString;
+
+impl PitchName {
+    /// Read a name from the start of some text, returning it and the bytes read
+    pub(crate) fn parse_prefix(text: &str) -> Result<(Self, usize), SymbolError> {
+        let mut chars = text.chars();
+        let letter = match chars.next() {
+            None => return Err(SymbolError::Empty),
+            Some('C' | 'c') => Letter::C,
+            Some('D' | 'd') => Letter::D,
+            Some('E' | 'e') => Letter::E,
+            Some('F' | 'f') => Letter::F,
+            Some('G' | 'g') => Letter::G,
+            Some('A' | 'a') => Letter::A,
+            Some('B' | 'b') => Letter::B,
+            Some(other) => return Err(SymbolError::Letter(other)),
+        };
+        let (accidental, read) =
+            Accidental::parse_prefix(chars.as_str()).map_err(|error| error.after(1))?;
+        Ok((PitchName::new(letter, accidental), read + 1))
+    }
+}
+
+impl Accidental {
+    /// Read up to two sharps or flats, or a double sharp, from the start of some text,
+    /// returning them and the bytes read
+    pub(crate) fn parse_prefix(text: &str) -> Result<(Self, usize), SymbolError> {
+        let mut read = 0;
+        let mut semitones = 0;
+        for char in text.chars().take(2) {
+            let step = match char {
+                '#' | '♯' => 1,
+                'b' | '♭' => -1,
+                'x' | '𝄪' if read == 0 => 2,
+                _ => break,
+            };
+            // a sharp and a flat don't mix
+            if semitones * step < 0 {
+                break;
+            }
+            semitones += step;
+            read += char.len_utf8();
+        }
+        let accidental =
+            Accidental::from_semitones(semitones).ok_or(SymbolError::Unexpected(read))?;
+        Ok((accidental, read))
+    }
+}
+
+impl SymbolError {
+    /// Move the position of an error past some bytes read before it
+    pub(crate) const fn after(self, read: usize) -> Self {
+        match self {
+            SymbolError::Unexpected(at) => SymbolError::Unexpected(at + read),
+            other => other,
+        }
+    }
+}
+
+impl FromStr for PitchName {
+    type Err = SymbolError;
+    /// Reads a letter, upper or lower case, followed by up to two sharps (`#`) or flats (`b`),
+    /// or a double sharp (`x`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, read) = PitchName::parse_prefix(s)?;
+        if read != s.len() {
+            return Err(SymbolError::Unexpected(read));
+        }
+        Ok(name)
+    }
+}
+
+/// The chord tones named by a chord symbol before its number
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Major,
+    /// `maj`, which only changes a seventh chord
+    MajorSeventh,
+    Minor,
+    MinorMajor,
+    Diminished,
+    Augmented,
+    HalfDiminished,
+}
+
+/// The parts of a chord symbol after its root, as they are read
+struct Symbol {
+    family: Option<Family>,
+    number: Option<u8>,
+    sus: Option<u8>,
+    flat_five: bool,
+    sharp_five: bool,
+    extensions: u8,
+}
+
+impl Symbol {
+    fn parse(text: &str, offset: usize) -> Result<Self, SymbolError> {
+        let mut symbol = Symbol {
+            family: None,
+            number: None,
+            sus: None,
+            flat_five: false,
+            sharp_five: false,
+            extensions: 0,
+        };
+        let mut rest = text;
+        while !rest.is_empty() {
+            let at = offset + text.len() - rest.len();
+            let unexpected = SymbolError::Unexpected(at);
+            if let Some(after) = rest.strip_prefix(['(', ')', ',', ' ']) {
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["maj", "Maj", "MAJ", "Δ", "M"]) {
+                symbol.family = match symbol.family {
+                    None => Some(Family::MajorSeventh),
+                    Some(Family::Minor) => Some(Family::MinorMajor),
+                    _ => return Err(unexpected),
+                };
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["dim", "°", "o"]) {
+                symbol.set_family(Family::Diminished, unexpected)?;
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["aug"]) {
+                symbol.set_family(Family::Augmented, unexpected)?;
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["ø"]) {
+                symbol.set_family(Family::HalfDiminished, unexpected)?;
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["min", "mi", "m"]) {
+                symbol.set_family(Family::Minor, unexpected)?;
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["sus"]) {
+                let (number, after) = digits(after);
+                symbol.sus = match number {
+                    None | Some(4) => Some(4),
+                    Some(2) => Some(2),
+                    _ => return Err(unexpected),
+                };
+                rest = after;
+            } else if let Some(after) = strip_any(rest, &["add"]) {
+                let (number, after) = digits(after);
+                let extension = match number {
+                    Some(2 | 9) => Extension::Nine,
+                    Some(4 | 11) => Extension::Eleven,
+                    Some(13) => Extension::Thirteen,
+                    _ => return Err(unexpected),
+                };
+                symbol.add(extension);
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('-')
+                && symbol.family.is_none()
+                && symbol.number.is_none()
+            {
+                symbol.set_family(Family::Minor, unexpected)?;
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('+')
+                && !matches!(digits(after).0, Some(5 | 9 | 11))
+            {
+                symbol.set_family(Family::Augmented, unexpected)?;
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix(['b', '♭', '#', '♯', '+', '-']) {
+                let sharp = matches!(rest.chars().next(), Some('#' | '♯' | '+'));
+                match digits(after) {
+                    (Some(5), after) => {
+                        if sharp {
+                            symbol.sharp_five = true;
+                        } else {
+                            symbol.flat_five = true;
+                        }
+                        rest = after;
+                    }
+                    (Some(number), after) => {
+                        let extension = match (sharp, number) {
+                            (false, 9) => Extension::FlatNine,
+                            (true, 9) => Extension::SharpNine,
+                            (true, 11) => Extension::SharpEleven,
+                            (false, 13) => Extension::FlatThirteen,
+                            _ => return Err(unexpected),
+                        };
+                        symbol.add(extension);
+                        rest = after;
+                    }
+                    (None, _) => return Err(unexpected),
+                }
+            } else if let (Some(number), after) = digits(rest) {
+                match (symbol.number, number) {
+                    (None, 5 | 6 | 7 | 9 | 11 | 13) => symbol.number = Some(number),
+                    // 6/9
+                    (None, 69) => {
+                        symbol.number = Some(6);
+                        symbol.add(Extension::Nine);
+                    }
+                    (Some(_), 9) => symbol.add(Extension::Nine),
+                    (Some(_), 11) => symbol.add(Extension::Eleven),
+                    (Some(_), 13) => symbol.add(Extension::Thirteen),
+                    _ => return Err(unexpected),
+                }
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('/')
+                && symbol.number == Some(6)
+            {
+                rest = after;
+            } else {
+                return Err(unexpected);
+            }
+        }
+        Ok(symbol)
+    }
+
+    fn set_family(&mut self, family: Family, error: SymbolError) -> Result<(), SymbolError> {
+        if self.family.is_some() || self.number.is_some() {
+            return Err(error);
+        }
+        self.family = Some(family);
+        Ok(())
+    }
+
+    fn add(&mut self, extension: Extension) {
+        self.extensions |= 1 << extension as u8;
+    }
+
+    /// The quality and any extensions the symbol's number implies
+    fn quality(&self) -> Result<(ChordQuality, &'static [Extension]), SymbolError> {
+        use ChordQuality::*;
+        use Family::*;
+        let family = match (self.family.unwrap_or(Family::Major), self.sharp_five) {
+            (Family::Major, true) => Family::Augmented,
+            (Family::Augmented, _) | (_, false) => self.family.unwrap_or(Family::Major),
+            _ => return Err(SymbolError::Incompatible),
+        };
+        let family = match (family, self.flat_five) {
+            (Family::Minor, true) if self.number.is_some() => HalfDiminished,
+            (family, false) => family,
+            (Family::Diminished | HalfDiminished, true) => family,
+            _ => return Err(SymbolError::Incompatible),
+        };
+        let implied: &'static [Extension] = match self.number {
+            Some(9) => &[Extension::Nine],
+            Some(11) => &[Extension::Nine, Extension::Eleven],
+            Some(13) => &[Extension::Nine, Extension::Thirteen],
+            _ => &[],
+        };
+        let seventh = matches!(self.number, Some(7 | 9 | 11 | 13));
+        let quality = match (family, self.number, self.sus) {
+            (Family::Major, None, None) | (MajorSeventh, None, None) => ChordQuality::Major,
+            (Family::Major, None, Some(2)) => Sus2,
+            (Family::Major, None, Some(_)) => Sus4,
+            (Family::Major, Some(5), None) => Power,
+            (Family::Major, Some(6), None) => Major6,
+            (Family::Major, _, None) if seventh => Dominant7,
+            (Family::Major, _, Some(4)) if seventh => Dominant7Sus4,
+            (MajorSeventh, _, None) if seventh => Major7,
+            (Family::Minor, None, None) => ChordQuality::Minor,
+            (Family::Minor, Some(6), None) => Minor6,
+            (Family::Minor, _, None) if seventh => Minor7,
+            (MinorMajor, _, None) if seventh => MinorMajor7,
+            (Family::Diminished, None, None) => ChordQuality::Diminished,
+            (Family::Diminished, _, None) if seventh => Diminished7,
+            (Family::Augmented, None, None) => ChordQuality::Augmented,
+            (Family::Augmented, _, None) if seventh => Augmented7,
+            (HalfDiminished, None, None) => HalfDiminished7,
+            (HalfDiminished, _, None) if seventh => HalfDiminished7,
+            _ => return Err(SymbolError::Incompatible),
+        };
+        Ok((quality, implied))
+    }
+}
+
+/// Strip the first of some prefixes that the text starts with
+fn strip_any<'s>(text: &'s str, prefixes: &[&str]) -> Option<&'s str> {
+    prefixes.iter().find_map(|prefix| text.strip_prefix(prefix))
+}
+
+/// Read a number from the start of the text
+fn digits(text: &str) -> (Option<u8>, &str) {
+    let end = text
+        .find(|char: char| !char.is_ascii_digit())
+        .unwrap_or(text.len());
+    (text[..end].parse().ok(), &text[end..])
+}
+
+#[doc = r#"
+Reads a chord symbol, such as `Cmaj7`, `F#m7b5`, `Bb/D` or `G13sus`.
+
+A symbol is a root, then its quality and extensions, then an optional slash bass.
+Every symbol a [`Chord`] displays can be read back, along with common alternatives:
+
+| Written | Read as |
+|---------|---------|
+| `m`, `mi`, `min`, `-` | minor |
+| `maj`, `M`, `Δ` | a major seventh, when followed by 7, 9, 11 or 13 |
+| `dim`, `o`, `°` | diminished, or a diminished seventh with 7 |
+| `aug`, `+`, `#5` | augmented |
+| `ø`, `m7b5` | half diminished |
+| `sus`, `sus4`, `sus2` | suspended |
+| `9`, `11`, `13` | a seventh chord with a ninth, plus the eleventh or thirteenth |
+| `6/9`, `69` | a sixth chord with a ninth |
+| `add9`, `add2`, `add11`, `add4`, `add13` | added notes |
+| `b9`, `#9`, `#11`, `b13` | alterations, optionally in parentheses |
+
+# Example
+```rust
+# use midix::prelude::*;
+let chord: Chord = "F#m7b5".parse().unwrap();
+assert_eq!(chord.quality(), ChordQuality::HalfDiminished7);
+assert_eq!(chord.root().to_string(), "F#");
+
+let chord: Chord = "G13sus".parse().unwrap();
+assert_eq!(chord.quality(), ChordQuality::Dominant7Sus4);
+assert!(chord.has_extension(Extension::Thirteen));
+assert_eq!(chord.to_string(), "G13sus4");
+
+let chord: Chord = "Bb/D".parse().unwrap();
+assert_eq!(chord.bass().unwrap().key(), Key::D);
+
+assert!("H7".parse::<Chord>().is_err());
+```
+"#]
+impl FromStr for Chord {
+    type Err = SymbolError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (root, read) = PitchName::parse_prefix(s)?;
+        let rest = &s[read..];
+        // a slash followed by a letter is a bass, rather than the slash of 6/9
+        let (symbol, bass) = match rest.rfind('/') {
+            Some(slash)
+                if rest[slash + 1..].starts_with(|char: char| char.is_ascii_alphabetic()) =>
+            {
+                let bass: PitchName = rest[slash + 1..]
+                    .parse()
+                    .map_err(|_| SymbolError::Unexpected(read + slash + 1))?;
+                (&rest[..slash], Some(bass))
+            }
+            _ => (rest, None),
+        };
+        let symbol = Symbol::parse(symbol, read)?;
+        let (quality, implied) = symbol.quality()?;
+        let mut chord = Chord::new(root, quality);
+        for extension in implied.iter().chain(
+            Extension::ALL
+                .iter()
+                .filter(|extension| symbol.extensions & (1 << **extension as u8) != 0),
+        ) {
+            chord = chord.with_extension(*extension);
+        }
+        if let Some(bass) = bass {
+            chord = chord.with_bass(bass);
+        }
+        Ok(chord)
+    }
+}
+
+#[cfg(test)]
+fn read(symbol: &str) -> (ChordQuality, alloc::vec::Vec<Extension>) {
+    let chord: Chord = symbol.parse().unwrap();
+    (chord.quality(), chord.extensions().collect())
+}
+
+#[test]
+fn parse_pitch_names() {
+    let name = |text: &str| text.parse::<PitchName>().map(|name| name.to_string());
+    assert_eq!(name("Bb"), Ok("Bb".to_string()));
+    assert_eq!(name("f#"), Ok("F#".to_string()));
+    assert_eq!(name("Ebb"), Ok("Ebb".to_string()));
+    assert_eq!(name("Gx"), Ok("G##".to_string()));
+    assert_eq!(name("C♯"), Ok("C#".to_string()));
+    assert_eq!(name(""), Err(SymbolError::Empty));
+    assert_eq!(name("H"), Err(SymbolError::Letter('H')));
+    assert_eq!(name("C#b"), Err(SymbolError::Unexpected(2)));
+}
+
+#[test]
+fn parse_chord_symbols() {
+    use ChordQuality::*;
+    use Extension::*;
+    assert_eq!(read("C"), (Major, alloc::vec![]));
+    assert_eq!(read("Cmaj7"), (Major7, alloc::vec![]));
+    assert_eq!(read("CΔ9"), (Major7, alloc::vec![Nine]));
+    assert_eq!(read("C-7"), (Minor7, alloc::vec![]));
+    assert_eq!(read("Cmin"), (Minor, alloc::vec![]));
+    assert_eq!(read("Cm(maj7)"), (MinorMajor7, alloc::vec![]));
+    assert_eq!(read("Cø"), (HalfDiminished7, alloc::vec![]));
+    assert_eq!(read("Co7"), (Diminished7, alloc::vec![]));
+    assert_eq!(read("C+"), (Augmented, alloc::vec![]));
+    assert_eq!(read("C7#5"), (Augmented7, alloc::vec![]));
+    assert_eq!(read("C5"), (Power, alloc::vec![]));
+    assert_eq!(read("Csus2"), (Sus2, alloc::vec![]));
+    assert_eq!(read("C6/9"), (Major6, alloc::vec![Nine]));
+    assert_eq!(read("Cm69"), (Minor6, alloc::vec![Nine]));
+    assert_eq!(read("C11"), (Dominant7, alloc::vec![Nine, Eleven]));
+    assert_eq!(
+        read("C7(b9, #11)"),
+        (Dominant7, alloc::vec![FlatNine, SharpEleven])
+    );
+    assert_eq!(
+        read("C13#11"),
+        (Dominant7, alloc::vec![Nine, SharpEleven, Thirteen])
+    );
+    assert_eq!(read("Cadd2"), (Major, alloc::vec![Nine]));
+    assert_eq!(read("C7add13"), (Dominant7, alloc::vec![Thirteen]));
+
+    for error in ["Cm(maj)", "Cmsus4", "C7b5", "Cmajm7", "C8", "C7x", "C/H"] {
+        assert!(error.parse::<Chord>().is_err(), "{error}");
+    }
+    assert_eq!("Cmsus4".parse::<Chord>(), Err(SymbolError::Incompatible));
+    assert_eq!("C7x".parse::<Chord>(), Err(SymbolError::Unexpected(2)));
+}
+
+#[test]
+fn symbols_round_trip() {
+    let roots = ["C", "F#", "Bb", "Ebb"];
+    let mut chords = alloc::vec::Vec::new();
+    for root in roots {
+        let root: PitchName = root.parse().unwrap();
+        for quality in ChordQuality::ALL {
+            chords.push(Chord::new(root, quality));
+            for extension in Extension::ALL {
+                chords.push(Chord::new(root, quality).with_extension(extension));
+                chords.push(
+                    Chord::new(root, quality)
+                        .with_extension(extension)
+                        .with_extension(Extension::Nine)
+                        .with_bass(PitchName::new(Letter::E, Accidental::Flat)),
+                );
+            }
+        }
+    }
+    for chord in chords {
+        let symbol = chord.to_string();
+        assert_eq!(symbol.parse::<Chord>(), Ok(chord), "{symbol}");
+    }
+}
@@ -0,0 +1,205 @@
+use libm::{exp2, floor, log2};
+
+use crate::prelude::*;
+
+#[doc = r#"
+The frequency of A4, from which every other note is tuned in equal temperament.
+
+Pitches are MIDI note numbers that may have a fraction, so `69.5` is a quarter tone above A4.
+
+# Example
+```rust
+# use midix::prelude::*;
+let orchestra = Tuning::new(442.).unwrap();
+assert_eq!(note!(A, 4).frequency(orchestra), 442.);
+assert!((note!(C, 4).frequency(Tuning::STANDARD) - 261.626).abs() < 0.001);
+
+// a tuner: the nearest note, and how many cents sharp or flat
+let (note, cents) = Note::from_frequency(445., Tuning::STANDARD).unwrap();
+assert_eq!(note, note!(A, 4));
+assert!((cents - 19.56).abs() < 0.01);
+
+// a bend of a whole step up, with the usual bend range of 2 semitones
+let bend = PitchBend::from_semitones(2., 2.);
+assert!((note!(C, 4).bent_pitch(bend, 2.) - 62.).abs() < 0.001);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Tuning {
+    a4: f64,
+}
+
+impl Tuning {
+    /// Concert pitch, with A4 at 440 Hz
+    pub const STANDARD: Self = Self { a4: 440. };
+
+    /// Tune A4 to a frequency in Hz, such as 442 or 432.
+    ///
+    /// Returns `None` if the frequency isn't positive and finite.
+    pub const fn new(a4: f64) -> Option<Self> {
+        if a4 > 0. && a4 < f64::INFINITY {
+            Some(Self { a4 })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the frequency of A4 in Hz
+    pub const fn a4(&self) -> f64 {
+        self.a4
+    }
+
+    /// Returns the frequency in Hz of a pitch
+    pub fn frequency(&self, pitch: f64) -> f64 {
+        self.a4 * exp2((pitch - 69.) / 12.)
+    }
+
+    /// Returns the pitch of a frequency in Hz, or `None` if it isn't positive
+    pub fn pitch(&self, frequency: f64) -> Option<f64> {
+        (frequency > 0. && frequency.is_finite()).then(|| 69. + 12. * log2(frequency / self.a4))
+    }
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl Note {
+    /// Returns the frequency of the note in Hz
+    pub fn frequency(&self, tuning: Tuning) -> f64 {
+        tuning.frequency(self.byte() as f64)
+    }
+
+    /// Returns the note nearest a frequency, with how many cents the frequency is above it,
+    /// from -50 up to, but not including, 50.
+    ///
+    /// Returns `None` if the frequency isn't positive or the note is outside the MIDI range.
+    pub fn from_frequency(frequency: f64, tuning: Tuning) -> Option<(Note, f64)> {
+        let pitch = tuning.pitch(frequency)?;
+        let nearest = floor(pitch + 0.5);
+        if !(0. ..=127.).contains(&nearest) {
+            return None;
+        }
+        let note = Note::from_databyte_unchecked(nearest as u8);
+        Some((note, (pitch - nearest) * 100.))
+    }
+
+    /// Returns the pitch of the note under a pitch bend, for a bend range in semitones
+    pub fn bent_pitch(&self, bend: PitchBend, range: f64) -> f64 {
+        self.byte() as f64 + bend.semitones(range)
+    }
+
+    /// Returns the frequency in Hz of the note under a pitch bend, for a bend range in semitones
+    pub fn bent_frequency(&self, bend: PitchBend, range: f64, tuning: Tuning) -> f64 {
+        tuning.frequency(self.bent_pitch(bend, range))
+    }
+}
+
+impl PitchBend {
+    /// Returns the bend in semitones, for a bend range in semitones.
+    ///
+    /// The usual range is 2 semitones, set on a channel with registered parameter 0.
+    pub fn semitones(self, range: f64) -> f64 {
+        self.as_f64() * range
+    }
+
+    /// Create a bend of a number of semitones, for a bend range in semitones.
+    ///
+    /// The bend is rounded to the nearest step, with halves away from zero,
+    /// and bends beyond the range are clamped.
+    pub fn from_semitones(semitones: f64, range: f64) -> Self {
+        if range == 0. {
+            return Self::from_int(0);
+        }
+        let steps = (semitones / range).clamp(-1., 1.) * 0x2000 as f64;
+        let rounded = if steps < 0. {
+            -floor(0.5 - steps)
+        } else {
+            floor(steps + 0.5)
+        };
+        Self::from_int(rounded as i16)
+    }
+}
+
+#[cfg(test)]
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+#[test]
+fn note_frequencies() {
+    assert!(close(note!(A, 4).frequency(Tuning::STANDARD), 440.));
+    assert!(close(note!(A, 5).frequency(Tuning::default()), 880.));
+    assert!(close(
+        note!(A, 3).frequency(Tuning::new(432.).unwrap()),
+        216.
+    ));
+    for a4 in [0., -440., f64::NAN, f64::INFINITY] {
+        assert_eq!(Tuning::new(a4), None);
+    }
+    assert!(close(
+        Note::from_databyte(0).unwrap().frequency(Tuning::STANDARD),
+        8.175_798_915_643_707
+    ));
+    let tuning = Tuning::new(442.).unwrap();
+    for note in Note::all() {
+        let (found, cents) = Note::from_frequency(note.frequency(tuning), tuning).unwrap();
+        assert_eq!(found, note);
+        assert!(close(cents, 0.));
+    }
+}
+
+#[test]
+fn nearest_note() {
+    let tuning = Tuning::STANDARD;
+    let (note, cents) = Note::from_frequency(tuning.frequency(60.49), tuning).unwrap();
+    assert_eq!(note, note!(C, 4));
+    assert!(close(cents, 49.));
+    let (note, cents) = Note::from_frequency(tuning.frequency(60.5), tuning).unwrap();
+    assert_eq!(note, note!(CSharp, 4));
+    assert!(close(cents, -50.));
+    let (note, cents) = Note::from_frequency(tuning.frequency(-0.3), tuning).unwrap();
+    assert_eq!(note.byte(), 0);
+    assert!(close(cents, -30.));
+
+    assert_eq!(Note::from_frequency(0., tuning), None);
+    assert_eq!(Note::from_frequency(-440., tuning), None);
+    assert_eq!(Note::from_frequency(f64::NAN, tuning), None);
+    assert_eq!(Note::from_frequency(20_000., tuning), None);
+    assert_eq!(Note::from_frequency(tuning.frequency(-0.6), tuning), None);
+}
+
+#[test]
+fn pitch_bends() {
+    let none = PitchBend::from_int(0);
+    assert!(close(none.semitones(2.), 0.));
+    assert!(close(PitchBend::from_int(-0x2000).semitones(12.), -12.));
+    assert!(close(PitchBend::from_semitones(-1., 2.).semitones(2.), -1.));
+    assert_eq!(
+        PitchBend::from_semitones(5., 2.),
+        PitchBend::from_int(0x1FFF)
+    );
+    assert_eq!(PitchBend::from_semitones(1., 0.), none);
+    // a step is 1/4096 of a semitone with a range of 2
+    assert_eq!(PitchBend::from_semitones(0.0002, 2.).as_int(), 1);
+    assert_eq!(PitchBend::from_semitones(-0.0002, 2.).as_int(), -1);
+    assert_eq!(PitchBend::from_semitones(0.0001, 2.), none);
+    assert_eq!(PitchBend::from_semitones(-0.0001, 2.), none);
+    assert_eq!(
+        PitchBend::from_semitones(-2., 2.),
+        PitchBend::from_int(-0x2000)
+    );
+
+    let bend = PitchBend::from_semitones(-0.5, 2.);
+    assert!(close(note!(A, 4).bent_pitch(bend, 2.), 68.5));
+    assert!(close(
+        note!(A, 4).bent_frequency(bend, 2., Tuning::STANDARD),
+        Tuning::STANDARD.frequency(68.5)
+    ));
+    assert!(close(
+        note!(A, 4).bent_frequency(none, 2., Tuning::STANDARD),
+        440.
+    ));
+}
@@ -0,0 +1,243 @@
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+/// How the notes of a [`Chord`] are arranged above its lowest note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Voicing {
+    /// Root position, each note the nearest above the one before it, with extensions on top
+    #[default]
+    Close,
+    /// A close voicing with its second highest note moved down an octave
+    Drop2,
+    /// A close voicing starting from a chord tone other than the root,
+    /// so `Inversion(1)` has the third in the bass
+    Inversion(u8),
+    /// Every note of the chord, in every octave, across a number of octaves above the bass
+    Spread(u8),
+}
+
+#[doc = r#"
+The notes of a [`Chord`] voiced from a lowest note, ready to play.
+
+# Example
+```rust
+# use midix::prelude::*;
+let chord: Chord = "Cmaj7".parse().unwrap();
+
+let close = chord.voice(Voicing::Close, note!(C, 4));
+assert_eq!(close.notes(), [note!(C, 4), note!(E, 4), note!(G, 4), note!(B, 4)]);
+
+let drop2 = chord.voice(Voicing::Drop2, note!(G, 3));
+assert_eq!(drop2.notes(), [note!(G, 3), note!(C, 4), note!(E, 4), note!(B, 4)]);
+
+// write the chord into a track for a bar
+let mut track = Track::new(Vec::new());
+close.write(&mut track, Channel::One, 0..384, Velocity::new(80).unwrap());
+assert_eq!(track.len(), 8);
+```
+"#]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VoicedChord {
+    notes: Vec<Note>,
+}
+
+impl VoicedChord {
+    /// Returns the notes, from the lowest
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Returns the notes, from the lowest
+    pub fn into_notes(self) -> Vec<Note> {
+        self.notes
+    }
+
+    /// Returns a note on for every note, from the lowest
+    pub fn note_ons(&self, velocity: Velocity) -> impl Iterator<Item = VoiceEvent> + '_ {
+        self.notes
+            .iter()
+            .map(move |note| VoiceEvent::note_on(*note, velocity))
+    }
+
+    /// Returns a note off for every note, from the lowest
+    pub fn note_offs(&self) -> impl Iterator<Item = VoiceEvent> + '_ {
+        self.notes
+            .iter()
+            .map(|note| VoiceEvent::note_off(*note, Velocity::ZERO))
+    }
+
+    /// Play the notes in a track on a channel, from the start of `ticks` until its end
+    pub fn write(
+        &self,
+        track: &mut Track<'_>,
+        channel: Channel,
+        ticks: Range<u32>,
+        velocity: Velocity,
+    ) {
+        for on in self.note_ons(velocity) {
+            track.insert(ticks.start, channel.send_event(on).into());
+        }
+        for off in self.note_offs() {
+            track.insert(ticks.end, channel.send_event(off).into());
+        }
+    }
+}
+
+impl Chord {
+    /// Voice the chord with its bass at or above `lowest`.
+    ///
+    /// A slash bass is played alone at the bottom, with the rest of the chord voiced above it.
+    /// Notes that would be above the MIDI range are left out.
+    pub fn voice(&self, voicing: Voicing, lowest: Note) -> VoicedChord {
+        let root = self.root().pitch_class() as i16;
+        let semitones: Vec<i16> = self
+            .intervals()
+            .iter()
+            .map(|interval| interval.semitones() as i16)
+            .collect();
+        let chord_tones = self.quality().intervals().len();
+        let mut notes: Vec<i16> = Vec::new();
+
+        // the first note at or above the lowest with a pitch class
+        let above = |pitch_class: i16, floor: i16| floor + (pitch_class - floor).rem_euclid(12);
+        let floor = lowest.byte() as i16;
+
+        if let Voicing::Spread(octaves) = voicing {
+            let bass = self.bass().map_or(root, |bass| bass.pitch_class() as i16);
+            let bass = above(bass, floor);
+            let top = bass + 12 * octaves.max(1) as i16;
+            notes.extend((bass..=top).filter(|note| {
+                let pitch_class = note.rem_euclid(12);
+                pitch_class == bass.rem_euclid(12)
+                    || semitones
+                        .iter()
+                        .any(|semitone| (root + semitone).rem_euclid(12) == pitch_class)
+            }));
+        } else {
+            let mut floor = floor;
+            if let Some(bass) = self.bass() {
+                let bass = above(bass.pitch_class() as i16, floor);
+                notes.push(bass);
+                floor = bass + 1;
+            }
+            let start = match voicing {
+                Voicing::Inversion(inversion) => inversion as usize % chord_tones,
+                _ => 0,
+            };
+            // chord tones from the inversion, then the rest of them, then extensions
+            let order = (start..chord_tones)
+                .chain(0..start)
+                .chain(chord_tones..semitones.len());
+            let mut upper: Vec<i16> = Vec::new();
+            for index in order {
+                let pitch_class = root + semitones[index];
+                let floor = upper.last().map_or(floor, |last| last + 1);
+                upper.push(above(pitch_class, floor));
+            }
+            if voicing == Voicing::Drop2 && upper.len() >= 2 {
+                let second = upper.len() - 2;
+                upper[second] -= 12;
+                upper.sort_unstable();
+                // keep the voicing above the lowest note, and above a slash bass
+                while upper[0] < floor {
+                    for note in &mut upper {
+                        *note += 12;
+                    }
+                }
+            }
+            notes.extend(upper);
+        }
+
+        let notes = notes
+            .into_iter()
+            .filter(|note| *note <= 127)
+            .map(|note| Note::from_databyte_unchecked(note as u8))
+            .collect();
+        VoicedChord { notes }
+    }
+}
+
+#[cfg(test)]
+fn voiced(symbol: &str, voicing: Voicing, lowest: Note) -> Vec<Note> {
+    symbol
+        .parse::<Chord>()
+        .unwrap()
+        .voice(voicing, lowest)
+        .into_notes()
+}
+
+#[test]
+fn close_and_inverted() {
+    assert_eq!(
+        voiced("C9", Voicing::Close, note!(A, 3)),
+        [
+            note!(C, 4),
+            note!(E, 4),
+            note!(G, 4),
+            note!(ASharp, 4),
+            note!(D, 5)
+        ]
+    );
+    assert_eq!(
+        voiced("Am", Voicing::Inversion(1), note!(C, 4)),
+        [note!(C, 4), note!(E, 4), note!(A, 4)]
+    );
+    assert_eq!(
+        voiced("G7", Voicing::Inversion(3), note!(C, 4)),
+        [note!(F, 4), note!(G, 4), note!(B, 4), note!(D, 5)]
+    );
+    assert_eq!(
+        voiced("Bb/D", Voicing::Close, note!(C, 3)),
+        [note!(D, 3), note!(ASharp, 3), note!(D, 4), note!(F, 4)]
+    );
+}
+
+#[test]
+fn drop2_and_spread() {
+    // the dropped note moves the voicing up, to stay above the lowest note
+    assert_eq!(
+        voiced("Dm7", Voicing::Drop2, note!(D, 3)),
+        [note!(A, 3), note!(D, 4), note!(F, 4), note!(C, 5)]
+    );
+    assert_eq!(
+        voiced("Dm7", Voicing::Drop2, note!(A, 2)),
+        [note!(A, 2), note!(D, 3), note!(F, 3), note!(C, 4)]
+    );
+    assert_eq!(
+        voiced("C/E", Voicing::Drop2, note!(C, 3)),
+        [note!(E, 3), note!(E, 4), note!(C, 5), note!(G, 5)]
+    );
+    assert_eq!(
+        voiced("C5", Voicing::Spread(2), note!(C, 2)),
+        [
+            note!(C, 2),
+            note!(G, 2),
+            note!(C, 3),
+            note!(G, 3),
+            note!(C, 4)
+        ]
+    );
+    let high = Note::from_databyte(120).unwrap();
+    assert_eq!(
+        voiced("C", Voicing::Close, high),
+        [high, note!(E, 9), note!(G, 9)]
+    );
+    assert_eq!(voiced("D", Voicing::Close, high).len(), 2);
+}
+
+#[test]
+fn voiced_events() {
+    let chord: Chord = "Em".parse().unwrap();
+    let voiced = chord.voice(Voicing::Close, note!(E, 4));
+    let ons: Vec<_> = voiced.note_ons(Velocity::MAX).collect();
+    assert_eq!(ons[0], VoiceEvent::note_on(note!(E, 4), Velocity::MAX));
+    assert_eq!(voiced.note_offs().count(), 3);
+
+    let mut track = Track::new(Vec::new());
+    voiced.write(&mut track, Channel::Two, 96..192, Velocity::MAX);
+    assert_eq!(track.notes_at(96), [note!(E, 4), note!(G, 4), note!(B, 4)]);
+    assert_eq!(track.notes_at(192), []);
+}
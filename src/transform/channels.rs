@@ -0,0 +1,514 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+#[doc = r#"
+Routes each of the 16 channels to any set of channels.
+
+A channel may be sent to one channel (a remap), several channels (a copy) or none (a mute).
+The default map sends every channel to itself.
+
+Only channel voice messages are routed. Other events pass through unchanged.
+
+# Example
+```rust
+# use midix::prelude::*;
+let map = ChannelMap::new()
+    // our synth plays drums on channel 16
+    .set(Channel::Ten, Channel::Sixteen)
+    // double the melody on another patch
+    .add(Channel::One, Channel::Two)
+    .mute(Channel::Three);
+
+let mut track = Track::new(Vec::new());
+for channel in [Channel::One, Channel::Three, Channel::Ten] {
+    track.insert(0, channel.send_event(VoiceEvent::note_on(note!(C, 4), Velocity::MAX)).into());
+}
+map.apply_to_track(&mut track);
+
+let channels: Vec<_> = track
+    .events()
+    .iter()
+    .map(|e| e.event().channel_voice().unwrap().channel())
+    .collect();
+assert_eq!(channels, [Channel::One, Channel::Two, Channel::Sixteen]);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelMap {
+    /// For each source channel, one bit per destination channel
+    routes: [u16; 16],
+}
+
+impl Default for ChannelMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelMap {
+    /// A map that sends every channel to itself
+    pub const fn new() -> Self {
+        let mut routes = [0; 16];
+        let mut channel = 0;
+        while channel < 16 {
+            routes[channel] = 1 << channel;
+            channel += 1;
+        }
+        Self { routes }
+    }
+
+    /// A map that sends every channel nowhere
+    pub const fn empty() -> Self {
+        Self { routes: [0; 16] }
+    }
+
+    /// A map that sends channel `n` to `table[n]`
+    pub const fn from_table(table: [Channel; 16]) -> Self {
+        let mut routes = [0; 16];
+        let mut channel = 0;
+        while channel < 16 {
+            routes[channel] = 1 << table[channel] as u8;
+            channel += 1;
+        }
+        Self { routes }
+    }
+
+    /// Send `from` to `to` only
+    pub const fn set(mut self, from: Channel, to: Channel) -> Self {
+        self.routes[from as usize] = 1 << to as u8;
+        self
+    }
+
+    /// Also send `from` to `to`
+    pub const fn add(mut self, from: Channel, to: Channel) -> Self {
+        self.routes[from as usize] |= 1 << to as u8;
+        self
+    }
+
+    /// Send `from` nowhere
+    pub const fn mute(mut self, from: Channel) -> Self {
+        self.routes[from as usize] = 0;
+        self
+    }
+
+    /// Returns true if events on `from` are sent to `to`
+    pub const fn routes(&self, from: Channel, to: Channel) -> bool {
+        self.routes[from as usize] & (1 << to as u8) != 0
+    }
+
+    /// Returns every channel that events on `from` are sent to, in order
+    pub fn destinations(&self, from: Channel) -> impl Iterator<Item = Channel> + use<> {
+        let routes = self.routes[from as usize];
+        Channel::all()
+            .into_iter()
+            .filter(move |to| routes & (1 << *to as u8) != 0)
+    }
+
+    /// Returns the routed copies of a message
+    pub fn route(
+        &self,
+        msg: ChannelVoiceMessage,
+    ) -> impl Iterator<Item = ChannelVoiceMessage> + use<> {
+        self.destinations(msg.channel())
+            .map(move |channel| channel.send_event(msg.event))
+    }
+
+    /// Route every event of a track.
+    ///
+    /// Copies of an event are placed together, in channel order.
+    pub fn apply_to_track(&self, track: &mut Track<'_>) {
+        let events = core::mem::take(&mut track.events);
+        track.events = self.apply_to_ticked(events).collect();
+    }
+
+    /// Route every event of a file
+    pub fn apply_to_file(&self, file: &mut MidiFile<'_>) {
+        for track in file.tracks_mut() {
+            self.apply_to_track(track);
+        }
+    }
+
+    /// Route a stream of events, such as the output of [`MidiFile::into_events`]
+    pub fn apply_to_events<'a, I>(self, events: I) -> impl Iterator<Item = Timed<LiveEvent<'a>>>
+    where
+        I: IntoIterator<Item = Timed<LiveEvent<'a>>>,
+    {
+        events.into_iter().flat_map(move |timed| {
+            self.route_event(timed.event)
+                .map(move |event| Timed::new(timed.timestamp, event))
+        })
+    }
+
+    fn apply_to_ticked<'a>(
+        &self,
+        events: Vec<Ticked<LiveEvent<'a>>>,
+    ) -> impl Iterator<Item = Ticked<LiveEvent<'a>>> {
+        events.into_iter().flat_map(move |ticked| {
+            let tick = ticked.accumulated_ticks();
+            self.route_event(ticked.into_event())
+                .map(move |event| Ticked::new(tick, event))
+        })
+    }
+
+    fn route_event<'a>(
+        &self,
+        event: LiveEvent<'a>,
+    ) -> impl Iterator<Item = LiveEvent<'a>> + use<'a> {
+        let (routed, other) = match event {
+            LiveEvent::ChannelVoice(msg) => (Some(self.route(msg)), None),
+            other => (None, Some(other)),
+        };
+        routed
+            .into_iter()
+            .flatten()
+            .map(LiveEvent::ChannelVoice)
+            .chain(other)
+    }
+}
+
+/// The kind of an event, used by an [`EventFilter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// [`VoiceEvent::NoteOff`]
+    NoteOff,
+    /// [`VoiceEvent::NoteOn`], including those with a velocity of zero
+    NoteOn,
+    /// [`VoiceEvent::Aftertouch`]
+    Aftertouch,
+    /// [`VoiceEvent::ControlChange`]
+    ControlChange,
+    /// [`VoiceEvent::ProgramChange`]
+    ProgramChange,
+    /// [`VoiceEvent::ChannelPressureAfterTouch`]
+    ChannelPressure,
+    /// [`VoiceEvent::PitchBend`]
+    PitchBend,
+    /// [`ChannelModeMessage`]
+    ChannelMode,
+    /// [`SystemExclusiveMessage`]
+    SystemExclusive,
+    /// Any other [`SystemCommonMessage`]
+    SystemCommon,
+    /// [`SystemRealTimeMessage`]
+    SystemRealTime,
+}
+
+impl EventKind {
+    /// Returns the kind of a voice event
+    pub const fn of_voice(event: &VoiceEvent) -> Self {
+        match event {
+            VoiceEvent::NoteOff { .. } => Self::NoteOff,
+            VoiceEvent::NoteOn { .. } => Self::NoteOn,
+            VoiceEvent::Aftertouch { .. } => Self::Aftertouch,
+            VoiceEvent::ControlChange(_) => Self::ControlChange,
+            VoiceEvent::ProgramChange { .. } => Self::ProgramChange,
+            VoiceEvent::ChannelPressureAfterTouch { .. } => Self::ChannelPressure,
+            VoiceEvent::PitchBend(_) => Self::PitchBend,
+        }
+    }
+
+    /// Returns the kind of a live event
+    pub const fn of_event(event: &LiveEvent<'_>) -> Self {
+        match event {
+            LiveEvent::ChannelVoice(msg) => Self::of_voice(&msg.event),
+            LiveEvent::SysCommon(SystemCommonMessage::SystemExclusive(_)) => Self::SystemExclusive,
+            LiveEvent::SysCommon(_) => Self::SystemCommon,
+            LiveEvent::SysRealTime(_) => Self::SystemRealTime,
+        }
+    }
+
+    /// Returns the kind of a message
+    pub const fn of_message(message: &MidiMessage<'_>) -> Self {
+        match message {
+            MidiMessage::ChannelVoice(msg) => Self::of_voice(&msg.event),
+            MidiMessage::ChannelMode(_) => Self::ChannelMode,
+            MidiMessage::SysExclusive(_)
+            | MidiMessage::SysCommon(SystemCommonMessage::SystemExclusive(_)) => {
+                Self::SystemExclusive
+            }
+            MidiMessage::SysCommon(_) => Self::SystemCommon,
+            MidiMessage::SysRealTime(_) => Self::SystemRealTime,
+        }
+    }
+}
+
+#[doc = r#"
+Keeps or drops events by channel and by kind.
+
+Channels only apply to channel voice messages. Other events are filtered by their kind alone.
+The default filter keeps everything.
+
+# Example
+```rust
+# use midix::prelude::*;
+# use core::convert::Infallible;
+// only notes, and never the drums
+let filter = EventFilter::new()
+    .keep_kinds(&[EventKind::NoteOn, EventKind::NoteOff])
+    .drop_channel(Channel::Ten);
+
+let mut target = Vec::new().filter(move |msg: &MidiMessage<'_>| filter.accepts_message(msg));
+
+let note = VoiceEvent::note_on(note!(C, 4), Velocity::MAX);
+target.handle_event(Channel::One.send_event(note).into()).unwrap();
+target.handle_event(Channel::Ten.send_event(note).into()).unwrap();
+target.handle_event(SystemRealTimeMessage::TimingClock.into()).unwrap();
+
+assert_eq!(target.into_inner().len(), 1);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventFilter {
+    /// One bit per kept channel
+    channels: u16,
+    /// One bit per kept [`EventKind`]
+    kinds: u16,
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventFilter {
+    /// A filter that keeps every event
+    pub const fn new() -> Self {
+        Self {
+            channels: u16::MAX,
+            kinds: u16::MAX,
+        }
+    }
+
+    /// Keep only events on these channels
+    pub fn keep_channels(mut self, channels: &[Channel]) -> Self {
+        self.channels = channels.iter().fold(0, |mask, c| mask | 1 << *c as u8);
+        self
+    }
+
+    /// Drop events on a channel
+    pub const fn drop_channel(mut self, channel: Channel) -> Self {
+        self.channels &= !(1 << channel as u8);
+        self
+    }
+
+    /// Keep only events of these kinds
+    pub fn keep_kinds(mut self, kinds: &[EventKind]) -> Self {
+        self.kinds = kinds.iter().fold(0, |mask, k| mask | 1 << *k as u8);
+        self
+    }
+
+    /// Drop events of a kind
+    pub const fn drop_kind(mut self, kind: EventKind) -> Self {
+        self.kinds &= !(1 << kind as u8);
+        self
+    }
+
+    /// Returns true if events on this channel are kept
+    pub const fn keeps_channel(&self, channel: Channel) -> bool {
+        self.channels & (1 << channel as u8) != 0
+    }
+
+    /// Returns true if events of this kind are kept
+    pub const fn keeps_kind(&self, kind: EventKind) -> bool {
+        self.kinds & (1 << kind as u8) != 0
+    }
+
+    /// Returns true if the event is kept
+    pub fn accepts_event(&self, event: &LiveEvent<'_>) -> bool {
+        let channel = event.channel_voice().map(ChannelVoiceMessage::channel);
+        self.accepts(EventKind::of_event(event), channel)
+    }
+
+    /// Returns true if the message is kept
+    pub fn accepts_message(&self, message: &MidiMessage<'_>) -> bool {
+        let channel = match message {
+            MidiMessage::ChannelVoice(msg) => Some(msg.channel()),
+            _ => None,
+        };
+        self.accepts(EventKind::of_message(message), channel)
+    }
+
+    /// Remove the events a track doesn't keep, returning how many were removed
+    pub fn apply_to_track(&self, track: &mut Track<'_>) -> usize {
+        let len = track.events.len();
+        track.retain(|event| self.accepts_event(event.event()));
+        len - track.events.len()
+    }
+
+    /// Remove the events a file doesn't keep, returning how many were removed
+    pub fn apply_to_file(&self, file: &mut MidiFile<'_>) -> usize {
+        file.tracks_mut()
+            .iter_mut()
+            .map(|track| self.apply_to_track(track))
+            .sum()
+    }
+
+    /// Filter a stream of events, such as the output of [`MidiFile::into_events`]
+    pub fn apply_to_events<'a, I>(self, events: I) -> impl Iterator<Item = Timed<LiveEvent<'a>>>
+    where
+        I: IntoIterator<Item = Timed<LiveEvent<'a>>>,
+    {
+        events
+            .into_iter()
+            .filter(move |timed| self.accepts_event(&timed.event))
+    }
+
+    fn accepts(&self, kind: EventKind, channel: Option<Channel>) -> bool {
+        self.keeps_kind(kind) && channel.is_none_or(|channel| self.keeps_channel(channel))
+    }
+}
+
+impl<'a> Track<'a> {
+    /// Move the events of one channel into new tracks, split by pitch.
+    ///
+    /// Notes below the first split point go into the first track, notes from the first
+    /// split point up to the second go into the second track, and so on, giving one
+    /// more track than there are split points. Empty tracks are still returned.
+    ///
+    /// Events on the channel without a note, such as controllers and pitch bends,
+    /// are copied into every new track, since they affect every note.
+    pub fn split_channel(&mut self, channel: Channel, split_points: &[Note]) -> Vec<Track<'a>> {
+        let mut split_points = split_points.to_vec();
+        split_points.sort();
+
+        let mut parts = alloc::vec![Vec::new(); split_points.len() + 1];
+        self.events.retain(|event| {
+            let Some(msg) = event.event().channel_voice() else {
+                return true;
+            };
+            if msg.channel() != channel {
+                return true;
+            }
+            match msg.note() {
+                Some(note) => {
+                    let part = split_points.partition_point(|point| *point <= note);
+                    parts[part].push(event.clone());
+                }
+                None => parts.iter_mut().for_each(|part| part.push(event.clone())),
+            }
+            false
+        });
+
+        parts
+            .into_iter()
+            .map(|events| {
+                let info = TrackInfo {
+                    channel: Some(channel),
+                    ..Default::default()
+                };
+                Track::from_ticked(info, events)
+            })
+            .collect()
+    }
+}
+
+impl MidiFile<'_> {
+    /// Move the events of one channel of a track into new tracks, split by pitch.
+    ///
+    /// The new tracks are inserted after the track. See [`Track::split_channel`].
+    ///
+    /// # Errors
+    /// - If the file is [`FormatType::SingleMultiChannel`], which can only hold one track
+    /// - If there is no track at `track`
+    pub fn split_channel(
+        &mut self,
+        track: usize,
+        channel: Channel,
+        split_points: &[Note],
+    ) -> Result<(), EditError> {
+        if self.format_type() == FormatType::SingleMultiChannel {
+            return Err(EditError::SingleTrackFormat(split_points.len() + 2));
+        }
+        let parts = self
+            .track_mut(track)
+            .ok_or(EditError::TrackIndex(track))?
+            .split_channel(channel, split_points);
+        for (offset, part) in parts.into_iter().enumerate() {
+            self.insert_track(track + 1 + offset, part)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn route_copies_and_mutes() {
+    let map = ChannelMap::from_table([Channel::Four; 16])
+        .add(Channel::One, Channel::Two)
+        .mute(Channel::Five);
+    let msg = Channel::One.send_event(VoiceEvent::program_change(Program::new(3).unwrap()));
+    let routed: Vec<_> = map.route(msg).map(|m| m.channel()).collect();
+    assert_eq!(routed, [Channel::Two, Channel::Four]);
+
+    let msg = Channel::Five.send_event(VoiceEvent::program_change(Program::new(3).unwrap()));
+    assert_eq!(map.route(msg).count(), 0);
+
+    let events = [
+        Timed::new(0, LiveEvent::from(msg)),
+        Timed::new(1, LiveEvent::from(SystemRealTimeMessage::Stop)),
+    ];
+    let routed: Vec<_> = map.apply_to_events(events).collect();
+    assert_eq!(routed, [Timed::new(1, SystemRealTimeMessage::Stop.into())]);
+}
+
+#[test]
+fn filter_channels_and_kinds() {
+    let mut track = Track::new(Vec::new());
+    let note = VoiceEvent::note_on(note!(C, 4), Velocity::MAX);
+    track.insert(0, Channel::One.send_event(note).into());
+    track.insert(0, Channel::Two.send_event(note).into());
+    track.insert(
+        0,
+        Channel::One
+            .send_event(VoiceEvent::pitch_bend(PitchBend::new_unchecked(0, 0x40)))
+            .into(),
+    );
+    track.insert(0, SystemExclusiveMessage::new(alloc::vec![0x7E]).into());
+
+    let removed = EventFilter::new()
+        .keep_channels(&[Channel::One])
+        .drop_kind(EventKind::PitchBend)
+        .apply_to_track(&mut track);
+    assert_eq!(removed, 2);
+    assert_eq!(
+        track
+            .events()
+            .iter()
+            .map(|e| EventKind::of_event(e.event()))
+            .collect::<Vec<_>>(),
+        [EventKind::NoteOn, EventKind::SystemExclusive]
+    );
+}
+
+#[test]
+fn split_channel_by_pitch() {
+    let mut track = Track::new(Vec::new());
+    for note in [note!(C, 2), note!(C, 4), note!(C, 5)] {
+        let on = VoiceEvent::note_on(note, Velocity::MAX);
+        track.insert(0, Channel::One.send_event(on).into());
+    }
+    let sustain =
+        VoiceEvent::control_change(Controller::damper_pedal(DataByte::new_unchecked(127)));
+    track.insert(0, Channel::One.send_event(sustain).into());
+    track.insert(
+        0,
+        Channel::Two
+            .send_event(VoiceEvent::note_on(note!(C, 3), Velocity::MAX))
+            .into(),
+    );
+
+    let mut file = MidiFile::new(
+        Format::Simultaneous(alloc::vec![track]),
+        Timing::new_ticks_per_quarter_note(96),
+    );
+    file.split_channel(0, Channel::One, &[note!(C, 4)]).unwrap();
+
+    let tracks = file.tracks();
+    assert_eq!(tracks.len(), 3);
+    // channel two is left behind
+    assert_eq!(tracks[0].events().len(), 1);
+    // the pedal is copied to both hands
+    assert_eq!(tracks[1].events().len(), 2);
+    assert_eq!(tracks[2].events().len(), 3);
+}
@@ -0,0 +1,322 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+use super::{note_spans, retime};
+
+/// The feel of one grid step of a [`Groove`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GrooveSlot {
+    /// How far notes on this step are played from the grid, in the groove's ticks.
+    ///
+    /// Negative values are early, positive values are late.
+    pub offset: i32,
+    /// How much louder (or quieter, if negative) notes on this step are played,
+    /// compared to the groove's average velocity
+    pub accent: i16,
+}
+
+#[doc = r#"
+A timing and velocity template, such as the feel of a reference drum loop.
+
+A groove holds one [`GrooveSlot`] for each step of a grid over a repeating length,
+usually a bar. It is extracted from a track with [`Groove::extract`] and
+applied to other tracks with [`Groove::apply_to_track`].
+
+Offsets are stored in the ticks of the reference track's resolution,
+and are rescaled when the groove is applied at another resolution.
+
+# Example
+```rust
+# use midix::prelude::*;
+fn hat(track: &mut Track, tick: u32, velocity: u8) {
+    let note = VoiceEvent::note_on(note!(FSharp, 2), Velocity::new(velocity).unwrap());
+    track.insert(tick, Channel::Ten.send_event(note).into());
+}
+
+// a swung hi-hat loop at 96 ticks per quarter note: every second eighth is late and soft
+let mut reference = Track::new(Vec::new());
+for beat in 0..4 {
+    hat(&mut reference, beat * 96, 100);
+    hat(&mut reference, beat * 96 + 60, 60);
+}
+let groove = Groove::extract(&reference, 96, Grid::EIGHTH, 8);
+assert_eq!(groove.slots()[1], GrooveSlot { offset: 12, accent: -20 });
+
+// a straight part, at a higher resolution
+let mut part = Track::new(Vec::new());
+hat(&mut part, 480, 80);
+hat(&mut part, 720, 80);
+groove.apply_to_track(&mut part, 480, 100);
+
+let events: Vec<_> = part
+    .events()
+    .iter()
+    .map(|e| (e.accumulated_ticks(), e.event().channel_voice().unwrap().velocity().unwrap().byte()))
+    .collect();
+assert_eq!(events, [(480, 100), (780, 60)]);
+```
+"#]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Groove {
+    grid: Grid,
+    ticks_per_quarter_note: u16,
+    slots: Vec<GrooveSlot>,
+}
+
+impl Groove {
+    /// Create a groove from its slots.
+    ///
+    /// Offsets are in ticks at `ticks_per_quarter_note`.
+    pub fn new(grid: Grid, ticks_per_quarter_note: u16, slots: Vec<GrooveSlot>) -> Self {
+        Self {
+            grid,
+            ticks_per_quarter_note,
+            slots,
+        }
+    }
+
+    /// Capture the feel of a track.
+    ///
+    /// Every note is assigned to the nearest line of `grid`, repeating every `steps` lines.
+    /// Each slot holds the average offset and accent of its notes.
+    /// Slots without any notes are left straight.
+    pub fn extract(
+        track: &Track<'_>,
+        ticks_per_quarter_note: u16,
+        grid: Grid,
+        steps: usize,
+    ) -> Self {
+        let step = grid.ticks(ticks_per_quarter_note);
+        let steps = steps.max(1);
+
+        // total offset, total velocity and note count of each slot
+        let mut totals = alloc::vec![(0i64, 0i64, 0i64); steps];
+        let events = track.events();
+        for span in note_spans(events) {
+            let event = &events[span.on];
+            let tick = event.accumulated_ticks() as i64;
+            let line = (tick + step as i64 / 2) / step as i64;
+            let velocity = velocity_of(event.event()).map_or(0, |v| v.byte()) as i64;
+
+            let total = &mut totals[line as usize % steps];
+            total.0 += tick - line * step as i64;
+            total.1 += velocity;
+            total.2 += 1;
+        }
+
+        let (velocity_sum, count) = totals
+            .iter()
+            .fold((0, 0), |(v, c), total| (v + total.1, c + total.2));
+        let mean_velocity = if count == 0 { 0 } else { velocity_sum / count };
+
+        let slots = totals
+            .into_iter()
+            .map(|(offset, velocity, count)| {
+                if count == 0 {
+                    return GrooveSlot::default();
+                }
+                GrooveSlot {
+                    offset: div_round(offset, count) as i32,
+                    accent: (div_round(velocity, count) - mean_velocity) as i16,
+                }
+            })
+            .collect();
+
+        Self {
+            grid,
+            ticks_per_quarter_note,
+            slots,
+        }
+    }
+
+    /// Returns the grid of the groove
+    pub fn grid(&self) -> Grid {
+        self.grid
+    }
+
+    /// Returns the resolution the groove's offsets are measured in
+    pub fn ticks_per_quarter_note(&self) -> u16 {
+        self.ticks_per_quarter_note
+    }
+
+    /// Returns the feel of each grid step
+    pub fn slots(&self) -> &[GrooveSlot] {
+        &self.slots
+    }
+
+    /// Returns a mutable reference to the feel of each grid step
+    pub fn slots_mut(&mut self) -> &mut Vec<GrooveSlot> {
+        &mut self.slots
+    }
+
+    /// Move notes towards the groove's timing and accents.
+    ///
+    /// `strength` is a percentage: 0% leaves the track unchanged, 100% applies the groove fully.
+    /// Note offs move with their note ons, keeping note lengths.
+    pub fn apply_to_track(&self, track: &mut Track<'_>, ticks_per_quarter_note: u16, strength: u8) {
+        if self.slots.is_empty() {
+            return;
+        }
+        let strength = strength.min(100) as i64;
+        let step = self.grid.ticks(ticks_per_quarter_note) as i64;
+
+        let mut ticks: Vec<u32> = track
+            .events()
+            .iter()
+            .map(Ticked::accumulated_ticks)
+            .collect();
+
+        for span in note_spans(track.events()) {
+            let tick = ticks[span.on] as i64;
+            let line = (tick + step / 2) / step;
+            let slot = self.slots[line as usize % self.slots.len()];
+
+            let offset = div_round(
+                slot.offset as i64 * ticks_per_quarter_note as i64,
+                self.ticks_per_quarter_note.max(1) as i64,
+            );
+            let target = line * step + offset;
+            let shift = div_round((target - tick) * strength, 100).max(-tick);
+
+            ticks[span.on] = (tick + shift).clamp(0, u32::MAX as i64) as u32;
+            if let Some(off) = span.off {
+                ticks[off] = (ticks[off] as i64 + shift).clamp(0, u32::MAX as i64) as u32;
+            }
+
+            if let Some(velocity) = track.events[span.on]
+                .event_mut()
+                .channel_voice_mut()
+                .and_then(|msg| msg.event.velocity_mut())
+            {
+                let accent = div_round(slot.accent as i64 * strength, 100);
+                let accented = (velocity.byte() as i64 + accent).clamp(1, 127);
+                *velocity = Velocity::new_unchecked(accented as u8);
+            }
+        }
+
+        retime(track, &ticks);
+    }
+
+    /// Apply the groove to every track of a file, using the file's resolution
+    ///
+    /// # Errors
+    /// If the file is timed in SMPTE frames
+    pub fn apply_to_file(&self, file: &mut MidiFile<'_>, strength: u8) -> Result<(), EditError> {
+        let ticks_per_quarter_note = file
+            .timing()
+            .ticks_per_quarter_note()
+            .ok_or(EditError::SmpteTiming)?;
+        for track in file.tracks_mut() {
+            self.apply_to_track(track, ticks_per_quarter_note, strength);
+        }
+        Ok(())
+    }
+}
+
+fn velocity_of(event: &LiveEvent<'_>) -> Option<Velocity> {
+    event.channel_voice()?.event.velocity()
+}
+
+/// Divide, rounding halves away from zero
+fn div_round(value: i64, by: i64) -> i64 {
+    if (value < 0) == (by < 0) {
+        (value + by / 2) / by
+    } else {
+        (value - by / 2) / by
+    }
+}
+
+#[cfg(test)]
+fn groove_track(notes: &[(u32, u8)]) -> Track<'static> {
+    let mut track = Track::new(Vec::new());
+    for (tick, velocity) in notes {
+        let on = VoiceEvent::note_on(note!(D, 2), Velocity::new(*velocity).unwrap());
+        let off = VoiceEvent::note_off(note!(D, 2), Velocity::ZERO);
+        track.insert(*tick, Channel::Ten.send_event(on).into());
+        track.insert(*tick + 10, Channel::Ten.send_event(off).into());
+    }
+    track
+}
+
+#[test]
+fn extract_averages_slots() {
+    // two bars of quarter notes at 96 ticks per quarter note
+    let track = groove_track(&[
+        (0, 100),
+        (98, 60),
+        (190, 90),
+        (288, 70),
+        (384, 110),
+        (486, 60),
+        (674, 70),
+    ]);
+    let groove = Groove::extract(&track, 96, Grid::QUARTER, 4);
+    assert_eq!(
+        groove.slots(),
+        [
+            GrooveSlot {
+                offset: 0,
+                accent: 25
+            },
+            GrooveSlot {
+                offset: 4,
+                accent: -20
+            },
+            GrooveSlot {
+                offset: -2,
+                accent: 10
+            },
+            GrooveSlot {
+                offset: 1,
+                accent: -10
+            },
+        ]
+    );
+}
+
+#[test]
+fn strength_scales_groove() {
+    let groove = Groove::new(
+        Grid::EIGHTH,
+        96,
+        alloc::vec![
+            GrooveSlot::default(),
+            GrooveSlot {
+                offset: 20,
+                accent: -40
+            }
+        ],
+    );
+    let mut track = groove_track(&[(48, 100), (96, 100)]);
+    groove.apply_to_track(&mut track, 96, 50);
+
+    let events: Vec<_> = track
+        .events()
+        .iter()
+        .map(|e| {
+            let msg = e.event().channel_voice().unwrap();
+            (e.accumulated_ticks(), msg.velocity().unwrap().byte())
+        })
+        .collect();
+    assert_eq!(events, [(58, 80), (68, 0), (96, 100), (106, 0)]);
+}
+
+#[test]
+fn notes_at_the_last_tick() {
+    let late = GrooveSlot {
+        offset: 40,
+        accent: 0,
+    };
+    let groove = Groove::new(Grid::EIGHTH, 96, alloc::vec![late, late]);
+    let mut track = groove_track(&[(u32::MAX - 10, 100)]);
+    groove.apply_to_track(&mut track, 96, 100);
+
+    // the note is held at the last tick, instead of wrapping around to the start
+    let ticks: Vec<_> = track
+        .events()
+        .iter()
+        .map(Ticked::accumulated_ticks)
+        .collect();
+    assert_eq!(ticks, [u32::MAX, u32::MAX]);
+}
@@ -0,0 +1,231 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+use super::{note_spans, retime};
+use crate::utils::SplitMix64;
+
+#[doc = r#"
+Adds small random variations to the timing, velocity and length of notes.
+
+Randomness comes from a seeded generator, so the same seed applied to the same
+track always gives the same result.
+
+Notes are never moved before tick 0, and never shortened to less than one tick.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+for tick in [0, 96, 192, 288] {
+    let on = VoiceEvent::note_on(note!(C, 4), Velocity::new(80).unwrap());
+    let off = VoiceEvent::note_off(note!(C, 4), Velocity::ZERO);
+    track.insert(tick, Channel::One.send_event(on).into());
+    track.insert(tick + 48, Channel::One.send_event(off).into());
+}
+let mut copy = track.clone();
+
+let humanize = Humanize::new(7).with_timing(4).with_velocity(10).with_length(6);
+humanize.clone().apply_to_track(&mut track);
+humanize.clone().apply_to_track(&mut copy);
+
+// reproducible
+assert_eq!(track, copy);
+for event in track.events() {
+    let msg = event.event().channel_voice().unwrap();
+    if msg.is_note_on().is_some() {
+        let velocity = msg.velocity().unwrap().byte();
+        assert!((70..=90).contains(&velocity));
+    }
+}
+```
+"#]
+#[derive(Debug, Clone)]
+pub struct Humanize {
+    rng: SplitMix64,
+    timing: u32,
+    velocity: u8,
+    length: u32,
+    chord_order: bool,
+}
+
+impl Humanize {
+    /// Create a humanizer that changes nothing until ranges are set
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix64(seed),
+            timing: 0,
+            velocity: 0,
+            length: 0,
+            chord_order: false,
+        }
+    }
+
+    /// Move note starts by up to `ticks` earlier or later.
+    ///
+    /// Note offs move with their note ons.
+    pub const fn with_timing(mut self, ticks: u32) -> Self {
+        self.timing = ticks;
+        self
+    }
+
+    /// Change note on velocities by up to `amount` softer or louder
+    pub const fn with_velocity(mut self, amount: u8) -> Self {
+        self.velocity = amount;
+        self
+    }
+
+    /// Make notes up to `ticks` shorter or longer
+    pub const fn with_length(mut self, ticks: u32) -> Self {
+        self.length = ticks;
+        self
+    }
+
+    /// Keep the order notes of a chord start in.
+    ///
+    /// Notes that start on the same tick are still moved by different amounts,
+    /// but never past one another.
+    pub const fn with_chord_order(mut self, keep: bool) -> Self {
+        self.chord_order = keep;
+        self
+    }
+
+    /// Humanize the notes of a track.
+    ///
+    /// Each call continues the random sequence, so humanizing several tracks
+    /// with one `Humanize` gives each track a different feel.
+    pub fn apply_to_track(&mut self, track: &mut Track<'_>) {
+        let mut ticks: Vec<u32> = track
+            .events()
+            .iter()
+            .map(Ticked::accumulated_ticks)
+            .collect();
+        let spans = note_spans(track.events());
+
+        let mut shifts: Vec<i64> = spans.iter().map(|_| self.rng.spread(self.timing)).collect();
+        if self.chord_order {
+            // spans are in note on order, so chords are runs of spans on one tick
+            let mut start = 0;
+            while start < spans.len() {
+                let tick = ticks[spans[start].on];
+                let end = start
+                    + spans[start..]
+                        .iter()
+                        .take_while(|span| ticks[span.on] == tick)
+                        .count();
+                shifts[start..end].sort_unstable();
+                start = end;
+            }
+        }
+
+        for (span, shift) in spans.iter().zip(shifts) {
+            let start = ticks[span.on] as i64;
+            let new_start = (start + shift).clamp(0, u32::MAX as i64);
+            ticks[span.on] = new_start as u32;
+
+            if let Some(off) = span.off {
+                let length = ticks[off] as i64 - start;
+                let new_length = (length + self.rng.spread(self.length)).max(1);
+                ticks[off] = (new_start + new_length).min(u32::MAX as i64) as u32;
+            }
+
+            let change = self.rng.spread(self.velocity as u32);
+            if let Some(velocity) = track.events[span.on]
+                .event_mut()
+                .channel_voice_mut()
+                .and_then(|msg| msg.event.velocity_mut())
+            {
+                let changed = (velocity.byte() as i64 + change).clamp(1, 127);
+                *velocity = Velocity::new_unchecked(changed as u8);
+            }
+        }
+
+        retime(track, &ticks);
+    }
+
+    /// Humanize every track of a file
+    pub fn apply_to_file(&mut self, file: &mut MidiFile<'_>) {
+        for track in file.tracks_mut() {
+            self.apply_to_track(track);
+        }
+    }
+}
+
+#[cfg(test)]
+fn chord(tick: u32, notes: &[u8]) -> Track<'static> {
+    let mut track = Track::new(Vec::new());
+    for note in notes {
+        let note = Note::from_databyte(*note).unwrap();
+        let on = VoiceEvent::note_on(note, Velocity::MAX);
+        let off = VoiceEvent::note_off(note, Velocity::ZERO);
+        track.insert(tick, Channel::One.send_event(on).into());
+        track.insert(tick + 1, Channel::One.send_event(off).into());
+    }
+    track
+}
+
+#[test]
+fn never_before_zero_or_shorter_than_a_tick() {
+    let mut track = chord(0, &[60, 64, 67]);
+    Humanize::new(1)
+        .with_timing(50)
+        .with_length(50)
+        .apply_to_track(&mut track);
+    for span in note_spans(track.events()) {
+        let on = track.events()[span.on].accumulated_ticks();
+        let off = track.events()[span.off.unwrap()].accumulated_ticks();
+        assert!(off > on);
+    }
+}
+
+#[test]
+fn keeps_chord_order() {
+    for seed in 0..20 {
+        let mut track = chord(100, &[48, 52, 55, 60]);
+        Humanize::new(seed)
+            .with_timing(20)
+            .with_chord_order(true)
+            .apply_to_track(&mut track);
+        let order: Vec<_> = track
+            .events()
+            .iter()
+            .filter_map(|e| e.event().channel_voice()?.is_note_on())
+            .map(|note| note.byte())
+            .collect();
+        assert_eq!(order, [48, 52, 55, 60]);
+    }
+}
+
+#[test]
+fn seeds_are_reproducible() {
+    let humanize = Humanize::new(42).with_timing(10).with_velocity(20);
+    let mut first = chord(50, &[60, 62]);
+    let mut second = first.clone();
+    humanize.clone().apply_to_track(&mut first);
+    humanize.clone().apply_to_track(&mut second);
+    assert_eq!(first, second);
+
+    let mut other = chord(50, &[60, 62]);
+    Humanize::new(43)
+        .with_timing(10)
+        .with_velocity(20)
+        .apply_to_track(&mut other);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn never_after_the_last_tick() {
+    for seed in 0..20 {
+        let mut track = chord(u32::MAX - 1, &[60, 64, 67]);
+        Humanize::new(seed)
+            .with_timing(50)
+            .apply_to_track(&mut track);
+        // notes pushed late are held at the last tick, instead of wrapping around
+        assert!(
+            track
+                .events()
+                .iter()
+                .all(|e| e.accumulated_ticks() >= u32::MAX - 51)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+#![doc = r#"
+Musical transformations over tracks, files and event streams
+"#]
+
+mod transpose;
+pub use transpose::*;
+
+mod quantize;
+pub use quantize::*;
+
+mod channels;
+pub use channels::*;
+
+mod velocity;
+pub use velocity::*;
+
+mod groove;
+pub use groove::*;
+
+mod humanize;
+pub use humanize::*;
+
+mod serial;
+pub use serial::*;
+
+mod resolution;
+pub use resolution::*;
+
+mod spans;
+pub(crate) use spans::*;
@@ -0,0 +1,359 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+use super::{note_spans, retime};
+
+#[doc = r#"
+A musical note value used as a quantization grid.
+
+Grids are fractions of a whole note, so they are independent of a file's resolution.
+Use [`Grid::ticks`] to get the length of a grid step for a [`Timing`].
+
+# Example
+```rust
+# use midix::prelude::*;
+// sixteenth notes at 96 ticks per quarter note
+assert_eq!(Grid::SIXTEENTH.ticks(96), 24);
+// eighth note triplets
+assert_eq!(Grid::EIGHTH.triplet().ticks(96), 32);
+// dotted eighths
+assert_eq!(Grid::EIGHTH.dotted().ticks(96), 72);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grid {
+    numerator: u32,
+    denominator: u32,
+}
+
+impl Grid {
+    /// A whole note
+    pub const WHOLE: Self = Self::note(1);
+    /// A half note
+    pub const HALF: Self = Self::note(2);
+    /// A quarter note
+    pub const QUARTER: Self = Self::note(4);
+    /// An eighth note
+    pub const EIGHTH: Self = Self::note(8);
+    /// A sixteenth note
+    pub const SIXTEENTH: Self = Self::note(16);
+    /// A thirty-second note
+    pub const THIRTY_SECOND: Self = Self::note(32);
+
+    /// A `1/division` note. `Grid::note(16)` is a sixteenth note.
+    ///
+    /// A division of 0 is treated as 1.
+    pub const fn note(division: u32) -> Self {
+        Self {
+            numerator: 1,
+            denominator: if division == 0 { 1 } else { division },
+        }
+    }
+
+    /// Three of these notes in the time of two
+    pub const fn triplet(self) -> Self {
+        Self {
+            numerator: self.numerator * 2,
+            denominator: self.denominator * 3,
+        }
+    }
+
+    /// This note, plus half its length
+    pub const fn dotted(self) -> Self {
+        Self {
+            numerator: self.numerator * 3,
+            denominator: self.denominator * 2,
+        }
+    }
+
+    /// The length of one grid step, in ticks, rounded to the nearest tick.
+    ///
+    /// Always at least one tick.
+    pub const fn ticks(&self, ticks_per_quarter_note: u16) -> u32 {
+        let whole = ticks_per_quarter_note as u64 * 4 * self.numerator as u64;
+        let ticks = (whole + self.denominator as u64 / 2) / self.denominator as u64;
+        if ticks == 0 { 1 } else { ticks as u32 }
+    }
+}
+
+/// What happens to the end of a note when its start is quantized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoteEnds {
+    /// Move the note off with its note on, keeping the note's length
+    #[default]
+    KeepLength,
+    /// Leave the note off where it is
+    KeepPosition,
+    /// Quantize the note off to the grid as well.
+    ///
+    /// A note that would end on or before its start is made one grid step long.
+    Quantize,
+}
+
+#[doc = r#"
+Moves events towards a rhythmic grid.
+
+Note ons are moved to the nearest grid line, and their note offs follow according to [`NoteEnds`].
+Other events are left in place unless [`Quantize::with_all_events`] is set.
+
+- Strength moves events only part of the way, as a percentage.
+- Swing delays every second grid line, using the familiar drum machine scale:
+  50% is straight, 66% is a triplet feel and 75% is the heaviest swing.
+- The window leaves events alone if they are further from the grid than a percentage of a grid step,
+  which keeps intentional pushes and flams intact.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+let on = Channel::One.send_event(VoiceEvent::note_on(note!(C, 4), Velocity::MAX));
+let off = Channel::One.send_event(VoiceEvent::note_off(note!(C, 4), Velocity::ZERO));
+track.insert(26, on.into());
+track.insert(50, off.into());
+
+// 96 ticks per quarter note: sixteenths are 24 ticks long
+Quantize::new(Grid::SIXTEENTH).apply_to_track(&mut track, 96);
+
+let ticks: Vec<_> = track.events().iter().map(|e| e.accumulated_ticks()).collect();
+assert_eq!(ticks, [24, 48]);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantize {
+    grid: Grid,
+    strength: u8,
+    swing: u8,
+    window: u8,
+    ends: NoteEnds,
+    all_events: bool,
+}
+
+impl Quantize {
+    /// Quantize fully to a grid, without swing
+    pub const fn new(grid: Grid) -> Self {
+        Self {
+            grid,
+            strength: 100,
+            swing: 50,
+            window: 100,
+            ends: NoteEnds::KeepLength,
+            all_events: false,
+        }
+    }
+
+    /// Returns the grid events are moved towards
+    pub const fn grid(&self) -> Grid {
+        self.grid
+    }
+
+    /// How far events move towards the grid, from 0% (not at all) to 100% (onto the grid).
+    ///
+    /// Values above 100 are treated as 100.
+    pub const fn with_strength(mut self, percent: u8) -> Self {
+        self.strength = if percent > 100 { 100 } else { percent };
+        self
+    }
+
+    /// Delay every second grid line, from 50% (straight) to 75%.
+    ///
+    /// Values are clamped to that range.
+    pub const fn with_swing(mut self, percent: u8) -> Self {
+        self.swing = if percent < 50 {
+            50
+        } else if percent > 75 {
+            75
+        } else {
+            percent
+        };
+        self
+    }
+
+    /// Only move events within a percentage of a grid step from their grid line.
+    ///
+    /// 50% or more moves every event.
+    pub const fn with_window(mut self, percent: u8) -> Self {
+        self.window = percent;
+        self
+    }
+
+    /// Choose what happens to the end of each note
+    pub const fn with_note_ends(mut self, ends: NoteEnds) -> Self {
+        self.ends = ends;
+        self
+    }
+
+    /// Quantize every event, not only notes
+    pub const fn with_all_events(mut self, all_events: bool) -> Self {
+        self.all_events = all_events;
+        self
+    }
+
+    /// Returns where an event at `tick` would be moved to
+    pub fn quantize_tick(&self, tick: u32, ticks_per_quarter_note: u16) -> u32 {
+        self.quantize_with_step(tick, self.grid.ticks(ticks_per_quarter_note))
+    }
+
+    /// Quantize the events of a track
+    pub fn apply_to_track(&self, track: &mut Track<'_>, ticks_per_quarter_note: u16) {
+        let step = self.grid.ticks(ticks_per_quarter_note);
+        let original: Vec<u32> = track
+            .events()
+            .iter()
+            .map(Ticked::accumulated_ticks)
+            .collect();
+        let mut ticks = original.clone();
+        let mut in_span = alloc::vec![false; ticks.len()];
+
+        for span in note_spans(track.events()) {
+            in_span[span.on] = true;
+            let start = original[span.on];
+            let new_start = self.quantize_with_step(start, step);
+            ticks[span.on] = new_start;
+
+            let Some(off) = span.off else {
+                continue;
+            };
+            in_span[off] = true;
+            let end = original[off];
+            ticks[off] = match self.ends {
+                NoteEnds::KeepLength => new_start.saturating_add(end - start),
+                NoteEnds::KeepPosition => end.max(new_start),
+                NoteEnds::Quantize => {
+                    let new_end = self.quantize_with_step(end, step);
+                    if new_end <= new_start && end > start {
+                        new_start.saturating_add(step)
+                    } else {
+                        new_end.max(new_start)
+                    }
+                }
+            };
+        }
+
+        if self.all_events {
+            for (tick, in_span) in ticks.iter_mut().zip(in_span) {
+                if !in_span {
+                    *tick = self.quantize_with_step(*tick, step);
+                }
+            }
+        }
+
+        retime(track, &ticks);
+    }
+
+    /// Quantize every track of a file, using the file's resolution
+    ///
+    /// # Errors
+    /// If the file is timed in SMPTE frames
+    pub fn apply_to_file(&self, file: &mut MidiFile<'_>) -> Result<(), EditError> {
+        let ticks_per_quarter_note = file
+            .timing()
+            .ticks_per_quarter_note()
+            .ok_or(EditError::SmpteTiming)?;
+        for track in file.tracks_mut() {
+            self.apply_to_track(track, ticks_per_quarter_note);
+        }
+        Ok(())
+    }
+
+    fn quantize_with_step(&self, tick: u32, step: u32) -> u32 {
+        let pair = step as u64 * 2;
+        let offbeat = (pair * self.swing as u64 + 50) / 100;
+
+        let tick_64 = tick as u64;
+        let pair_start = tick_64 / pair * pair;
+        let target = [pair_start, pair_start + offbeat, pair_start + pair]
+            .into_iter()
+            .min_by_key(|line| line.abs_diff(tick_64))
+            .unwrap();
+
+        let distance = target.abs_diff(tick_64);
+        if self.window < 50 && distance * 100 > step as u64 * self.window as u64 {
+            return tick;
+        }
+
+        let moved = (distance * self.strength as u64 + 50) / 100;
+        if target >= tick_64 {
+            (tick_64 + moved).min(u32::MAX as u64) as u32
+        } else {
+            (tick_64 - moved) as u32
+        }
+    }
+}
+
+#[cfg(test)]
+fn quantized(quantize: Quantize, notes: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut track = Track::new(Vec::new());
+    for (index, (start, end)) in notes.iter().enumerate() {
+        let note = Note::from_databyte(60 + index as u8).unwrap();
+        let on = Channel::One.send_event(VoiceEvent::note_on(note, Velocity::MAX));
+        let off = Channel::One.send_event(VoiceEvent::note_off(note, Velocity::ZERO));
+        track.insert(*start, on.into());
+        track.insert(*end, off.into());
+    }
+    quantize.apply_to_track(&mut track, 96);
+
+    let events = track.events();
+    let mut spans: Vec<_> = note_spans(events)
+        .into_iter()
+        .map(|span| {
+            let start = events[span.on].accumulated_ticks();
+            let end = events[span.off.unwrap()].accumulated_ticks();
+            (span.note, (start, end))
+        })
+        .collect();
+    // order by pitch, which is the order the notes were given in
+    spans.sort_by_key(|(note, _)| *note);
+    spans.into_iter().map(|(_, span)| span).collect()
+}
+
+#[test]
+fn notes_at_the_last_tick() {
+    let last = (u32::MAX - 10, u32::MAX);
+    for ends in [NoteEnds::KeepLength, NoteEnds::Quantize] {
+        let quantize = Quantize::new(Grid::SIXTEENTH).with_note_ends(ends);
+        let [(start, end)] = quantized(quantize, &[last])[..] else {
+            panic!("one note in, one note out");
+        };
+        // the note off stays after the note on, instead of wrapping around
+        assert!(u32::MAX - 24 <= start && start <= end);
+    }
+}
+
+#[test]
+fn grid_ticks() {
+    assert_eq!(Grid::QUARTER.ticks(480), 480);
+    assert_eq!(Grid::SIXTEENTH.triplet().ticks(480), 80);
+    assert_eq!(Grid::note(64).triplet().ticks(96), 4);
+    assert_eq!(Grid::note(1024).ticks(24), 1);
+}
+
+#[test]
+fn strength_and_window() {
+    let grid = Quantize::new(Grid::SIXTEENTH);
+    assert_eq!(
+        quantized(grid.with_strength(50), &[(10, 30), (28, 40)]),
+        [(5, 25), (26, 38)]
+    );
+    // only events within 2.4 ticks of the grid move
+    assert_eq!(
+        quantized(grid.with_window(10), &[(10, 30), (26, 40)]),
+        [(10, 30), (24, 38)]
+    );
+}
+
+#[test]
+fn swing_and_note_ends() {
+    // 66% swing on sixteenths puts the off-beat line at tick 32 of each 48 tick pair
+    let swung = Quantize::new(Grid::SIXTEENTH).with_swing(66);
+    assert_eq!(
+        quantized(swung, &[(2, 10), (27, 40), (46, 60)]),
+        [(0, 8), (32, 45), (48, 62)]
+    );
+
+    let ends = Quantize::new(Grid::SIXTEENTH).with_note_ends(NoteEnds::Quantize);
+    assert_eq!(quantized(ends, &[(3, 40), (50, 55)]), [(0, 48), (48, 72)]);
+
+    let ends = Quantize::new(Grid::SIXTEENTH).with_note_ends(NoteEnds::KeepPosition);
+    assert_eq!(quantized(ends, &[(3, 40)]), [(0, 40)]);
+}
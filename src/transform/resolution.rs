@@ -0,0 +1,332 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+use super::{note_spans, retime};
+
+/// How two notes were squeezed together by a [`TimingConversion`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionKind {
+    /// The note's start and end landed on the same tick
+    ZeroLength,
+    /// The note now starts on the same tick as an earlier note of the same pitch and channel
+    Merged,
+}
+
+/// A note that no longer sounds as it did before a [`TimingConversion`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collision {
+    /// The index of the note's track
+    pub track: usize,
+    /// The tick of the note on, after conversion
+    pub tick: u32,
+    /// The channel of the note
+    pub channel: Channel,
+    /// The pitch of the note
+    pub note: Note,
+    /// What happened to the note
+    pub kind: CollisionKind,
+}
+
+#[doc = r#"
+Converts a file to a different [`Timing`], moving every event to keep its place in time.
+
+- Between two resolutions of ticks per quarter note (for example 480 to 96), ticks are scaled.
+- Between musical ticks and [`Timing::Smpte`], a [`TempoMap`] gives the time of each tick.
+  It is counted in ticks of the musical side of the conversion, and defaults to
+  [`TempoMap::from_file`].
+- Between two SMPTE timings, ticks are scaled.
+
+SMPTE tick rates use the nominal frame rate ([`SmpteFps::as_division`]), as [`Timing::micros_per_tick`] does.
+
+A track keeps a single tempo, so converting from SMPTE to musical ticks needs a tempo map
+without changes.
+
+Lowering the resolution can squeeze notes together. These are returned as [`Collision`]s,
+so they can be reported or fixed.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+let on = VoiceEvent::note_on(note!(C, 4), Velocity::MAX);
+let off = VoiceEvent::note_off(note!(C, 4), Velocity::ZERO);
+track.insert(960, Channel::One.send_event(on).into());
+track.insert(962, Channel::One.send_event(off).into());
+let mut file = MidiFile::new(Format::SingleMultiChannel(track), Timing::new_ticks_per_quarter_note(480));
+
+let collisions = TimingConversion::new(Timing::new_ticks_per_quarter_note(96)).apply_to_file(&mut file).unwrap();
+assert_eq!(file.timing().ticks_per_quarter_note(), Some(96));
+assert_eq!(file.tracks()[0].events()[0].accumulated_ticks(), 192);
+
+// the note was too short to survive
+assert_eq!(collisions[0].kind, CollisionKind::ZeroLength);
+```
+"#]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingConversion {
+    timing: Timing,
+    rounding: Rounding,
+    tempo_map: Option<TempoMap>,
+}
+
+impl TimingConversion {
+    /// Convert to a timing, rounding to the nearest tick
+    pub fn new(timing: Timing) -> Self {
+        Self {
+            timing,
+            rounding: Rounding::Nearest,
+            tempo_map: None,
+        }
+    }
+
+    /// Choose how positions between two ticks are rounded
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Use a tempo map when converting between musical and SMPTE timing
+    pub fn with_tempo_map(mut self, tempo_map: TempoMap) -> Self {
+        self.tempo_map = Some(tempo_map);
+        self
+    }
+
+    /// Returns the tick an event at `tick` would be moved to
+    pub fn convert_tick(&self, tick: u32, from: Timing, tempo_map: &TempoMap) -> u32 {
+        // the map counts musical ticks, and between two resolutions ticks are only scaled
+        let constant = TempoMap::default();
+        let (from_map, to_map) = match (from, self.timing) {
+            (Timing::TicksPerQuarterNote(_), Timing::Smpte(_)) => (tempo_map, &constant),
+            (Timing::Smpte(_), Timing::TicksPerQuarterNote(_)) => (&constant, tempo_map),
+            _ => (&constant, &constant),
+        };
+        convert_tick_between(tick, (from, from_map), (self.timing, to_map), self.rounding)
+    }
+
+    /// Convert a file to the new timing.
+    ///
+    /// Returns the notes squeezed together by the conversion, or an error when converting
+    /// from SMPTE to musical ticks with a tempo map that changes tempo,
+    /// since the file's tracks could only keep its first tempo.
+    pub fn apply_to_file(&self, file: &mut MidiFile<'_>) -> Result<Vec<Collision>, EditError> {
+        let from = file.timing();
+        let tempo_map = self
+            .tempo_map
+            .clone()
+            .unwrap_or_else(|| TempoMap::from_file(file));
+        if let (Timing::Smpte(_), Timing::TicksPerQuarterNote(_)) = (from, self.timing)
+            && tempo_map.changes().len() > 1
+        {
+            return Err(EditError::TempoChanges(tempo_map.changes().len()));
+        }
+
+        let mut collisions = Vec::new();
+        for (index, track) in file.tracks_mut().iter_mut().enumerate() {
+            let original: Vec<u32> = track
+                .events()
+                .iter()
+                .map(Ticked::accumulated_ticks)
+                .collect();
+            let ticks: Vec<u32> = original
+                .iter()
+                .map(|tick| self.convert_tick(*tick, from, &tempo_map))
+                .collect();
+
+            // the tick each pitch last started on, before and after conversion
+            let mut last_starts: Vec<(Channel, Note, u32, u32)> = Vec::new();
+            for span in note_spans(track.events()) {
+                let (before, after) = (original[span.on], ticks[span.on]);
+                let mut collide = |kind| {
+                    collisions.push(Collision {
+                        track: index,
+                        tick: after,
+                        channel: span.channel,
+                        note: span.note,
+                        kind,
+                    })
+                };
+
+                if let Some(off) = span.off
+                    && ticks[off] == after
+                    && original[off] != before
+                {
+                    collide(CollisionKind::ZeroLength);
+                }
+
+                match last_starts
+                    .iter_mut()
+                    .find(|(channel, note, ..)| *channel == span.channel && *note == span.note)
+                {
+                    Some(last) => {
+                        if last.3 == after && last.2 != before {
+                            collide(CollisionKind::Merged);
+                        }
+                        (last.2, last.3) = (before, after);
+                    }
+                    None => last_starts.push((span.channel, span.note, before, after)),
+                }
+            }
+
+            retime(track, &ticks);
+        }
+
+        if let (Timing::Smpte(_), Timing::TicksPerQuarterNote(_)) = (from, self.timing)
+            && let Some(first) = file.tracks_mut().first_mut()
+        {
+            first.info_mut().tempo = tempo_map.tempo_at(0);
+        }
+        file.set_timing(self.timing);
+
+        Ok(collisions)
+    }
+}
+
+/// Move a tick of one timing to the tick of another at the same time,
+/// with a tempo map for each side counted in its own ticks.
+///
+/// Maps are only used for musical timings.
+pub(crate) fn convert_tick_between(
+    tick: u32,
+    (from, from_map): (Timing, &TempoMap),
+    (to, to_map): (Timing, &TempoMap),
+    rounding: Rounding,
+) -> u32 {
+    // the time of the tick as a fraction of microseconds
+    let (micros, per) = match from {
+        Timing::TicksPerQuarterNote(tpqn) => (
+            from_map.scaled_micros(tick),
+            tpqn.ticks_per_quarter_note().max(1) as u128,
+        ),
+        Timing::Smpte(smpte) => (tick as u128 * 1_000_000, ticks_per_second(&smpte)),
+    };
+    match to {
+        Timing::TicksPerQuarterNote(tpqn) => to_map.tick_at_scaled(
+            micros * tpqn.ticks_per_quarter_note() as u128,
+            per,
+            rounding,
+        ),
+        Timing::Smpte(smpte) => rounding
+            .divide(micros * ticks_per_second(&smpte), per * 1_000_000)
+            .min(u32::MAX as u128) as u32,
+    }
+}
+
+fn ticks_per_second(smpte: &SmpteHeader) -> u128 {
+    (smpte.fps().as_division() as u128 * smpte.ticks_per_frame() as u128).max(1)
+}
+
+#[cfg(test)]
+fn file_with_ticks(timing: Timing, ticks: &[u32]) -> MidiFile<'static> {
+    let mut track = Track::new(Vec::new());
+    for tick in ticks {
+        track.insert(*tick, SystemExclusiveMessage::new(alloc::vec![0x7D]).into());
+    }
+    MidiFile::new(Format::SingleMultiChannel(track), timing)
+}
+
+#[cfg(test)]
+fn ticks_of(file: &MidiFile) -> Vec<u32> {
+    file.tracks()[0]
+        .events()
+        .iter()
+        .map(Ticked::accumulated_ticks)
+        .collect()
+}
+
+#[test]
+fn resolution_rounding() {
+    let from = Timing::new_ticks_per_quarter_note(480);
+    let to = Timing::new_ticks_per_quarter_note(96);
+
+    let mut file = file_with_ticks(from, &[0, 2, 3, 7, 480]);
+    TimingConversion::new(to).apply_to_file(&mut file).unwrap();
+    assert_eq!(ticks_of(&file), [0, 0, 1, 1, 96]);
+
+    let mut file = file_with_ticks(from, &[0, 2, 3, 7, 480]);
+    TimingConversion::new(to)
+        .with_rounding(Rounding::Down)
+        .apply_to_file(&mut file)
+        .unwrap();
+    assert_eq!(ticks_of(&file), [0, 0, 0, 1, 96]);
+}
+
+#[test]
+fn smpte_round_trip() {
+    let metrical = Timing::new_ticks_per_quarter_note(96);
+    // 1000 ticks per second
+    let smpte = Timing::new_smpte(SmpteFps::TwentyFive, DataByte::new_unchecked(40));
+    let tempo_map = TempoMap::new(Tempo::new(500_000)).with_change(96, Tempo::new(1_000_000));
+
+    let mut file = file_with_ticks(metrical, &[0, 48, 96, 144]);
+    TimingConversion::new(smpte)
+        .with_tempo_map(tempo_map.clone())
+        .apply_to_file(&mut file)
+        .unwrap();
+    assert_eq!(ticks_of(&file), [0, 250, 500, 1_000]);
+
+    // the tempo change can't be kept by the track
+    assert_eq!(
+        TimingConversion::new(metrical)
+            .with_tempo_map(tempo_map)
+            .apply_to_file(&mut file),
+        Err(EditError::TempoChanges(2))
+    );
+    assert_eq!(ticks_of(&file), [0, 250, 500, 1_000]);
+
+    // with one tempo every event keeps its time
+    let tempo = Tempo::new(500_000);
+    TimingConversion::new(metrical)
+        .with_tempo_map(TempoMap::new(tempo))
+        .apply_to_file(&mut file)
+        .unwrap();
+    assert_eq!(ticks_of(&file), [0, 48, 96, 192]);
+    assert_eq!(file.tracks()[0].info().tempo, tempo);
+    let times: Vec<_> = ticks_of(&file)
+        .into_iter()
+        .map(|tick| metrical.ticks_to_micros(tick, tempo))
+        .collect();
+    assert_eq!(times, [0, 250, 500, 1_000].map(UMicros::from_ms));
+}
+
+#[test]
+fn tempo_maps_on_both_sides() {
+    let from = Timing::new_ticks_per_quarter_note(96);
+    let to = Timing::new_ticks_per_quarter_note(480);
+    // 120bpm, then 60bpm from the second beat
+    let from_map = TempoMap::new(Tempo::new(500_000)).with_change(96, Tempo::new(1_000_000));
+    let to_map = TempoMap::new(Tempo::new(1_000_000));
+    let convert =
+        |tick| convert_tick_between(tick, (from, &from_map), (to, &to_map), Rounding::Nearest);
+    // half a second, then two and a half seconds, at one second a beat
+    assert_eq!(convert(96), 240);
+    assert_eq!(convert(288), 1_200);
+}
+
+#[test]
+fn merged_notes_collide() {
+    let mut track = Track::new(Vec::new());
+    for tick in [0, 3] {
+        let on = VoiceEvent::note_on(note!(A, 3), Velocity::MAX);
+        let off = VoiceEvent::note_off(note!(A, 3), Velocity::ZERO);
+        track.insert(tick, Channel::Two.send_event(on).into());
+        track.insert(tick + 2, Channel::Two.send_event(off).into());
+    }
+    let mut file = MidiFile::new(
+        Format::SingleMultiChannel(track),
+        Timing::new_ticks_per_quarter_note(960),
+    );
+    let collisions = TimingConversion::new(Timing::new_ticks_per_quarter_note(24))
+        .with_rounding(Rounding::Down)
+        .apply_to_file(&mut file)
+        .unwrap();
+    let kinds: Vec<_> = collisions.iter().map(|c| c.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            CollisionKind::ZeroLength,
+            CollisionKind::ZeroLength,
+            CollisionKind::Merged
+        ]
+    );
+}
@@ -0,0 +1,387 @@
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+use super::note_spans;
+
+#[doc = r#"
+Reverses the notes inside a region of ticks, so the last note plays first.
+
+Each note that starts and ends inside the region is mirrored, keeping its length.
+Notes crossing the edges of the region, and events that aren't notes, stay where they are.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+for (tick, note) in [(0, note!(C, 4)), (96, note!(E, 4)), (192, note!(G, 4))] {
+    track.insert(tick, Channel::One.send_event(VoiceEvent::note_on(note, Velocity::MAX)).into());
+    track.insert(tick + 96, Channel::One.send_event(VoiceEvent::note_off(note, Velocity::ZERO)).into());
+}
+
+let reversed = Retrograde::new(0..288).apply(&track);
+let first = reversed.events()[0].event().channel_voice().unwrap().note();
+assert_eq!(first, Some(note!(G, 4)));
+```
+"#]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Retrograde {
+    region: Range<u32>,
+}
+
+impl Retrograde {
+    /// Reverse the notes from the start of `region` up to its end
+    pub const fn new(region: Range<u32>) -> Self {
+        Self { region }
+    }
+
+    /// Returns a copy of the track with the notes of the region reversed
+    pub fn apply<'a>(&self, track: &Track<'a>) -> Track<'a> {
+        let Range { start, end } = self.region;
+        let mut ticks = ticks_of(track);
+        for span in note_spans(track.events()) {
+            let Some(off) = span.off else {
+                continue;
+            };
+            let (on_tick, off_tick) = (ticks[span.on], ticks[off]);
+            if on_tick < start || off_tick > end {
+                continue;
+            }
+            ticks[span.on] = start + (end - off_tick);
+            ticks[off] = start + (end - on_tick);
+        }
+        rebuild(track, &ticks)
+    }
+}
+
+#[doc = r#"
+Mirrors the pitch of every note around an axis.
+
+A note three semitones above the axis becomes a note three semitones below it.
+[`Channel::Ten`] is left alone by default, since its notes pick drum sounds rather than pitches.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+let on = VoiceEvent::note_on(note!(E, 4), Velocity::MAX);
+track.insert(0, Channel::One.send_event(on).into());
+
+let inverted = Inversion::new(note!(C, 4)).apply(&track).unwrap();
+let note = inverted.events()[0].event().channel_voice().unwrap().note();
+assert_eq!(note, Some(note!(GSharp, 3)));
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Inversion {
+    axis: Note,
+    drums: bool,
+}
+
+impl Inversion {
+    /// Mirror notes around `axis`, skipping [`Channel::Ten`]
+    pub const fn new(axis: Note) -> Self {
+        Self { axis, drums: false }
+    }
+
+    /// Also invert the notes of [`Channel::Ten`]
+    pub const fn with_drums(mut self, drums: bool) -> Self {
+        self.drums = drums;
+        self
+    }
+
+    /// Returns a copy of the track with every note inverted.
+    ///
+    /// # Errors
+    /// If a note would be inverted outside of the MIDI range (0-127)
+    pub fn apply<'a>(&self, track: &Track<'a>) -> Result<Track<'a>, EditError> {
+        let axis = self.axis.byte() as i16;
+        map_notes(track, self.drums, |note| 2 * axis - note.byte() as i16)
+    }
+}
+
+#[doc = r#"
+Stretches (augmentation) or shrinks (diminution) time by a rational factor.
+
+Events at or after the origin are moved so their distance from it is multiplied
+by `numerator / denominator`, rounded to the nearest tick. Events before the origin stay put.
+Notes are never shortened to less than one tick.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+let on = VoiceEvent::note_on(note!(C, 4), Velocity::MAX);
+let off = VoiceEvent::note_off(note!(C, 4), Velocity::ZERO);
+track.insert(96, Channel::One.send_event(on).into());
+track.insert(144, Channel::One.send_event(off).into());
+
+// twice as slow
+let augmented = Augmentation::new(2, 1).apply(&track);
+let ticks: Vec<_> = augmented.events().iter().map(Ticked::accumulated_ticks).collect();
+assert_eq!(ticks, [192, 288]);
+
+// two thirds as long, from the note
+let diminished = Augmentation::new(2, 3).with_origin(96).apply(&track);
+let ticks: Vec<_> = diminished.events().iter().map(Ticked::accumulated_ticks).collect();
+assert_eq!(ticks, [96, 128]);
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Augmentation {
+    numerator: u32,
+    denominator: u32,
+    origin: u32,
+}
+
+impl Augmentation {
+    /// Multiply time by `numerator / denominator`, from tick 0.
+    ///
+    /// A denominator of zero is treated as one.
+    pub const fn new(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator: if denominator == 0 { 1 } else { denominator },
+            origin: 0,
+        }
+    }
+
+    /// Stretch time from a tick, rather than from tick 0
+    pub const fn with_origin(mut self, tick: u32) -> Self {
+        self.origin = tick;
+        self
+    }
+
+    /// Returns the tick an event at `tick` would be moved to
+    pub const fn scale_tick(&self, tick: u32) -> u32 {
+        if tick < self.origin {
+            return tick;
+        }
+        let distance = (tick - self.origin) as u128 * self.numerator as u128;
+        let scaled = Rounding::Nearest.divide(distance, self.denominator as u128);
+        let moved = self.origin as u128 + scaled;
+        if moved > u32::MAX as u128 {
+            u32::MAX
+        } else {
+            moved as u32
+        }
+    }
+
+    /// Returns a copy of the track with time stretched
+    pub fn apply<'a>(&self, track: &Track<'a>) -> Track<'a> {
+        let original = ticks_of(track);
+        let mut ticks: Vec<u32> = original.iter().map(|tick| self.scale_tick(*tick)).collect();
+        for span in note_spans(track.events()) {
+            if let Some(off) = span.off
+                && original[off] > original[span.on]
+            {
+                ticks[off] = ticks[off].max(ticks[span.on].saturating_add(1));
+            }
+        }
+        rebuild(track, &ticks)
+    }
+}
+
+#[doc = r#"
+Moves notes up or down by a number of steps within a scale.
+
+This is a diatonic transposition: in C major, rotating by two steps moves
+C to E, and B to D in the octave above. Notes outside the scale are left alone.
+[`Channel::Ten`] is left alone by default, since its notes pick drum sounds rather than pitches.
+
+# Example
+```rust
+# use midix::prelude::*;
+let c_major = [Key::C, Key::D, Key::E, Key::F, Key::G, Key::A, Key::B];
+let mut track = Track::new(Vec::new());
+let on = VoiceEvent::note_on(note!(B, 4), Velocity::MAX);
+track.insert(0, Channel::One.send_event(on).into());
+
+let rotated = ScaleRotation::new(&c_major, 2).apply(&track).unwrap();
+let note = rotated.events()[0].event().channel_voice().unwrap().note();
+assert_eq!(note, Some(note!(D, 5)));
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScaleRotation {
+    /// One bit for each pitch class of the scale
+    pitch_classes: u16,
+    steps: i32,
+    drums: bool,
+}
+
+impl ScaleRotation {
+    /// Move notes by `steps` degrees of a scale, given by its keys in any order
+    pub fn new(scale: &[Key], steps: i32) -> Self {
+        let pitch_classes = scale
+            .iter()
+            .fold(0, |mask, key| mask | 1 << key.pitch_class());
+        Self {
+            pitch_classes,
+            steps,
+            drums: false,
+        }
+    }
+
+    /// Also rotate the notes of [`Channel::Ten`]
+    pub const fn with_drums(mut self, drums: bool) -> Self {
+        self.drums = drums;
+        self
+    }
+
+    /// Returns the note `note` would be moved to, which may be outside of the MIDI range.
+    ///
+    /// Notes outside the scale are returned unchanged.
+    pub fn rotate(&self, note: Note) -> i16 {
+        let byte = note.byte() as i16;
+        let degrees: Vec<i16> = (0..12)
+            .filter(|pitch_class| self.pitch_classes & (1 << pitch_class) != 0)
+            .collect();
+        let Some(degree) = degrees.iter().position(|pc| *pc == byte % 12) else {
+            return byte;
+        };
+        let len = degrees.len() as i64;
+        let position = (byte / 12) as i64 * len + degree as i64 + self.steps as i64;
+        let octave = position.div_euclid(len);
+        let pitch = octave * 12 + degrees[position.rem_euclid(len) as usize] as i64;
+        pitch.clamp(i16::MIN as i64, i16::MAX as i64) as i16
+    }
+
+    /// Returns a copy of the track with every note in the scale rotated.
+    ///
+    /// # Errors
+    /// If a note would be moved outside of the MIDI range (0-127)
+    pub fn apply<'a>(&self, track: &Track<'a>) -> Result<Track<'a>, EditError> {
+        map_notes(track, self.drums, |note| self.rotate(note))
+    }
+}
+
+fn ticks_of(track: &Track<'_>) -> Vec<u32> {
+    track
+        .events()
+        .iter()
+        .map(Ticked::accumulated_ticks)
+        .collect()
+}
+
+/// Copy a track with its events moved to `ticks`.
+///
+/// Note offs are placed before other events on the same tick, so a note
+/// moved to start where another of the same pitch ends isn't cut short.
+fn rebuild<'a>(track: &Track<'a>, ticks: &[u32]) -> Track<'a> {
+    let mut events: Vec<Ticked<LiveEvent<'a>>> = track
+        .events()
+        .iter()
+        .zip(ticks)
+        .map(|(event, tick)| Ticked::new(*tick, event.event().clone()))
+        .collect();
+    events.sort_by_key(|event| {
+        let is_off = event
+            .event()
+            .channel_voice()
+            .is_some_and(|msg| msg.is_note_off().is_some());
+        (event.accumulated_ticks(), !is_off)
+    });
+    Track::from_ticked(track.info().clone(), events)
+}
+
+/// Copy a track with the note of every message moved to the byte returned by `f`
+fn map_notes<'a>(
+    track: &Track<'a>,
+    drums: bool,
+    f: impl Fn(Note) -> i16,
+) -> Result<Track<'a>, EditError> {
+    let mut track = track.clone();
+    for (_, event) in track.events_mut() {
+        let Some(msg) = event.channel_voice_mut() else {
+            continue;
+        };
+        if msg.channel() == Channel::Ten && !drums {
+            continue;
+        }
+        let Some(note) = msg.event.note_mut() else {
+            continue;
+        };
+        let target = f(*note);
+        let semitones = (target - note.byte() as i16).clamp(i8::MIN as i16, i8::MAX as i16) as i8;
+        *note = note
+            .checked_transpose(semitones)
+            .filter(|moved| moved.byte() as i16 == target)
+            .ok_or(EditError::NoteOutOfRange {
+                note: *note,
+                semitones,
+            })?;
+    }
+    Ok(track)
+}
+
+#[cfg(test)]
+fn melody(notes: &[(u32, u32, u8)]) -> Track<'static> {
+    let mut track = Track::new(Vec::new());
+    for (on, off, key) in notes {
+        let note = Note::from_databyte(*key).unwrap();
+        let on_event = VoiceEvent::note_on(note, Velocity::MAX);
+        let off_event = VoiceEvent::note_off(note, Velocity::ZERO);
+        track.insert(*on, Channel::One.send_event(on_event).into());
+        track.insert(*off, Channel::One.send_event(off_event).into());
+    }
+    track
+}
+
+#[cfg(test)]
+fn spans_of(track: &Track) -> Vec<(u32, u32, u8)> {
+    note_spans(track.events())
+        .iter()
+        .map(|span| {
+            let on = track.events()[span.on].accumulated_ticks();
+            let off = track.events()[span.off.unwrap()].accumulated_ticks();
+            (on, off, span.note.byte())
+        })
+        .collect()
+}
+
+#[test]
+fn retrograde_mirrors_region() {
+    let track = melody(&[(0, 10, 60), (10, 20, 60), (20, 25, 62), (30, 50, 64)]);
+    let reversed = Retrograde::new(0..30).apply(&track);
+    assert_eq!(
+        spans_of(&reversed),
+        [(5, 10, 62), (10, 20, 60), (20, 30, 60), (30, 50, 64)]
+    );
+}
+
+#[test]
+fn inversion_range() {
+    let track = melody(&[(0, 10, 60), (10, 20, 67)]);
+    let inverted = Inversion::new(note!(C, 4)).apply(&track).unwrap();
+    assert_eq!(spans_of(&inverted), [(0, 10, 60), (10, 20, 53)]);
+
+    let low = Note::from_databyte(10).unwrap();
+    assert_eq!(
+        Inversion::new(low).apply(&track),
+        Err(EditError::NoteOutOfRange {
+            note: note!(C, 4),
+            semitones: -100
+        })
+    );
+}
+
+#[test]
+fn diminution_keeps_notes() {
+    let track = melody(&[(0, 1, 60), (1, 2, 62), (10, 40, 64)]);
+    let diminished = Augmentation::new(1, 4).apply(&track);
+    assert_eq!(spans_of(&diminished), [(0, 1, 60), (0, 1, 62), (3, 10, 64)]);
+}
+
+#[test]
+fn rotation_in_pentatonic() {
+    let pentatonic = [Key::C, Key::D, Key::E, Key::G, Key::A];
+    let rotation = ScaleRotation::new(&pentatonic, -2);
+    assert_eq!(rotation.rotate(note!(D, 4)), note!(A, 3).byte() as i16);
+    assert_eq!(rotation.rotate(note!(G, 4)), note!(D, 4).byte() as i16);
+    // not in the scale
+    assert_eq!(rotation.rotate(note!(F, 4)), note!(F, 4).byte() as i16);
+    assert_eq!(rotation.rotate(note!(C, -1)), -5);
+}
@@ -0,0 +1,53 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+
+/// A note on paired with its note off, by index into a track's events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NoteSpan {
+    pub channel: Channel,
+    pub note: Note,
+    pub on: usize,
+    /// `None` if the note is never released
+    pub off: Option<usize>,
+}
+
+/// Pair every note on with the first matching note off that follows it.
+///
+/// Overlapping notes of the same pitch and channel are released in the order they started.
+/// Spans are returned in the order of their note ons.
+pub(crate) fn note_spans(events: &[Ticked<LiveEvent<'_>>]) -> Vec<NoteSpan> {
+    let mut spans = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    for (index, event) in events.iter().enumerate() {
+        let Some(msg) = event.event().channel_voice() else {
+            continue;
+        };
+        let channel = msg.channel();
+        if let Some(note) = msg.is_note_on() {
+            open.push(spans.len());
+            spans.push(NoteSpan {
+                channel,
+                note,
+                on: index,
+                off: None,
+            });
+        } else if let Some(note) = msg.is_note_off()
+            && let Some(position) = open.iter().position(|span| {
+                let span: &NoteSpan = &spans[*span];
+                span.channel == channel && span.note == note
+            })
+        {
+            spans[open.remove(position)].off = Some(index);
+        }
+    }
+    spans
+}
+
+/// Move every event of a track to a new tick, keeping the order of events that land on the same tick
+pub(crate) fn retime(track: &mut Track<'_>, ticks: &[u32]) {
+    for (event, tick) in track.events.iter_mut().zip(ticks) {
+        event.set_accumulated_ticks(*tick);
+    }
+    track.events.sort_by_key(Ticked::accumulated_ticks);
+}
@@ -0,0 +1,290 @@
+use crate::prelude::*;
+
+/// What to do with a note that would be transposed outside of the MIDI range (0-127)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfRange {
+    /// Return an error, leaving the events unchanged
+    #[default]
+    Fail,
+    /// Remove the event
+    Drop,
+}
+
+#[doc = r#"
+Moves notes up or down by a number of semitones.
+
+Unlike adding to a [`Note`], which saturates, a note that would leave the
+MIDI range is either reported or dropped, depending on [`OutOfRange`].
+
+By default, events on [`Channel::Ten`] are left alone, since MIDI uses that channel
+for drums, where each note is a different instrument rather than a pitch.
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut track = Track::new(Vec::new());
+track.insert(0, Channel::One.send_event(VoiceEvent::note_on(note!(C, 4), Velocity::MAX)).into());
+track.insert(0, Channel::Ten.send_event(VoiceEvent::note_on(note!(C, 2), Velocity::MAX)).into());
+track.info_mut().key_signature = Some(KeySignature::new(0, false));
+
+Transpose::new(2).with_key_signature(true).apply_to_track(&mut track).unwrap();
+
+let notes: Vec<_> = track
+    .events()
+    .iter()
+    .filter_map(|e| e.event().channel_voice()?.note())
+    .collect();
+// the kick drum is untouched
+assert_eq!(notes, [note!(D, 4), note!(C, 2)]);
+// C major is now D major
+assert_eq!(track.info().key_signature, Some(KeySignature::new(2, false)));
+```
+"#]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transpose {
+    semitones: i8,
+    /// Channels that are left unchanged, one bit per channel
+    skipped: u16,
+    out_of_range: OutOfRange,
+    key_signature: bool,
+}
+
+impl Transpose {
+    /// Transpose by a number of semitones, skipping the drum channel
+    pub const fn new(semitones: i8) -> Self {
+        Self {
+            semitones,
+            skipped: 1 << Channel::Ten as u8,
+            out_of_range: OutOfRange::Fail,
+            key_signature: false,
+        }
+    }
+
+    /// Returns the number of semitones notes are moved by
+    pub const fn semitones(&self) -> i8 {
+        self.semitones
+    }
+
+    /// Transpose [`Channel::Ten`] as well as every other channel
+    pub const fn with_drums(self, transpose_drums: bool) -> Self {
+        if transpose_drums {
+            self.include_channel(Channel::Ten)
+        } else {
+            self.skip_channel(Channel::Ten)
+        }
+    }
+
+    /// Leave events on a channel unchanged
+    pub const fn skip_channel(mut self, channel: Channel) -> Self {
+        self.skipped |= 1 << channel as u8;
+        self
+    }
+
+    /// Transpose events on a channel that was skipped
+    pub const fn include_channel(mut self, channel: Channel) -> Self {
+        self.skipped &= !(1 << channel as u8);
+        self
+    }
+
+    /// Returns true if events on this channel are transposed
+    pub const fn transposes_channel(&self, channel: Channel) -> bool {
+        self.skipped & (1 << channel as u8) == 0
+    }
+
+    /// Choose what happens to notes moved outside of the MIDI range
+    pub const fn on_out_of_range(mut self, out_of_range: OutOfRange) -> Self {
+        self.out_of_range = out_of_range;
+        self
+    }
+
+    /// Also transpose the key signature of each track
+    pub const fn with_key_signature(mut self, key_signature: bool) -> Self {
+        self.key_signature = key_signature;
+        self
+    }
+
+    /// Transpose a single event.
+    ///
+    /// Returns `Ok(false)` if the event should be dropped.
+    ///
+    /// # Errors
+    /// If the note is out of range and [`OutOfRange::Fail`] is set. The event is unchanged.
+    pub fn apply_to_event(&self, event: &mut LiveEvent<'_>) -> Result<bool, EditError> {
+        let Some(note) = self.note_of(event) else {
+            return Ok(true);
+        };
+        match note.checked_transpose(self.semitones) {
+            Some(transposed) => {
+                *note = transposed;
+                Ok(true)
+            }
+            None => match self.out_of_range {
+                OutOfRange::Fail => Err(EditError::NoteOutOfRange {
+                    note: *note,
+                    semitones: self.semitones,
+                }),
+                OutOfRange::Drop => Ok(false),
+            },
+        }
+    }
+
+    /// Transpose every event in a track.
+    ///
+    /// Returns the number of events dropped.
+    ///
+    /// # Errors
+    /// If a note is out of range and [`OutOfRange::Fail`] is set. The track is unchanged.
+    pub fn apply_to_track(&self, track: &mut Track<'_>) -> Result<usize, EditError> {
+        self.check(track)?;
+        let len = track.events.len();
+        track
+            .events
+            .retain_mut(|event| self.apply_to_event(event.event_mut()).unwrap_or(false));
+        if self.key_signature
+            && let Some(key_signature) = &mut track.info_mut().key_signature
+        {
+            *key_signature = key_signature.transpose(self.semitones);
+        }
+        Ok(len - track.events.len())
+    }
+
+    /// Transpose every track in a file.
+    ///
+    /// Returns the number of events dropped.
+    ///
+    /// # Errors
+    /// If a note is out of range and [`OutOfRange::Fail`] is set. The file is unchanged.
+    pub fn apply_to_file(&self, file: &mut MidiFile<'_>) -> Result<usize, EditError> {
+        for track in file.tracks() {
+            self.check(track)?;
+        }
+        let mut dropped = 0;
+        for track in file.tracks_mut() {
+            dropped += self.apply_to_track(track)?;
+        }
+        Ok(dropped)
+    }
+
+    /// Transpose a stream of events, such as the output of [`MidiFile::into_events`].
+    ///
+    /// Dropped events are skipped. An out of range note yields an error in its place
+    /// when [`OutOfRange::Fail`] is set.
+    pub fn apply_to_events<'a, I>(
+        self,
+        events: I,
+    ) -> impl Iterator<Item = Result<Timed<LiveEvent<'a>>, EditError>>
+    where
+        I: IntoIterator<Item = Timed<LiveEvent<'a>>>,
+    {
+        events.into_iter().filter_map(move |mut timed| {
+            match self.apply_to_event(&mut timed.event) {
+                Ok(true) => Some(Ok(timed)),
+                Ok(false) => None,
+                Err(e) => Some(Err(e)),
+            }
+        })
+    }
+
+    /// Returns the note to transpose, if the event has one on a transposed channel
+    fn note_of<'e>(&self, event: &'e mut LiveEvent<'_>) -> Option<&'e mut Note> {
+        let msg = event.channel_voice_mut()?;
+        if !self.transposes_channel(msg.channel()) {
+            return None;
+        }
+        msg.event.note_mut()
+    }
+
+    fn check(&self, track: &Track<'_>) -> Result<(), EditError> {
+        if self.out_of_range == OutOfRange::Drop {
+            return Ok(());
+        }
+        for event in track.events() {
+            let Some(msg) = event.event().channel_voice() else {
+                continue;
+            };
+            if !self.transposes_channel(msg.channel()) {
+                continue;
+            }
+            if let Some(note) = msg.note()
+                && note.checked_transpose(self.semitones).is_none()
+            {
+                return Err(EditError::NoteOutOfRange {
+                    note,
+                    semitones: self.semitones,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn track_of(notes: &[(Channel, u8)]) -> Track<'static> {
+    let mut track = Track::new(alloc::vec::Vec::new());
+    for (tick, (channel, note)) in notes.iter().enumerate() {
+        let event = VoiceEvent::note_on(Note::from_databyte(*note).unwrap(), Velocity::MAX);
+        track.insert(tick as u32, channel.send_event(event).into());
+    }
+    track
+}
+
+#[cfg(test)]
+fn notes_of(track: &Track) -> alloc::vec::Vec<u8> {
+    track
+        .events()
+        .iter()
+        .filter_map(|e| Some(e.event().channel_voice()?.note()?.byte()))
+        .collect()
+}
+
+#[test]
+fn out_of_range_fails_or_drops() {
+    let mut track = track_of(&[(Channel::One, 60), (Channel::Two, 120)]);
+
+    let transpose = Transpose::new(10);
+    assert_eq!(
+        transpose.apply_to_track(&mut track),
+        Err(EditError::NoteOutOfRange {
+            note: Note::from_databyte(120).unwrap(),
+            semitones: 10
+        })
+    );
+    assert_eq!(notes_of(&track), [60, 120]);
+
+    let dropped = transpose
+        .on_out_of_range(OutOfRange::Drop)
+        .apply_to_track(&mut track)
+        .unwrap();
+    assert_eq!(dropped, 1);
+    assert_eq!(notes_of(&track), [70]);
+}
+
+#[test]
+fn skips_drums_by_default() {
+    let mut track = track_of(&[(Channel::Ten, 36), (Channel::Three, 36)]);
+    Transpose::new(-12).apply_to_track(&mut track).unwrap();
+    assert_eq!(notes_of(&track), [36, 24]);
+
+    Transpose::new(-12)
+        .with_drums(true)
+        .skip_channel(Channel::Three)
+        .apply_to_track(&mut track)
+        .unwrap();
+    assert_eq!(notes_of(&track), [24, 24]);
+}
+
+#[test]
+fn transpose_event_stream() {
+    let events = [
+        Timed::new(
+            0,
+            LiveEvent::from(
+                Channel::One.send_event(VoiceEvent::note_on(note!(C, 9), Velocity::MAX)),
+            ),
+        ),
+        Timed::new(10, LiveEvent::from(SystemRealTimeMessage::Start)),
+    ];
+    let results: alloc::vec::Vec<_> = Transpose::new(12).apply_to_events(events.clone()).collect();
+    assert!(results[0].is_err());
+    assert_eq!(results[1], Ok(events[1].clone()));
+}
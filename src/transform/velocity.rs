@@ -0,0 +1,366 @@
+use alloc::vec::Vec;
+
+use crate::{prelude::*, utils::SplitMix64};
+
+#[doc = r#"
+A response curve, mapping each velocity to a new one.
+
+A velocity of zero (a note off) always stays zero, and every other velocity
+maps to at least one, so curves never turn notes into note offs.
+
+# Example
+```rust
+# use midix::prelude::*;
+// a hard touch: playing softly still gives a strong note
+let curve = VelocityCurve::exponential(0.5);
+let soft = Velocity::new(32).unwrap();
+assert!(curve.apply(soft).byte() > 60);
+assert_eq!(curve.apply(Velocity::MAX), Velocity::MAX);
+```
+"#]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VelocityCurve {
+    table: [u8; 128],
+}
+
+impl Default for VelocityCurve {
+    /// The identity curve
+    fn default() -> Self {
+        Self::from_fn(|velocity| velocity)
+    }
+}
+
+impl VelocityCurve {
+    /// Scale velocities 1 to 127 onto `min` to `max`.
+    ///
+    /// `max` may be lower than `min`, to invert the response.
+    pub fn linear(min: Velocity, max: Velocity) -> Self {
+        let (min, max) = (min.byte().max(1) as f64, max.byte().max(1) as f64);
+        Self::from_fn(|velocity| {
+            let scaled = min + (velocity.byte() - 1) as f64 * (max - min) / 126.;
+            Velocity::new_unchecked((scaled + 0.5) as u8)
+        })
+    }
+
+    /// Raise velocities, as a fraction of 127, to a power.
+    ///
+    /// An exponent above one gives a softer response, and below one a harder response.
+    pub fn exponential(exponent: f64) -> Self {
+        let exponent = exponent.max(0.);
+        Self::from_fn(|velocity| {
+            let fraction = velocity.byte() as f64 / 127.;
+            let curved = 127. * libm::pow(fraction, exponent);
+            Velocity::new_unchecked((curved + 0.5) as u8)
+        })
+    }
+
+    /// Build a curve by calling `f` with every velocity from 1 to 127
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(Velocity) -> Velocity,
+    {
+        let mut table = [0; 128];
+        for (byte, value) in table.iter_mut().enumerate().skip(1) {
+            *value = f(Velocity::new_unchecked(byte as u8)).byte();
+        }
+        Self::from_table(table)
+    }
+
+    /// Build a curve from a table of the velocity for each velocity.
+    ///
+    /// Values above 127 are clamped.
+    pub fn from_table(mut table: [u8; 128]) -> Self {
+        table[0] = 0;
+        for value in &mut table[1..] {
+            *value = (*value).clamp(1, 127);
+        }
+        Self { table }
+    }
+
+    /// Returns the velocity for each velocity
+    pub fn table(&self) -> &[u8; 128] {
+        &self.table
+    }
+
+    /// Returns the new velocity for a velocity
+    pub fn apply(&self, velocity: Velocity) -> Velocity {
+        Velocity::new_unchecked(self.table[velocity.byte() as usize])
+    }
+}
+
+/// One step of a [`VelocityTransform`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum VelocityStage {
+    /// Map velocities through a curve
+    Curve(VelocityCurve),
+    /// Reduce the distance of velocities above `threshold` from it by `ratio`
+    Compress {
+        /// Velocities above this are compressed
+        threshold: Velocity,
+        /// How many times closer to the threshold they become
+        ratio: f64,
+    },
+    /// Increase the distance of velocities below `threshold` from it by `ratio`
+    Expand {
+        /// Velocities below this are expanded
+        threshold: Velocity,
+        /// How many times further from the threshold they become
+        ratio: f64,
+    },
+    /// Set every velocity to one value
+    Fixed(Velocity),
+    /// Change velocities randomly by up to this amount softer or louder
+    Spread(u8),
+}
+
+impl VelocityStage {
+    fn apply(&self, velocity: u8, rng: &mut SplitMix64) -> u8 {
+        let value = velocity as f64;
+        let changed = match self {
+            Self::Curve(curve) => return curve.apply(Velocity::new_unchecked(velocity)).byte(),
+            Self::Compress { threshold, ratio } => {
+                let threshold = threshold.byte() as f64;
+                if value <= threshold {
+                    return velocity;
+                }
+                threshold + (value - threshold) / ratio.max(1.)
+            }
+            Self::Expand { threshold, ratio } => {
+                let threshold = threshold.byte() as f64;
+                if value >= threshold {
+                    return velocity;
+                }
+                threshold - (threshold - value) * ratio.max(1.)
+            }
+            Self::Fixed(fixed) => fixed.byte() as f64,
+            Self::Spread(amount) => value + rng.spread(*amount as u32) as f64,
+        };
+        (changed + 0.5).clamp(1., 127.) as u8
+    }
+}
+
+#[doc = r#"
+Changes the velocity of note ons by passing them through a chain of [`VelocityStage`]s.
+
+Stages are applied in the order they are added.
+Note offs, including note ons with a velocity of zero, are never changed,
+and note ons never fall below a velocity of one.
+
+Random spread comes from a seeded generator, so the same seed
+applied to the same notes always gives the same result.
+
+A transform can run on a [`Track`], a [`MidiFile`], a stream of events,
+or a live [`MidiTarget`] with [`MidiTarget::map_velocity`].
+
+# Example
+```rust
+# use midix::prelude::*;
+let mut transform = VelocityTransform::new()
+    .with_curve(VelocityCurve::linear(Velocity::new(20).unwrap(), Velocity::MAX))
+    .with_compression(Velocity::new(100).unwrap(), 3.);
+
+assert_eq!(transform.apply(Velocity::new(1).unwrap()).byte(), 20);
+// 127 is compressed to a third of its distance above 100
+assert_eq!(transform.apply(Velocity::MAX).byte(), 109);
+assert_eq!(transform.apply(Velocity::ZERO), Velocity::ZERO);
+```
+"#]
+#[derive(Debug, Clone)]
+pub struct VelocityTransform {
+    stages: Vec<VelocityStage>,
+    rng: SplitMix64,
+}
+
+impl Default for VelocityTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VelocityTransform {
+    /// Create a transform that changes nothing until stages are added
+    pub const fn new() -> Self {
+        Self {
+            stages: Vec::new(),
+            rng: SplitMix64(0),
+        }
+    }
+
+    /// Seed the generator used by [`VelocityStage::Spread`]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = SplitMix64(seed);
+        self
+    }
+
+    /// Add a stage to the end of the chain
+    pub fn with_stage(mut self, stage: VelocityStage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Map velocities through a curve
+    pub fn with_curve(self, curve: VelocityCurve) -> Self {
+        self.with_stage(VelocityStage::Curve(curve))
+    }
+
+    /// Compress velocities above `threshold`, bringing them `ratio` times closer to it.
+    ///
+    /// Ratios below one are treated as one.
+    pub fn with_compression(self, threshold: Velocity, ratio: f64) -> Self {
+        self.with_stage(VelocityStage::Compress { threshold, ratio })
+    }
+
+    /// Expand velocities below `threshold`, moving them `ratio` times further from it.
+    ///
+    /// Ratios below one are treated as one.
+    pub fn with_expansion(self, threshold: Velocity, ratio: f64) -> Self {
+        self.with_stage(VelocityStage::Expand { threshold, ratio })
+    }
+
+    /// Set every velocity to one value
+    pub fn with_fixed(self, velocity: Velocity) -> Self {
+        self.with_stage(VelocityStage::Fixed(velocity))
+    }
+
+    /// Change velocities randomly by up to `amount` softer or louder
+    pub fn with_spread(self, amount: u8) -> Self {
+        self.with_stage(VelocityStage::Spread(amount))
+    }
+
+    /// Returns the stages, in the order they are applied
+    pub fn stages(&self) -> &[VelocityStage] {
+        &self.stages
+    }
+
+    /// Returns the new velocity of a note on
+    pub fn apply(&mut self, velocity: Velocity) -> Velocity {
+        if velocity.byte() == 0 {
+            return velocity;
+        }
+        let byte = self.stages.iter().fold(velocity.byte(), |byte, stage| {
+            stage.apply(byte, &mut self.rng)
+        });
+        Velocity::new_unchecked(byte)
+    }
+
+    /// Change the velocity of a message, if it is a note on.
+    ///
+    /// Returns true if the message was a note on.
+    pub fn apply_to_message(&mut self, msg: &mut ChannelVoiceMessage) -> bool {
+        match &mut msg.event {
+            VoiceEvent::NoteOn { velocity, .. } if velocity.byte() != 0 => {
+                *velocity = self.apply(*velocity);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Change the velocity of every note on in a track
+    pub fn apply_to_track(&mut self, track: &mut Track<'_>) {
+        for event in &mut track.events {
+            if let Some(msg) = event.event_mut().channel_voice_mut() {
+                self.apply_to_message(msg);
+            }
+        }
+    }
+
+    /// Change the velocity of every note on in a file
+    pub fn apply_to_file(&mut self, file: &mut MidiFile<'_>) {
+        for track in file.tracks_mut() {
+            self.apply_to_track(track);
+        }
+    }
+
+    /// Change the velocity of every note on in a stream of events, such as the output of [`MidiFile::into_events`]
+    pub fn apply_to_events<'a, I>(mut self, events: I) -> impl Iterator<Item = Timed<LiveEvent<'a>>>
+    where
+        I: IntoIterator<Item = Timed<LiveEvent<'a>>>,
+    {
+        events.into_iter().map(move |mut timed| {
+            if let Some(msg) = timed.event.channel_voice_mut() {
+                self.apply_to_message(msg);
+            }
+            timed
+        })
+    }
+}
+
+#[cfg(test)]
+fn velocities(transform: &mut VelocityTransform, bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .map(|byte| transform.apply(Velocity::new_unchecked(*byte)).byte())
+        .collect()
+}
+
+#[test]
+fn curves() {
+    let linear = VelocityCurve::linear(Velocity::new_unchecked(40), Velocity::new_unchecked(100));
+    assert_eq!(linear.table()[..2], [0, 40]);
+    assert_eq!(linear.table()[64], 70);
+    assert_eq!(linear.table()[127], 100);
+
+    let inverted = VelocityCurve::linear(Velocity::MAX, Velocity::new_unchecked(1));
+    assert_eq!(inverted.table()[1], 127);
+    assert_eq!(inverted.table()[127], 1);
+
+    let soft = VelocityCurve::exponential(2.);
+    assert_eq!(soft.table()[1], 1);
+    assert_eq!(soft.table()[64], 32);
+    assert_eq!(soft.table()[127], 127);
+    assert!(soft.table()[1..].windows(2).all(|pair| pair[0] <= pair[1]));
+
+    assert_eq!(VelocityCurve::default().table()[90], 90);
+}
+
+#[test]
+fn compression_and_expansion() {
+    let threshold = Velocity::new_unchecked(64);
+    let mut compress = VelocityTransform::new().with_compression(threshold, 2.);
+    assert_eq!(
+        velocities(&mut compress, &[30, 64, 100, 127]),
+        [30, 64, 82, 96]
+    );
+
+    let mut expand = VelocityTransform::new().with_expansion(threshold, 2.);
+    assert_eq!(
+        velocities(&mut expand, &[10, 40, 64, 100]),
+        [1, 16, 64, 100]
+    );
+
+    let mut fixed = VelocityTransform::new()
+        .with_fixed(Velocity::new_unchecked(90))
+        .with_spread(5);
+    for velocity in velocities(&mut fixed, &[1, 50, 127]) {
+        assert!((85..=95).contains(&velocity));
+    }
+}
+
+#[test]
+fn only_note_ons_change() {
+    let mut track = Track::new(Vec::new());
+    let on = VoiceEvent::note_on(note!(C, 4), Velocity::new_unchecked(30));
+    let off = VoiceEvent::note_off(note!(C, 4), Velocity::new_unchecked(30));
+    let silent = VoiceEvent::note_on(note!(C, 4), Velocity::ZERO);
+    track.insert(0, Channel::One.send_event(on).into());
+    track.insert(10, Channel::One.send_event(off).into());
+    track.insert(20, Channel::One.send_event(silent).into());
+
+    VelocityTransform::new()
+        .with_fixed(Velocity::MAX)
+        .apply_to_track(&mut track);
+    let bytes: Vec<_> = track
+        .events()
+        .iter()
+        .map(|e| {
+            e.event()
+                .channel_voice()
+                .unwrap()
+                .velocity()
+                .unwrap()
+                .byte()
+        })
+        .collect();
+    assert_eq!(bytes, [127, 30, 0]);
+}
@@ -5,3 +5,28 @@ pub(crate) fn check_u7(byte: u8) -> Result<u8, ParseError> {
         .then_some(byte)
         .ok_or(ParseError::InvalidDataByte(byte))
 }
+
+/// A small, seedable random number generator (SplitMix64).
+///
+/// It is not suitable for cryptography, but is fast, `no_std` and reproducible across platforms.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `-range..=range`
+    pub(crate) fn spread(&mut self, range: u32) -> i64 {
+        if range == 0 {
+            return 0;
+        }
+        let span = range as u64 * 2 + 1;
+        (self.next_u64() % span) as i64 - range as i64
+    }
+}
@@ -59,7 +59,7 @@ impl fmt::Display for Velocity {
 }
 
 /// The musical analog of the digital velocity
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Dynamic {
     /// no sound
     Off,
@@ -99,6 +99,19 @@ impl fmt::Display for Dynamic {
 }
 
 impl Dynamic {
+    /// Every dynamic, from [`Dynamic::Off`] to [`Dynamic::Fortississimo`]
+    pub const ALL: [Dynamic; 9] = [
+        Dynamic::Off,
+        Dynamic::Pianississimo,
+        Dynamic::Pianissimo,
+        Dynamic::Piano,
+        Dynamic::MezzoPiano,
+        Dynamic::MezzoForte,
+        Dynamic::Forte,
+        Dynamic::Fortissimo,
+        Dynamic::Fortississimo,
+    ];
+
     /// No sound
     pub const fn off() -> Self {
         Self::Off
@@ -144,3 +157,116 @@ impl Dynamic {
         Self::Fortississimo
     }
 }
+
+#[doc = r#"
+A configurable mapping between velocities and [`Dynamic`]s.
+
+Each dynamic starts at a lowest velocity, and has a velocity used when writing it.
+The default table matches [`Velocity::dynamic`], writing each dynamic
+in the middle of its range.
+
+A velocity of zero is always [`Dynamic::Off`].
+
+# Example
+```rust
+# use midix::prelude::*;
+// a light touch: forte from 70 upwards
+let table = DynamicTable::default()
+    .with_lower_bound(Dynamic::f(), 70)
+    .with_velocity(Dynamic::f(), Velocity::new(75).unwrap());
+
+assert_eq!(table.dynamic(Velocity::new(72).unwrap()), Dynamic::f());
+assert_eq!(table.velocity(Dynamic::f()).byte(), 75);
+// the fixed buckets of `Velocity::dynamic` are unchanged
+assert_eq!(Velocity::new(72).unwrap().dynamic(), Dynamic::mf());
+```
+"#]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(bevy::reflect::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicTable {
+    lower_bounds: [u8; 9],
+    velocities: [u8; 9],
+}
+
+impl Default for DynamicTable {
+    fn default() -> Self {
+        Self {
+            lower_bounds: [0, 1, 16, 32, 48, 64, 80, 96, 112],
+            velocities: [0, 8, 24, 40, 56, 72, 88, 104, 120],
+        }
+    }
+}
+
+impl DynamicTable {
+    /// Create a table from the lowest velocity of each dynamic, from ppp to fff,
+    /// writing each dynamic at its lowest velocity.
+    ///
+    /// Bounds above 127 are clamped.
+    pub fn from_lower_bounds(bounds: [u8; 8]) -> Self {
+        let mut table = Self::default();
+        for (dynamic, bound) in Dynamic::ALL[1..].iter().zip(bounds) {
+            table = table
+                .with_lower_bound(*dynamic, bound)
+                .with_velocity(*dynamic, Velocity::new_unchecked(bound.clamp(1, 127)));
+        }
+        table
+    }
+
+    /// Set the lowest velocity of a dynamic.
+    ///
+    /// [`Dynamic::Off`] always starts at zero, and other dynamics at one or more.
+    pub fn with_lower_bound(mut self, dynamic: Dynamic, bound: u8) -> Self {
+        if dynamic != Dynamic::Off {
+            self.lower_bounds[dynamic as usize] = bound.clamp(1, 127);
+        }
+        self
+    }
+
+    /// Set the velocity written for a dynamic.
+    ///
+    /// [`Dynamic::Off`] is always written as zero.
+    pub fn with_velocity(mut self, dynamic: Dynamic, velocity: Velocity) -> Self {
+        if dynamic != Dynamic::Off {
+            self.velocities[dynamic as usize] = velocity.byte().max(1);
+        }
+        self
+    }
+
+    /// Returns the lowest velocity of a dynamic
+    pub fn lower_bound(&self, dynamic: Dynamic) -> u8 {
+        self.lower_bounds[dynamic as usize]
+    }
+
+    /// Returns the dynamic of a velocity.
+    ///
+    /// This is the loudest dynamic whose lower bound is at or below the velocity.
+    pub fn dynamic(&self, velocity: Velocity) -> Dynamic {
+        if velocity.byte() == 0 {
+            return Dynamic::Off;
+        }
+        Dynamic::ALL
+            .iter()
+            .rev()
+            .find(|dynamic| self.lower_bound(**dynamic) <= velocity.byte())
+            .copied()
+            .unwrap_or(Dynamic::Pianississimo)
+    }
+
+    /// Returns the velocity written for a dynamic
+    pub fn velocity(&self, dynamic: Dynamic) -> Velocity {
+        Velocity::new_unchecked(self.velocities[dynamic as usize])
+    }
+}
+
+#[test]
+fn default_table_matches_velocity_dynamic() {
+    let table = DynamicTable::default();
+    for byte in 0..=127 {
+        let velocity = Velocity::new_unchecked(byte);
+        assert_eq!(table.dynamic(velocity), velocity.dynamic());
+    }
+    for dynamic in Dynamic::ALL {
+        assert_eq!(table.velocity(dynamic).dynamic(), dynamic);
+    }
+}